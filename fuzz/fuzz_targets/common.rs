@@ -0,0 +1,58 @@
+use arbitrary::Arbitrary;
+use rust_rectangle_dividing::axis::Axis;
+use rust_rectangle_dividing::axis_aligned_rectangle::AxisAlignedRectangle;
+use rust_rectangle_dividing::emission_order::EmissionOrder;
+use rust_rectangle_dividing::point::Point;
+use rust_rectangle_dividing::rectangle::Rectangle;
+
+/// An arbitrary dividing problem. the values are raw, so NaN, infinities, zero and negative sizes all occur
+#[derive(Debug, Arbitrary)]
+pub struct Input {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+    pub weights: Vec<f64>,
+    pub aspect_ratio: f64,
+    pub vertical: bool,
+    pub order: u8,
+}
+
+impl Input {
+    pub fn container(&self) -> AxisAlignedRectangle<f64> {
+        AxisAlignedRectangle::new(
+            &Point::new(self.x, self.y),
+            &Rectangle::new(self.width, self.height),
+        )
+    }
+
+    pub fn axis(&self) -> Axis {
+        match self.vertical {
+            true => Axis::Vertical,
+            false => Axis::Horizontal,
+        }
+    }
+
+    pub fn order(&self) -> EmissionOrder {
+        match self.order % 4 {
+            0 => EmissionOrder::Forward,
+            1 => EmissionOrder::Boustrophedon,
+            2 => EmissionOrder::Reverse,
+            _ => EmissionOrder::CenterOut,
+        }
+    }
+
+    /// whether the invariants are expected to hold: finite, reasonably sized values and positive weights.
+    /// other inputs only have to not crash
+    pub fn is_well_formed(&self) -> bool {
+        let in_range = |v: f64, min: f64| v.is_finite() && v >= min && v.abs() < 1e6;
+        in_range(self.x, -1e6)
+            && in_range(self.y, -1e6)
+            && in_range(self.width, 1.0)
+            && in_range(self.height, 1.0)
+            && in_range(self.aspect_ratio, 1e-3)
+            && !self.weights.is_empty()
+            && self.weights.len() <= 256
+            && self.weights.iter().all(|w| in_range(*w, 1e-3))
+    }
+}
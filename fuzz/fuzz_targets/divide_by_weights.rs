@@ -0,0 +1,40 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use rust_rectangle_dividing::dividing::Dividing;
+use rust_rectangle_dividing::rectangle::RectangleSize;
+use rust_rectangle_dividing::testing::Tolerances;
+
+mod common;
+
+fuzz_target!(|input: common::Input| {
+    if input.weights.len() > 4096 {
+        return;
+    }
+    let container = input.container();
+    let slices = container.divide_by_weights_and_axis(&input.weights, input.axis());
+    let grid = match input.vertical {
+        true => container.divide_vertical_then_horizontal_with_weights(
+            &input.weights,
+            input.aspect_ratio,
+            input.order % 2 == 1,
+        ),
+        false => container.divide_horizontal_then_vertical_with_weights(
+            &input.weights,
+            input.aspect_ratio,
+            input.order % 2 == 1,
+        ),
+    };
+    if !input.is_well_formed() {
+        return;
+    }
+    let tolerances = Tolerances {
+        area: container.width() * container.height() * 1e-9,
+        weight: 1e-6,
+        ..Tolerances::default()
+    };
+    for divided in [slices, grid] {
+        tolerances.assert_weights_dividing(&container, &divided, &input.weights);
+        tolerances.assert_no_overlaps(&container, &divided);
+    }
+});
@@ -0,0 +1,31 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use rust_rectangle_dividing::dividing::Dividing;
+use rust_rectangle_dividing::rectangle::RectangleSize;
+use rust_rectangle_dividing::testing::Tolerances;
+
+mod common;
+
+fuzz_target!(|input: common::Input| {
+    if input.weights.len() > 4096 {
+        return;
+    }
+    let container = input.container();
+    let divided = container.divide_strips(
+        &input.weights,
+        input.axis(),
+        input.aspect_ratio,
+        input.order(),
+    );
+    if !input.is_well_formed() {
+        return;
+    }
+    let tolerances = Tolerances {
+        area: container.width() * container.height() * 1e-9,
+        weight: 1e-6,
+        ..Tolerances::default()
+    };
+    tolerances.assert_weights_dividing(&container, &divided, &input.weights);
+    tolerances.assert_no_overlaps(&container, &divided);
+});
@@ -0,0 +1,444 @@
+//! Weight-accuracy guarantee mode.
+//!
+//! Dividing is exact up to float rounding, but snapping the cuts to pixels (or any other step) moves area
+//! between neighbouring cells. Snapping each cut from its exact position, rather than rounding the sizes,
+//! keeps the error from accumulating along a strip; the cuts are then moved a step at a time to spread the
+//! remaining error over the cells, and the result is checked against a tolerance.
+use num_traits::{Float, Num, NumAssignOps, NumOps};
+
+use crate::area::Area;
+use crate::axis::Axis;
+use crate::axis_aligned_rectangle::AxisAlignedRectangle;
+use crate::component::Component;
+use crate::emission_order::EmissionOrder;
+use crate::layout::Layout;
+use crate::point::Point;
+use crate::rectangle::{Rectangle, RectangleSize};
+use crate::tolerance::Tolerance;
+use crate::weight::normalize_weights;
+
+/// A cell whose share of the container's area misses its normalized weight by more than the tolerance
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct WeightAccuracyError<T> {
+    /// the index of the worst cell (and of its weight)
+    pub index: usize,
+    /// the share of the container's area the cell covers, minus its normalized weight
+    pub error: T,
+    pub tolerance: Tolerance<T>,
+}
+
+impl<T> std::fmt::Display for WeightAccuracyError<T>
+where
+    T: std::fmt::Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "the area of cell {} is off its weight by {} ({})",
+            self.index, self.error, self.tolerance
+        )
+    }
+}
+
+impl<T> std::error::Error for WeightAccuracyError<T> where T: std::fmt::Debug + std::fmt::Display {}
+
+/// the share of the container's area each cell covers, minus its normalized weight
+pub fn area_errors<T, C>(cells: &[C], container_area: T, weights: &[T]) -> Vec<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps + for<'a> std::iter::Sum<&'a T>,
+    C: Area<T>,
+{
+    cells
+        .iter()
+        .zip(normalize_weights(weights).iter())
+        .map(|(cell, weight)| cell.area() / container_area - *weight)
+        .collect()
+}
+
+/// check that every cell's share of the container's area is within the tolerance of its normalized weight,
+/// reporting the worst cell otherwise
+pub fn check_weight_accuracy<T, C>(
+    cells: &[C],
+    container_area: T,
+    weights: &[T],
+    tolerance: &Tolerance<T>,
+) -> Result<(), WeightAccuracyError<T>>
+where
+    T: Copy + Num + NumAssignOps + NumOps + PartialOrd + for<'a> std::iter::Sum<&'a T>,
+    C: Area<T>,
+{
+    let mut worst: Option<WeightAccuracyError<T>> = None;
+    for (index, (cell, weight)) in cells
+        .iter()
+        .zip(normalize_weights(weights).iter())
+        .enumerate()
+    {
+        let share = cell.area() / container_area;
+        if tolerance.approx_eq(share, *weight) {
+            continue;
+        }
+        let magnitude = match share < *weight {
+            true => *weight - share,
+            false => share - *weight,
+        };
+        if worst.as_ref().is_none_or(|w| magnitude > w.error) {
+            worst = Some(WeightAccuracyError {
+                index,
+                error: magnitude,
+                tolerance: *tolerance,
+            });
+        }
+    }
+    match worst {
+        Some(error) => Err(error),
+        None => Ok(()),
+    }
+}
+
+/// The cuts of a strip layout along its two axes: the edges of the strips, and the cuts inside each strip.
+/// `members[s][j]` is the cell between `cuts[s][j]` and `cuts[s][j + 1]` of the s-th strip from the start edge
+struct StripCuts<T> {
+    edges: Vec<T>,
+    cuts: Vec<Vec<T>>,
+    members: Vec<Vec<usize>>,
+}
+
+impl<T> StripCuts<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps + Float,
+{
+    /// the cuts of the cells of a strip layout, in the order of their position
+    fn new(cells: &[AxisAlignedRectangle<T>], strips: &[usize], primary_axis: Axis) -> Self {
+        let span = |cell: &AxisAlignedRectangle<T>, axis: Axis| match axis {
+            Axis::Vertical => (cell.x(), cell.x() + cell.width()),
+            Axis::Horizontal => (cell.y(), cell.y() + cell.height()),
+        };
+        let mut by_strip: Vec<Vec<usize>> = Vec::new();
+        for (i, strip) in strips.iter().enumerate() {
+            if by_strip.len() <= *strip {
+                by_strip.resize(*strip + 1, Vec::new());
+            }
+            by_strip[*strip].push(i);
+        }
+        by_strip.retain(|members| !members.is_empty());
+        let start = |i: &usize, axis: Axis| span(&cells[*i], axis).0;
+        let compare = |a: T, b: T| a.partial_cmp(&b).unwrap_or(std::cmp::Ordering::Equal);
+        by_strip.sort_by(|a, b| compare(start(&a[0], primary_axis), start(&b[0], primary_axis)));
+        let mut edges = Vec::with_capacity(by_strip.len() + 1);
+        let mut cuts = Vec::with_capacity(by_strip.len());
+        for members in &mut by_strip {
+            members.sort_by(|a, b| {
+                compare(
+                    start(a, primary_axis.opposite()),
+                    start(b, primary_axis.opposite()),
+                )
+            });
+            edges.push(start(&members[0], primary_axis));
+            let mut strip_cuts: Vec<T> = members
+                .iter()
+                .map(|i| start(i, primary_axis.opposite()))
+                .collect();
+            if let Some(last) = members.last() {
+                strip_cuts.push(span(&cells[*last], primary_axis.opposite()).1);
+            }
+            cuts.push(strip_cuts);
+        }
+        if let Some(last) = by_strip.last().and_then(|members| members.first()) {
+            edges.push(span(&cells[*last], primary_axis).1);
+        }
+        Self {
+            edges,
+            cuts,
+            members: by_strip,
+        }
+    }
+
+    /// the cells, the n-th one for the n-th weight
+    fn cells(&self, primary_axis: Axis) -> Vec<AxisAlignedRectangle<T>> {
+        let mut cells = vec![
+            AxisAlignedRectangle::new(
+                &Point::new(T::zero(), T::zero()),
+                &Rectangle::new(T::zero(), T::zero())
+            );
+            self.members.iter().map(|m| m.len()).sum()
+        ];
+        for (s, members) in self.members.iter().enumerate() {
+            let (start, end) = (self.edges[s], self.edges[s + 1]);
+            for (j, i) in members.iter().enumerate() {
+                let (from, to) = (self.cuts[s][j], self.cuts[s][j + 1]);
+                cells[*i] = match primary_axis {
+                    Axis::Vertical => AxisAlignedRectangle::new(
+                        &Point::new(start, from),
+                        &Rectangle::new(end - start, to - from),
+                    ),
+                    Axis::Horizontal => AxisAlignedRectangle::new(
+                        &Point::new(from, start),
+                        &Rectangle::new(to - from, end - start),
+                    ),
+                };
+            }
+        }
+        cells
+    }
+
+    /// the worst and the total error of the cells
+    fn score(&self, targets: &[T]) -> (T, T) {
+        let mut worst = T::zero();
+        let mut total = T::zero();
+        for (s, members) in self.members.iter().enumerate() {
+            let thickness = self.edges[s + 1] - self.edges[s];
+            for (j, i) in members.iter().enumerate() {
+                let area = thickness * (self.cuts[s][j + 1] - self.cuts[s][j]);
+                let error = (area - targets[*i]).abs();
+                worst = worst.max(error);
+                total += error;
+            }
+        }
+        (worst, total)
+    }
+
+    /// place the cuts inside the s-th strip on the grid of `step` so that the worst area error of its cells is
+    /// the smallest for the thickness of the strip: the area the strip misses (or has over) its targets is shared
+    /// evenly, and the steps left over go to the cells the furthest below their targets
+    fn apportion(&mut self, s: usize, targets: &[T], step: T) {
+        let members = &self.members[s];
+        let cuts = &self.cuts[s];
+        let (start, end) = (cuts[0], cuts[cuts.len() - 1]);
+        let units = ((end - start) / step).round();
+        let unit_area = (self.edges[s + 1] - self.edges[s]) * step;
+        let total = members.iter().fold(T::zero(), |sum, i| sum + targets[*i]);
+        let count = members.iter().fold(T::zero(), |sum, _| sum + T::one());
+        // the steps each cell would need, in whole and fractions
+        let needed: Vec<T> = members
+            .iter()
+            .map(|i| match (unit_area > T::zero(), total > T::zero()) {
+                (true, _) => targets[*i] / unit_area,
+                (false, true) => targets[*i] / total * units,
+                (false, false) => units / count,
+            })
+            .collect();
+        let shift = (needed.iter().fold(T::zero(), |sum, n| sum + *n) - units) / count;
+        let wanted: Vec<T> = needed.iter().map(|n| (*n - shift).max(T::zero())).collect();
+        let mut steps: Vec<T> = wanted.iter().map(|w| w.floor()).collect();
+        let mut assigned = steps.iter().fold(T::zero(), |sum, n| sum + *n);
+        let furthest = |steps: &[T], below: bool| {
+            (0..steps.len())
+                .filter(|j| below || steps[*j] >= T::one())
+                .map(|j| (j, steps[j] - wanted[j]))
+                .reduce(|a, b| match (b.1 < a.1) == below {
+                    true => b,
+                    false => a,
+                })
+                .map(|(j, _)| j)
+        };
+        while assigned < units {
+            let Some(j) = furthest(&steps, true) else {
+                break;
+            };
+            steps[j] += T::one();
+            assigned += T::one();
+        }
+        while assigned > units {
+            let Some(j) = furthest(&steps, false) else {
+                break;
+            };
+            steps[j] -= T::one();
+            assigned -= T::one();
+        }
+        let mut position = start;
+        let mut placed = vec![start];
+        for n in &steps[..steps.len() - 1] {
+            position += *n * step;
+            placed.push(position);
+        }
+        placed.push(end);
+        self.cuts[s] = placed;
+    }
+
+    /// share the cells of every strip by `apportion`, and then move the strip edges a step at a time while it makes
+    /// the worst (and then the total) error of the cells smaller, until the worst error is within `enough`
+    fn adjust(&mut self, targets: &[T], step: T, enough: T) {
+        for s in 0..self.members.len() {
+            self.apportion(s, targets, step);
+        }
+        let mut score = self.score(targets);
+        while score.0 > enough {
+            let mut best: Option<((T, T), usize, T)> = None;
+            for k in 1..self.edges.len().saturating_sub(1) {
+                for delta in [step, T::zero() - step] {
+                    let edge = self.edges[k] + delta;
+                    if edge < self.edges[k - 1] || self.edges[k + 1] < edge {
+                        continue;
+                    }
+                    let (edges, before, after) = (
+                        self.edges.clone(),
+                        self.cuts[k - 1].clone(),
+                        self.cuts[k].clone(),
+                    );
+                    self.edges[k] = edge;
+                    self.apportion(k - 1, targets, step);
+                    self.apportion(k, targets, step);
+                    let candidate = self.score(targets);
+                    if best.as_ref().map_or(score, |(s, _, _)| *s) > candidate {
+                        best = Some((candidate, k, edge));
+                    }
+                    (self.edges, self.cuts[k - 1], self.cuts[k]) = (edges, before, after);
+                }
+            }
+            let Some((candidate, k, edge)) = best else {
+                break;
+            };
+            self.edges[k] = edge;
+            self.apportion(k - 1, targets, step);
+            self.apportion(k, targets, step);
+            score = candidate;
+        }
+    }
+}
+
+/// round a value to the nearest multiple of `step`
+fn snap<T>(value: T, step: T) -> T
+where
+    T: Float,
+{
+    (value / step).round() * step
+}
+
+/// dividing into strips (see `Dividing::divide_strips`) with every cut on the grid of `step`. the strip edges
+/// start snapped from their exact positions and are moved a step at a time toward the areas of the weights,
+/// and the cuts inside each strip share the area the strip misses (or has over) its weights evenly among its cells,
+/// so that the error of snapping and of the strip grouping is spread over the cells rather than left where it fell.
+/// the worst cell is reported when its share of the area is still off its normalized weight by more than
+/// the tolerance once no single step of an edge makes the layout better. a non-positive step leaves the cuts exact
+pub fn divide_strips_accurate<T>(
+    rect: &AxisAlignedRectangle<T>,
+    weights: &[T],
+    primary_axis: Axis,
+    aspect_ratio: T,
+    order: EmissionOrder,
+    step: T,
+    tolerance: &Tolerance<T>,
+) -> Result<Layout<T>, WeightAccuracyError<T>>
+where
+    T: Copy + Num + NumAssignOps + NumOps + Float + for<'a> std::iter::Sum<&'a T>,
+{
+    let exact = Layout::divide_strips(rect, weights, primary_axis, aspect_ratio, order);
+    let strips = exact.strips().map(|s| s.to_vec()).unwrap_or_default();
+    if step <= T::zero() || exact.is_empty() {
+        check_weight_accuracy(exact.cells(), rect.area(), weights, tolerance)?;
+        return Ok(Layout::new(exact.into_cells()).with_strips(strips));
+    }
+    let mut cuts = StripCuts::new(exact.cells(), &strips, primary_axis);
+    cuts.edges.iter_mut().for_each(|e| *e = snap(*e, step));
+    for strip_cuts in &mut cuts.cuts {
+        strip_cuts.iter_mut().for_each(|c| *c = snap(*c, step));
+    }
+    let container_area = (cuts.edges[cuts.edges.len() - 1] - cuts.edges[0])
+        * (cuts.cuts[0][cuts.cuts[0].len() - 1] - cuts.cuts[0][0]);
+    let targets: Vec<T> = normalize_weights(weights)
+        .iter()
+        .map(|w| *w * container_area)
+        .collect();
+    cuts.adjust(&targets, step, tolerance.absolute * container_area);
+    let cells = cuts.cells(primary_axis);
+    check_weight_accuracy(&cells, container_area, weights, tolerance)?;
+    Ok(Layout::new(cells).with_strips(strips))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::verify_tiling;
+
+    #[test]
+    fn test_check_weight_accuracy() {
+        let cells = vec![Rectangle::new(1.0, 1.0), Rectangle::new(3.0, 1.0)];
+        assert_eq!(area_errors(&cells, 4.0, &[1.0, 3.0]), vec![0.0, 0.0]);
+        assert!(check_weight_accuracy(&cells, 4.0, &[1.0, 3.0], &Tolerance::exact()).is_ok());
+        assert_eq!(
+            check_weight_accuracy(&cells, 4.0, &[1.0, 1.0], &Tolerance::absolute(0.1)),
+            Err(WeightAccuracyError {
+                index: 0,
+                error: 0.25,
+                tolerance: Tolerance::absolute(0.1)
+            })
+        );
+        assert!(
+            check_weight_accuracy(&cells, 4.0, &[1.0, 1.0], &Tolerance::absolute(0.25)).is_ok()
+        );
+    }
+
+    #[test]
+    fn test_divide_strips_accurate() {
+        let rect = AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(100.0, 100.0));
+        let weights = vec![3.0, 3.0, 3.0, 1.0];
+        let layout = divide_strips_accurate(
+            &rect,
+            &weights,
+            Axis::Vertical,
+            1.0,
+            EmissionOrder::Forward,
+            1.0,
+            &Tolerance::absolute(0.01),
+        )
+        .unwrap();
+        assert_eq!(layout.len(), weights.len());
+        assert!(layout.strips().is_some());
+        for cell in &layout {
+            assert_eq!(*cell, cell.round());
+        }
+
+        let error = divide_strips_accurate(
+            &rect,
+            &weights,
+            Axis::Vertical,
+            1.0,
+            EmissionOrder::Forward,
+            10.0,
+            &Tolerance::absolute(0.01),
+        )
+        .unwrap_err();
+        assert!(error.error > 0.01);
+        assert_eq!(error.tolerance, Tolerance::absolute(0.01));
+        assert_eq!(
+            error.to_string(),
+            format!(
+                "the area of cell {} is off its weight by {} (tolerance 0.01 absolute, 0 relative)",
+                error.index, error.error
+            )
+        );
+    }
+
+    #[test]
+    fn test_divide_strips_accurate_moves_cuts() {
+        let rect = AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(10.0, 60.0));
+        let weights = vec![5.0, 2.0, 2.0, 4.0, 9.0];
+        // rounding the exact cells is too far off
+        let exact =
+            Layout::divide_strips(&rect, &weights, Axis::Vertical, 1.0, EmissionOrder::Forward);
+        let rounded: Vec<AxisAlignedRectangle<f64>> =
+            exact.iter().map(|cell| cell.round()).collect();
+        assert!(
+            check_weight_accuracy(&rounded, rect.area(), &weights, &Tolerance::absolute(0.01))
+                .is_err()
+        );
+        // moving the cuts spreads the error
+        let layout = divide_strips_accurate(
+            &rect,
+            &weights,
+            Axis::Vertical,
+            1.0,
+            EmissionOrder::Forward,
+            1.0,
+            &Tolerance::absolute(0.01),
+        )
+        .unwrap();
+        for cell in &layout {
+            assert_eq!(*cell, cell.round());
+        }
+        assert_eq!(
+            verify_tiling(&rect, layout.cells(), &Tolerance::exact()),
+            Ok(())
+        );
+    }
+}
@@ -0,0 +1,111 @@
+//! `approx` integration.
+//!
+//! `AbsDiffEq`, `RelativeEq` and `UlpsEq` compare the components of the geometry types,
+//! so `assert_relative_eq!` and friends work on divided rectangles directly.
+use ::approx::{AbsDiffEq, RelativeEq, UlpsEq};
+use num_traits::{Num, NumAssignOps, NumOps};
+
+use crate::axis_aligned_rectangle::AxisAlignedRectangle;
+use crate::component::Component;
+use crate::point::Point;
+use crate::rectangle::{Rectangle, RectangleSize};
+
+macro_rules! impl_approx {
+    ($t:ident, $($component:ident),*) => {
+        impl<T> AbsDiffEq for $t<T>
+        where
+            T: Copy + Num + NumAssignOps + NumOps + AbsDiffEq,
+            T::Epsilon: Copy,
+        {
+            type Epsilon = T::Epsilon;
+
+            fn default_epsilon() -> Self::Epsilon {
+                T::default_epsilon()
+            }
+
+            fn abs_diff_eq(&self, other: &Self, epsilon: Self::Epsilon) -> bool {
+                $(self.$component().abs_diff_eq(&other.$component(), epsilon))&&*
+            }
+        }
+
+        impl<T> RelativeEq for $t<T>
+        where
+            T: Copy + Num + NumAssignOps + NumOps + RelativeEq,
+            T::Epsilon: Copy,
+        {
+            fn default_max_relative() -> Self::Epsilon {
+                T::default_max_relative()
+            }
+
+            fn relative_eq(
+                &self,
+                other: &Self,
+                epsilon: Self::Epsilon,
+                max_relative: Self::Epsilon,
+            ) -> bool {
+                $(self.$component().relative_eq(&other.$component(), epsilon, max_relative))&&*
+            }
+        }
+
+        impl<T> UlpsEq for $t<T>
+        where
+            T: Copy + Num + NumAssignOps + NumOps + UlpsEq,
+            T::Epsilon: Copy,
+        {
+            fn default_max_ulps() -> u32 {
+                T::default_max_ulps()
+            }
+
+            fn ulps_eq(&self, other: &Self, epsilon: Self::Epsilon, max_ulps: u32) -> bool {
+                $(self.$component().ulps_eq(&other.$component(), epsilon, max_ulps))&&*
+            }
+        }
+    };
+}
+
+impl_approx!(Point, x, y);
+impl_approx!(Rectangle, width, height);
+impl_approx!(AxisAlignedRectangle, x, y, width, height);
+
+#[cfg(test)]
+mod tests {
+    use ::approx::{assert_abs_diff_eq, assert_relative_eq, assert_relative_ne, assert_ulps_eq};
+
+    use super::*;
+    use crate::dividing::Dividing;
+
+    #[test]
+    fn test_approx() {
+        let p = Point::new(0.1 + 0.2, 1.0);
+        assert_relative_eq!(p, Point::new(0.3, 1.0));
+        assert_ulps_eq!(p, Point::new(0.3, 1.0));
+        assert_relative_ne!(p, Point::new(0.3, 1.001));
+        assert_abs_diff_eq!(
+            Rectangle::new(2.0, 3.0),
+            Rectangle::new(2.05, 3.0),
+            epsilon = 0.1
+        );
+
+        let rect = AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(1.0, 1.0));
+        let divided =
+            rect.divide_by_weights_and_axis(&[1.0, 1.0, 1.0], crate::axis::Axis::Vertical);
+        assert_relative_eq!(
+            divided[1],
+            AxisAlignedRectangle::new(&Point::new(1.0 / 3.0, 0.0), &Rectangle::new(1.0 / 3.0, 1.0))
+        );
+        assert_relative_eq!(
+            divided[..],
+            [
+                AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(1.0 / 3.0, 1.0)),
+                AxisAlignedRectangle::new(
+                    &Point::new(1.0 / 3.0, 0.0),
+                    &Rectangle::new(1.0 / 3.0, 1.0)
+                ),
+                AxisAlignedRectangle::new(
+                    &Point::new(2.0 / 3.0, 0.0),
+                    &Rectangle::new(1.0 / 3.0, 1.0)
+                ),
+            ][..]
+        );
+    }
+}
@@ -1,4 +1,4 @@
-pub(crate) trait AspectRatio<T> {
-    #[allow(dead_code)]
+/// Width / height
+pub trait AspectRatio<T> {
     fn aspect_ratio(&self) -> T;
 }
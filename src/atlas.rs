@@ -0,0 +1,312 @@
+//! Texture atlases: named sizes packed into one container, e.g. sprite sheets.
+use std::collections::BTreeMap;
+
+use num_traits::{Num, NumAssignOps, NumOps};
+
+use crate::axis_aligned_rectangle::AxisAlignedRectangle;
+use crate::component::Component;
+use crate::packing::{GuillotinePacker, Packer};
+use crate::point::Point;
+use crate::rectangle::{Rectangle, RectangleSize};
+
+/// How the atlas grows when the items do not fit
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub enum Growth<T> {
+    /// the atlas keeps its size
+    #[default]
+    Fixed,
+    /// the shorter side (the width of a square) doubles, so a power-of-two size stays a power of two
+    PowerOfTwo,
+    /// the shorter side (the width of a square) grows by the step. a non-positive step does not grow
+    By(T),
+}
+
+/// Where an item went in the atlas
+#[derive(Debug, PartialEq, Clone)]
+pub struct AtlasEntry<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps,
+{
+    /// the item itself, of the size it was added with
+    pub rect: AxisAlignedRectangle<T>,
+    /// the item and its bleed around it
+    pub bleed_rect: AxisAlignedRectangle<T>,
+}
+
+/// A built atlas
+#[derive(Debug, PartialEq, Clone)]
+pub struct Atlas<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps,
+{
+    /// the size of the atlas after growing
+    pub size: Rectangle<T>,
+    pub entries: BTreeMap<String, AtlasEntry<T>>,
+}
+
+/// The items that did not fit, even after growing as far as allowed
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct AtlasError {
+    pub unplaced: Vec<String>,
+}
+
+impl std::fmt::Display for AtlasError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "items do not fit in the atlas: {}",
+            self.unplaced.join(", ")
+        )
+    }
+}
+
+impl std::error::Error for AtlasError {}
+
+/// Builds an atlas from named sizes. each item is surrounded by `bleed` (part of the item's area, e.g. extruded edges)
+/// and then by `padding` (space between items)
+#[derive(Debug, Clone)]
+pub struct AtlasBuilder<T, P = GuillotinePacker>
+where
+    T: Copy + Num + NumAssignOps + NumOps,
+{
+    size: Rectangle<T>,
+    max_size: Option<Rectangle<T>>,
+    growth: Growth<T>,
+    padding: T,
+    bleed: T,
+    packer: P,
+    items: Vec<(String, Rectangle<T>)>,
+}
+
+impl<T> AtlasBuilder<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps,
+{
+    /// an atlas of the initial size, packed by guillotine cuts
+    pub fn new(size: Rectangle<T>) -> Self {
+        Self {
+            size,
+            max_size: None,
+            growth: Growth::Fixed,
+            padding: T::zero(),
+            bleed: T::zero(),
+            packer: GuillotinePacker,
+            items: Vec::new(),
+        }
+    }
+}
+
+impl<T, P> AtlasBuilder<T, P>
+where
+    T: Copy + Num + NumAssignOps + NumOps + PartialOrd,
+    P: Packer<T>,
+{
+    pub fn with_packer<Q: Packer<T>>(self, packer: Q) -> AtlasBuilder<T, Q> {
+        AtlasBuilder {
+            size: self.size,
+            max_size: self.max_size,
+            growth: self.growth,
+            padding: self.padding,
+            bleed: self.bleed,
+            packer,
+            items: self.items,
+        }
+    }
+
+    pub fn with_growth(mut self, growth: Growth<T>) -> Self {
+        self.growth = growth;
+        self
+    }
+
+    /// the atlas does not grow beyond this size
+    pub fn with_max_size(mut self, max_size: Rectangle<T>) -> Self {
+        self.max_size = Some(max_size);
+        self
+    }
+
+    pub fn with_padding(mut self, padding: T) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    pub fn with_bleed(mut self, bleed: T) -> Self {
+        self.bleed = bleed;
+        self
+    }
+
+    /// add an item. an item added again under the same name replaces the earlier one
+    pub fn add(mut self, name: impl Into<String>, size: Rectangle<T>) -> Self {
+        let name = name.into();
+        self.items.retain(|(n, _)| *n != name);
+        self.items.push((name, size));
+        self
+    }
+
+    pub fn build(&self) -> Result<Atlas<T>, AtlasError> {
+        let two = T::one() + T::one();
+        let margin = self.padding + self.bleed;
+        let sizes: Vec<Rectangle<T>> = self
+            .items
+            .iter()
+            .map(|(_, size)| {
+                Rectangle::new(size.width() + margin * two, size.height() + margin * two)
+            })
+            .collect();
+        let mut size = self.size;
+        loop {
+            let container = AxisAlignedRectangle::new(&Point::new(T::zero(), T::zero()), &size);
+            let packed = self.packer.pack(&container, &sizes);
+            if packed.is_complete() {
+                let entries = self
+                    .items
+                    .iter()
+                    .zip(packed.placements.iter().flatten())
+                    .map(|((name, item), placed)| {
+                        let entry = AtlasEntry {
+                            rect: AxisAlignedRectangle::new(
+                                &Point::new(placed.x() + margin, placed.y() + margin),
+                                item,
+                            ),
+                            bleed_rect: AxisAlignedRectangle::new(
+                                &Point::new(placed.x() + self.padding, placed.y() + self.padding),
+                                &Rectangle::new(
+                                    item.width() + self.bleed * two,
+                                    item.height() + self.bleed * two,
+                                ),
+                            ),
+                        };
+                        (name.clone(), entry)
+                    })
+                    .collect();
+                return Ok(Atlas { size, entries });
+            }
+            match self.grow(&size) {
+                Some(grown) => size = grown,
+                None => {
+                    return Err(AtlasError {
+                        unplaced: packed
+                            .unplaced
+                            .iter()
+                            .map(|i| self.items[*i].0.clone())
+                            .collect(),
+                    })
+                }
+            }
+        }
+    }
+
+    /// the next size to try, if the atlas can grow
+    fn grow(&self, size: &Rectangle<T>) -> Option<Rectangle<T>> {
+        let step = match self.growth {
+            Growth::Fixed => return None,
+            Growth::PowerOfTwo => match size.width() < size.height() {
+                true => size.width(),
+                false => size.height(),
+            },
+            Growth::By(step) => step,
+        };
+        if step <= T::zero() {
+            return None;
+        }
+        let grown = match size.width() <= size.height() {
+            true => Rectangle::new(size.width() + step, size.height()),
+            false => Rectangle::new(size.width(), size.height() + step),
+        };
+        match self.max_size {
+            Some(max) if grown.width() > max.width() || grown.height() > max.height() => None,
+            _ => Some(grown),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::packing::SkylinePacker;
+
+    #[test]
+    fn test_build() {
+        let atlas = AtlasBuilder::new(Rectangle::new(8, 8))
+            .add("c", Rectangle::new(8, 4))
+            .add("a", Rectangle::new(4, 4))
+            .add("b", Rectangle::new(4, 4))
+            .build()
+            .unwrap();
+        assert_eq!(atlas.size, Rectangle::new(8, 8));
+        assert_eq!(atlas.entries.len(), 3);
+        assert_eq!(
+            atlas.entries["a"].rect,
+            AxisAlignedRectangle::new(&Point::new(0, 4), &Rectangle::new(4, 4))
+        );
+        assert_eq!(atlas.entries["a"].rect, atlas.entries["a"].bleed_rect);
+    }
+
+    #[test]
+    fn test_padding_and_bleed() {
+        let atlas = AtlasBuilder::new(Rectangle::new(20, 10))
+            .with_padding(1)
+            .with_bleed(2)
+            .add("a", Rectangle::new(4, 4))
+            .add("b", Rectangle::new(4, 4))
+            .build()
+            .unwrap();
+        let a = &atlas.entries["a"];
+        assert_eq!(
+            a.rect,
+            AxisAlignedRectangle::new(&Point::new(3, 3), &Rectangle::new(4, 4))
+        );
+        assert_eq!(
+            a.bleed_rect,
+            AxisAlignedRectangle::new(&Point::new(1, 1), &Rectangle::new(8, 8))
+        );
+        // b starts after a, its bleed and the padding of both
+        let b = &atlas.entries["b"];
+        assert!(
+            b.bleed_rect.x() >= a.bleed_rect.x() + a.bleed_rect.width() + 2
+                || b.bleed_rect.y() >= a.bleed_rect.y() + a.bleed_rect.height() + 2
+        );
+    }
+
+    #[test]
+    fn test_growth() {
+        let builder = AtlasBuilder::new(Rectangle::new(4, 4))
+            .with_packer(SkylinePacker)
+            .add("a", Rectangle::new(4, 4))
+            .add("b", Rectangle::new(4, 4))
+            .add("c", Rectangle::new(4, 4));
+        assert_eq!(
+            builder.build(),
+            Err(AtlasError {
+                unplaced: vec!["b".to_string(), "c".to_string()]
+            })
+        );
+
+        let atlas = builder
+            .clone()
+            .with_growth(Growth::PowerOfTwo)
+            .build()
+            .unwrap();
+        assert_eq!(atlas.size, Rectangle::new(8, 8));
+
+        let atlas = builder.clone().with_growth(Growth::By(4)).build().unwrap();
+        assert_eq!(atlas.size, Rectangle::new(8, 8));
+
+        let error = builder
+            .with_growth(Growth::PowerOfTwo)
+            .with_max_size(Rectangle::new(8, 4))
+            .build()
+            .unwrap_err();
+        assert_eq!(error.unplaced, vec!["c".to_string()]);
+        assert_eq!(error.to_string(), "items do not fit in the atlas: c");
+    }
+
+    #[test]
+    fn test_replace() {
+        let atlas = AtlasBuilder::new(Rectangle::new(4, 4))
+            .add("a", Rectangle::new(4, 4))
+            .add("a", Rectangle::new(2, 2))
+            .build()
+            .unwrap();
+        assert_eq!(atlas.entries["a"].rect.rect(), Rectangle::new(2, 2));
+    }
+}
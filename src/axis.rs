@@ -1,4 +1,6 @@
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Axis {
     Vertical,
     Horizontal,
@@ -20,3 +22,92 @@ pub trait ValueForAxis<T> {
 pub trait SizeForAxis<T> {
     fn size_for_axis(&self, axis: Axis) -> T;
 }
+
+/// An axis in three dimensions. `Axis` is the 2D subset of it: a vertical cut divides along X
+/// and a horizontal cut along Y
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Axis3 {
+    X,
+    Y,
+    Z,
+}
+
+impl Axis3 {
+    pub const ALL: [Axis3; 3] = [Axis3::X, Axis3::Y, Axis3::Z];
+
+    /// 0, 1 and 2 for X, Y and Z, e.g. for indexing coordinates
+    pub fn index(&self) -> usize {
+        match self {
+            Axis3::X => 0,
+            Axis3::Y => 1,
+            Axis3::Z => 2,
+        }
+    }
+
+    /// X -> Y -> Z -> X
+    pub fn next(&self) -> Self {
+        match self {
+            Axis3::X => Axis3::Y,
+            Axis3::Y => Axis3::Z,
+            Axis3::Z => Axis3::X,
+        }
+    }
+
+    /// X -> Z -> Y -> X
+    pub fn previous(&self) -> Self {
+        self.next().next()
+    }
+
+    /// the two other axes, in cycling order. in 3D there is no single opposite axis
+    pub fn others(&self) -> [Self; 2] {
+        [self.next(), self.previous()]
+    }
+
+    /// the 2D axis for X and Y, `None` for Z
+    pub fn to_axis(&self) -> Option<Axis> {
+        match self {
+            Axis3::X => Some(Axis::Vertical),
+            Axis3::Y => Some(Axis::Horizontal),
+            Axis3::Z => None,
+        }
+    }
+}
+
+impl From<Axis> for Axis3 {
+    fn from(axis: Axis) -> Self {
+        match axis {
+            Axis::Vertical => Axis3::X,
+            Axis::Horizontal => Axis3::Y,
+        }
+    }
+}
+
+pub trait SizeForAxis3<T> {
+    fn size_for_axis3(&self, axis: Axis3) -> T;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_axis3() {
+        for axis in Axis3::ALL {
+            assert_eq!(axis.next().previous(), axis);
+            assert_eq!(axis.next().next().next(), axis);
+            let [a, b] = axis.others();
+            assert!(a != axis && b != axis && a != b);
+            assert_eq!(Axis3::ALL[axis.index()], axis);
+        }
+        assert_eq!(Axis3::Z.others(), [Axis3::X, Axis3::Y]);
+        for axis in [Axis::Vertical, Axis::Horizontal] {
+            assert_eq!(Axis3::from(axis).to_axis(), Some(axis));
+        }
+        assert_eq!(Axis3::Z.to_axis(), None);
+        // the 2D opposite is the other axis of the plane
+        assert_eq!(
+            Axis3::from(Axis::Vertical).next(),
+            Axis3::from(Axis::Vertical.opposite())
+        );
+    }
+}
@@ -7,10 +7,10 @@ use crate::component::Component;
 use crate::dividing::VerticalDividingHelper;
 use crate::point::{Edge, Point};
 use crate::rectangle::{Rectangle, RectangleSize};
-use crate::rotate::QuarterRotation;
+use crate::rotate::{QuarterRotation, Transpose};
 
 /// axis aligned starting at x, y and ending at x + width, y + height (left to right, top to bottom)
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
 pub struct AxisAlignedRectangle<T>
 where
     T: Copy + Num + NumAssignOps + NumOps,
@@ -87,6 +87,66 @@ where
     pub fn origin(&self) -> Point<T> {
         self.point
     }
+
+    /// the 0×0 rectangle at the origin
+    pub fn zero() -> Self {
+        Self::new(&Point::new(T::zero(), T::zero()), &Rectangle::zero())
+    }
+
+    /// the 1×1 rectangle at the origin
+    pub fn unit() -> Self {
+        Self::new(&Point::new(T::zero(), T::zero()), &Rectangle::unit())
+    }
+
+    pub fn set_x(&mut self, x: T) {
+        self.point = Point::new(x, self.point.y());
+    }
+
+    pub fn set_y(&mut self, y: T) {
+        self.point = Point::new(self.point.x(), y);
+    }
+
+    pub fn set_width(&mut self, width: T) {
+        self.rectangle.set_width(width);
+    }
+
+    pub fn set_height(&mut self, height: T) {
+        self.rectangle.set_height(height);
+    }
+
+    /// the same size at another x
+    pub fn with_x(mut self, x: T) -> Self {
+        self.set_x(x);
+        self
+    }
+
+    /// the same size at another y
+    pub fn with_y(mut self, y: T) -> Self {
+        self.set_y(y);
+        self
+    }
+
+    /// the same size at another origin
+    pub fn with_origin(mut self, origin: &Point<T>) -> Self {
+        self.point = *origin;
+        self
+    }
+
+    /// another size at the same origin
+    pub fn with_size(mut self, size: &Rectangle<T>) -> Self {
+        self.rectangle = *size;
+        self
+    }
+}
+
+/// A rectangle with default values. in many cases, this is 0×0 at (0, 0)
+impl<T> std::default::Default for AxisAlignedRectangle<T>
+where
+    T: Default + Copy + Num + NumAssignOps + NumOps,
+{
+    fn default() -> Self {
+        Self::new(&Point::default(), &Rectangle::default())
+    }
 }
 
 impl<T> AxisAlignedRectangle<T>
@@ -103,6 +163,23 @@ where
     }
 }
 
+impl<T> AxisAlignedRectangle<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps,
+{
+    /// mirror the rectangle left to right inside the container
+    pub fn mirror_horizontally(&self, within: &Self) -> Self {
+        let x = within.x() + within.x() + within.width() - self.x() - self.width();
+        Self::new(&Point::new(x, self.y()), &self.rectangle)
+    }
+
+    /// mirror the rectangle top to bottom inside the container
+    pub fn mirror_vertically(&self, within: &Self) -> Self {
+        let y = within.y() + within.y() + within.height() - self.y() - self.height();
+        Self::new(&Point::new(self.x(), y), &self.rectangle)
+    }
+}
+
 impl<T> AspectRatio<T> for AxisAlignedRectangle<T>
 where
     T: Copy + Num + NumAssignOps + NumOps,
@@ -164,6 +241,67 @@ where
         other.edges().iter().any(|p| self.includes(p))
     }
 
+    /// shrink every side by the amount, e.g. for padding. same as `shrink_clamped`: the result stays within this rectangle
+    pub fn inset(&self, amount: T) -> Self {
+        self.shrink_clamped(amount)
+    }
+
+    /// the overlapping part of two rectangles, if they overlap with a positive area
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let max = |a: T, b: T| match a > b {
+            true => a,
+            false => b,
+        };
+        let min = |a: T, b: T| match a < b {
+            true => a,
+            false => b,
+        };
+        let left = max(self.x(), other.x());
+        let top = max(self.y(), other.y());
+        let right = min(self.x() + self.width(), other.x() + other.width());
+        let bottom = min(self.y() + self.height(), other.y() + other.height());
+        match left < right && top < bottom {
+            true => Some(Self::new(
+                &Point::new(left, top),
+                &Rectangle::new(right - left, bottom - top),
+            )),
+            false => None,
+        }
+    }
+
+    /// the parts of this rectangle outside the other: up to four rectangles (above, below, left and right of it)
+    pub fn subtract(&self, other: &Self) -> Vec<Self> {
+        let Some(cut) = self.intersection(other) else {
+            return vec![self.clone()];
+        };
+        let right = self.x() + self.width();
+        let bottom = self.y() + self.height();
+        let cut_right = cut.x() + cut.width();
+        let cut_bottom = cut.y() + cut.height();
+        let pieces = [
+            Self::new(
+                &self.point,
+                &Rectangle::new(self.width(), cut.y() - self.y()),
+            ),
+            Self::new(
+                &Point::new(self.x(), cut_bottom),
+                &Rectangle::new(self.width(), bottom - cut_bottom),
+            ),
+            Self::new(
+                &Point::new(self.x(), cut.y()),
+                &Rectangle::new(cut.x() - self.x(), cut.height()),
+            ),
+            Self::new(
+                &Point::new(cut_right, cut.y()),
+                &Rectangle::new(right - cut_right, cut.height()),
+            ),
+        ];
+        pieces
+            .into_iter()
+            .filter(|p| p.width() > T::zero() && p.height() > T::zero())
+            .collect()
+    }
+
     #[allow(dead_code)]
     pub(crate) fn enclodes(&self, other: &Self) -> bool {
         // if all of the edges of the other rectangle are inside this rectangle, then they are enclosed
@@ -197,6 +335,110 @@ where
     }
 }
 
+/// Transpose an axis aligned rectangle relative to the origin of the container
+impl<T> Transpose for AxisAlignedRectangle<T>
+where
+    T: Copy + Num + NumAssignOps,
+{
+    fn transpose_within(&self, within: &Self) -> Self {
+        Self::new(
+            &Point::new(
+                within.x() + self.y() - within.y(),
+                within.y() + self.x() - within.x(),
+            ),
+            &Rectangle::new(self.height(), self.width()),
+        )
+    }
+}
+
+impl<T> AxisAlignedRectangle<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps + PartialOrd,
+{
+    /// shrink every side by the amount, never past the center: a side too short for the amount
+    /// collapses to zero at its middle, so the result stays within this rectangle. negative amounts do nothing
+    pub fn shrink_clamped(&self, amount: T) -> Self {
+        let two = T::one() + T::one();
+        let amount = match amount > T::zero() {
+            true => amount,
+            false => T::zero(),
+        };
+        let shrink = |start: T, size: T| match size > amount * two {
+            true => (start + amount, size - amount * two),
+            false => (start + size / two, T::zero()),
+        };
+        let (x, width) = shrink(self.x(), self.width());
+        let (y, height) = shrink(self.y(), self.height());
+        Self::new(&Point::new(x, y), &Rectangle::new(width, height))
+    }
+
+    /// grow every side by the amount, but not beyond the bounds. `None` if the grown rectangle is outside the bounds
+    pub fn grow_within(&self, amount: T, bounds: &Self) -> Option<Self> {
+        let grown = Self::new(
+            &Point::new(self.x() - amount, self.y() - amount),
+            &Rectangle::new(
+                self.width() + amount + amount,
+                self.height() + amount + amount,
+            ),
+        );
+        grown.intersection(bounds)
+    }
+
+    /// the size clamped between `min` and `max` (see `Rectangle::clamp`), keeping the origin
+    pub fn clamp_size(&self, min: &Rectangle<T>, max: &Rectangle<T>) -> Self {
+        Self::new(&self.point, &self.rectangle.clamp(min, max))
+    }
+
+    /// moved (and, if larger than the bounds, shrunk) as little as possible to be within the bounds,
+    /// e.g. to keep a dragged or resized cell inside its container
+    pub fn clamp_within(&self, bounds: &Self) -> Self {
+        let rect = self.clamp_size(&Rectangle::zero(), &bounds.rect());
+        let clamp = |start: T, size: T, bounds_start: T, bounds_size: T| {
+            if start < bounds_start {
+                bounds_start
+            } else if start + size > bounds_start + bounds_size {
+                bounds_start + bounds_size - size
+            } else {
+                start
+            }
+        };
+        Self::new(
+            &Point::new(
+                clamp(rect.x(), rect.width(), bounds.x(), bounds.width()),
+                clamp(rect.y(), rect.height(), bounds.y(), bounds.height()),
+            ),
+            &rect.rect(),
+        )
+    }
+}
+
+impl<T> AxisAlignedRectangle<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps + Ord,
+{
+    /// compare by the origin in reading order (by y, then by x). see `Point::cmp_reading_order`
+    pub fn cmp_reading_order(a: &Self, b: &Self) -> std::cmp::Ordering {
+        Point::cmp_reading_order(&a.point, &b.point)
+    }
+
+    /// compare by area, smaller first. reverse it (`cmp_area(b, a)`) for larger first
+    pub fn cmp_area(a: &Self, b: &Self) -> std::cmp::Ordering {
+        a.area().cmp(&b.area())
+    }
+}
+
+/// `x,y w×h`, e.g. `0,10 4×3`. the precision (`{:.1}`) applies to every component
+impl<T> std::fmt::Display for AxisAlignedRectangle<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps + std::fmt::Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Display::fmt(&self.point, f)?;
+        f.write_str(" ")?;
+        std::fmt::Display::fmt(&self.rectangle, f)
+    }
+}
+
 impl<T> VerticalDividingHelper<T> for AxisAlignedRectangle<T>
 where
     T: Copy + Num + NumAssignOps + NumOps,
@@ -234,6 +476,99 @@ mod tests {
         assert_eq!(result.height(), 5);
     }
 
+    #[test]
+    fn test_ordering_and_hash() {
+        let rect = |x, y, width, height| {
+            AxisAlignedRectangle::new(&Point::new(x, y), &Rectangle::new(width, height))
+        };
+        let mut rects = vec![rect(5, 0, 1, 1), rect(0, 2, 3, 3), rect(0, 0, 2, 1)];
+        rects.sort_by(AxisAlignedRectangle::cmp_reading_order);
+        assert_eq!(
+            rects,
+            vec![rect(0, 0, 2, 1), rect(5, 0, 1, 1), rect(0, 2, 3, 3)]
+        );
+        rects.sort_by(|a, b| AxisAlignedRectangle::cmp_area(b, a));
+        assert_eq!(
+            rects,
+            vec![rect(0, 2, 3, 3), rect(0, 0, 2, 1), rect(5, 0, 1, 1)]
+        );
+        // usable as a key, e.g. for caching the layout of a container
+        let mut cache = std::collections::HashMap::new();
+        cache.insert(rect(0, 0, 2, 1), "cached");
+        assert_eq!(cache.get(&rect(0, 0, 2, 1)), Some(&"cached"));
+        assert_eq!(cache.get(&rect(0, 0, 1, 2)), None);
+    }
+
+    #[test]
+    fn test_display() {
+        let rect = AxisAlignedRectangle::new(&Point::new(0, 10), &Rectangle::new(4, 3));
+        assert_eq!(rect.to_string(), "0,10 4×3");
+        let rect =
+            AxisAlignedRectangle::new(&Point::new(0.5, 1.0), &Rectangle::new(2.0, 1.0 / 3.0));
+        assert_eq!(format!("{:.2}", rect), "0.50,1.00 2.00×0.33");
+    }
+
+    #[test]
+    fn test_zero_unit_default() {
+        let zero = AxisAlignedRectangle::<i32>::zero();
+        assert_eq!(zero.origin(), Point::default());
+        assert_eq!(zero.rect(), Rectangle::new(0, 0));
+        assert_eq!(AxisAlignedRectangle::<i32>::default(), zero);
+        let unit = AxisAlignedRectangle::<f64>::unit();
+        assert_eq!(unit.rect(), Rectangle::new(1.0, 1.0));
+        assert_eq!(unit.origin(), Point::new(0.0, 0.0));
+    }
+
+    #[test]
+    fn test_setters() {
+        let mut rect = AxisAlignedRectangle::new(&Point::new(2, 3), &Rectangle::new(4, 5));
+        rect.set_x(1);
+        rect.set_y(-1);
+        rect.set_width(6);
+        rect.set_height(7);
+        assert_eq!(
+            rect,
+            AxisAlignedRectangle::new(&Point::new(1, -1), &Rectangle::new(6, 7))
+        );
+        let moved = rect.clone().with_x(10).with_y(20);
+        assert_eq!(moved.origin(), Point::new(10, 20));
+        assert_eq!(moved.rect(), rect.rect());
+        let updated = rect
+            .with_origin(&Point::new(0, 0))
+            .with_size(&Rectangle::new(2, 2));
+        assert_eq!(
+            updated,
+            AxisAlignedRectangle::new(&Point::new(0, 0), &Rectangle::new(2, 2))
+        );
+    }
+
+    #[test]
+    fn test_clamped_operations() {
+        let rect = |x, y, width, height| {
+            AxisAlignedRectangle::new(&Point::new(x, y), &Rectangle::new(width, height))
+        };
+        let cell = rect(10, 10, 10, 4);
+        assert_eq!(cell.shrink_clamped(1), rect(11, 11, 8, 2));
+        assert_eq!(cell.shrink_clamped(3), rect(13, 12, 4, 0));
+        assert_eq!(cell.shrink_clamped(-1), cell);
+
+        let bounds = rect(0, 0, 24, 24);
+        assert_eq!(cell.grow_within(2, &bounds), Some(rect(8, 8, 14, 8)));
+        assert_eq!(cell.grow_within(12, &bounds), Some(bounds.clone()));
+        assert_eq!(rect(30, 30, 1, 1).grow_within(2, &bounds), None);
+
+        assert_eq!(
+            cell.clamp_size(&Rectangle::new(5, 5), &Rectangle::new(8, 8)),
+            rect(10, 10, 8, 5)
+        );
+        assert_eq!(
+            rect(20, -5, 10, 4).clamp_within(&bounds),
+            rect(14, 0, 10, 4)
+        );
+        assert_eq!(rect(-5, 5, 40, 4).clamp_within(&bounds), rect(0, 5, 24, 4));
+        assert_eq!(cell.clamp_within(&bounds), cell);
+    }
+
     #[test]
     fn test_rotate() {
         let point = Point::new(2, 3);
@@ -243,6 +578,36 @@ mod tests {
         assert_eq!(result.rect(), Rectangle::new(5, 4));
     }
 
+    #[test]
+    fn test_mirror() {
+        let within = AxisAlignedRectangle::new(&Point::new(10, 20), &Rectangle::new(100, 50));
+        let rect = AxisAlignedRectangle::new(&Point::new(15, 30), &Rectangle::new(20, 10));
+        let mirrored = rect.mirror_horizontally(&within);
+        assert_eq!(mirrored.origin(), Point::new(85, 30));
+        assert_eq!(mirrored.rect(), rect.rect());
+        assert_eq!(mirrored.mirror_horizontally(&within), rect);
+
+        let mirrored = rect.mirror_vertically(&within);
+        assert_eq!(mirrored.origin(), Point::new(15, 50));
+        assert_eq!(mirrored.rect(), rect.rect());
+        assert_eq!(mirrored.mirror_vertically(&within), rect);
+    }
+
+    #[test]
+    fn test_transpose() {
+        let within = AxisAlignedRectangle::new(&Point::new(10, 20), &Rectangle::new(100, 50));
+        let transposed = within.transpose_within(&within);
+        assert_eq!(transposed.origin(), within.origin());
+        assert_eq!(transposed.rect(), Rectangle::new(50, 100));
+
+        let rect = AxisAlignedRectangle::new(&Point::new(15, 30), &Rectangle::new(20, 10));
+        let result = rect.transpose_within(&within);
+        assert_eq!(result.origin(), Point::new(20, 25));
+        assert_eq!(result.rect(), Rectangle::new(10, 20));
+        assert!(transposed.enclodes(&result));
+        assert_eq!(result.transpose_within(&transposed), rect);
+    }
+
     #[test]
     fn test_area() {
         let point = Point::new(2, 3);
@@ -274,6 +639,45 @@ mod tests {
         assert!(!a_rect.includes(&Point::new(6, 9)));
     }
 
+    #[test]
+    fn test_inset() {
+        let a = AxisAlignedRectangle::new(&Point::new(0, 0), &Rectangle::new(10, 4));
+        assert_eq!(
+            a.inset(1),
+            AxisAlignedRectangle::new(&Point::new(1, 1), &Rectangle::new(8, 2))
+        );
+        // too short for the amount: collapsed at the middle, not moved out of the rectangle
+        assert_eq!(
+            a.inset(3),
+            AxisAlignedRectangle::new(&Point::new(3, 2), &Rectangle::new(4, 0))
+        );
+    }
+
+    #[test]
+    fn test_intersection_and_subtract() {
+        let a = AxisAlignedRectangle::new(&Point::new(0, 0), &Rectangle::new(4, 4));
+        let b = AxisAlignedRectangle::new(&Point::new(1, 2), &Rectangle::new(2, 4));
+        assert_eq!(
+            a.intersection(&b),
+            Some(AxisAlignedRectangle::new(
+                &Point::new(1, 2),
+                &Rectangle::new(2, 2)
+            ))
+        );
+        let touching = AxisAlignedRectangle::new(&Point::new(4, 0), &Rectangle::new(1, 1));
+        assert_eq!(a.intersection(&touching), None);
+        assert_eq!(a.subtract(&touching), vec![a.clone()]);
+        assert_eq!(
+            a.subtract(&b),
+            vec![
+                AxisAlignedRectangle::new(&Point::new(0, 0), &Rectangle::new(4, 2)),
+                AxisAlignedRectangle::new(&Point::new(0, 2), &Rectangle::new(1, 2)),
+                AxisAlignedRectangle::new(&Point::new(3, 2), &Rectangle::new(1, 2)),
+            ]
+        );
+        assert!(a.subtract(&a).is_empty());
+    }
+
     #[test]
     fn test_overlaps() {
         let point = Point::new(2, 3);
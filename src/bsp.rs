@@ -0,0 +1,164 @@
+//! Binary space partitioning with random cuts, e.g. the rooms of a roguelike dungeon.
+use num_traits::{Float, Num, NumAssignOps, NumOps};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::axis::{Axis, SizeForAxis};
+use crate::axis_aligned_rectangle::AxisAlignedRectangle;
+use crate::dividing::Dividing;
+use crate::layout::Layout;
+use crate::rectangle::{Rectangle, RectangleSize};
+use crate::split_tree::{SplitChild, SplitTree};
+
+/// The constraints of a random partition
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct BspConfig<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps,
+{
+    /// no cell is narrower or lower than this. a part too small to cut in two of these is not cut
+    pub min_size: Rectangle<T>,
+    /// the number of cuts from the container to any cell at most
+    pub max_depth: usize,
+    /// how far from the middle a cut can fall, as a fraction of the size: 0 cuts in halves, 0.5 anywhere
+    pub jitter: T,
+}
+
+impl<T> BspConfig<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps + Float + for<'a> std::iter::Sum<&'a T>,
+{
+    /// a partition with cuts from the seed. the same seed gives the same partition
+    pub fn generate(&self, container: &AxisAlignedRectangle<T>, seed: u64) -> Layout<T> {
+        self.generate_with_rng(container, &mut StdRng::seed_from_u64(seed))
+    }
+
+    /// a partition with cuts from the random number generator. the cells are in depth-first order of the tree
+    /// (the layout keeps it), from the start edge of each cut on
+    pub fn generate_with_rng<R: Rng>(
+        &self,
+        container: &AxisAlignedRectangle<T>,
+        rng: &mut R,
+    ) -> Layout<T> {
+        let mut leaves = 0;
+        let tree = self.split(container, 0, rng, &mut leaves);
+        tree.materialize(container)
+    }
+
+    fn split<R: Rng>(
+        &self,
+        rect: &AxisAlignedRectangle<T>,
+        depth: usize,
+        rng: &mut R,
+        leaves: &mut usize,
+    ) -> SplitTree<T> {
+        let mut leaf = || {
+            *leaves += 1;
+            SplitTree::leaf(*leaves - 1)
+        };
+        if depth >= self.max_depth {
+            return leaf();
+        }
+        let two = T::one() + T::one();
+        let can_split = |axis: Axis| {
+            rect.size_for_axis(axis) >= self.min_size.size_for_axis(axis) * two
+                && rect.size_for_axis(axis) > T::zero()
+        };
+        let axis = match (can_split(Axis::Vertical), can_split(Axis::Horizontal)) {
+            (false, false) => return leaf(),
+            (true, false) => Axis::Vertical,
+            (false, true) => Axis::Horizontal,
+            // cut across the longer side of elongated parts, and either way otherwise
+            (true, true) => {
+                let ratio = rect.width() / rect.height();
+                let elongated = T::from(1.25).unwrap_or(T::one());
+                if ratio > elongated {
+                    Axis::Vertical
+                } else if ratio < T::one() / elongated {
+                    Axis::Horizontal
+                } else if rng.gen_bool(0.5) {
+                    Axis::Vertical
+                } else {
+                    Axis::Horizontal
+                }
+            }
+        };
+        let size = rect.size_for_axis(axis);
+        let offset = T::from(rng.gen_range(-1.0..=1.0)).unwrap_or(T::zero());
+        let lowest = self.min_size.size_for_axis(axis) / size;
+        let fraction = (T::one() / two + self.jitter * offset)
+            .max(lowest)
+            .min(T::one() - lowest);
+        let (first, second) = rect.divide(fraction * size, axis);
+        let first = self.split(&first, depth + 1, rng, leaves);
+        let second = self.split(&second, depth + 1, rng, leaves);
+        SplitTree::cut(
+            axis,
+            vec![
+                SplitChild {
+                    fraction,
+                    node: first,
+                },
+                SplitChild {
+                    fraction: T::one() - fraction,
+                    node: second,
+                },
+            ],
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::point::Point;
+    use crate::testing::verify_tiling;
+    use crate::tolerance::Tolerance;
+
+    fn dungeon() -> AxisAlignedRectangle<f64> {
+        AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(80.0, 50.0))
+    }
+
+    #[test]
+    fn test_generate() {
+        let config = BspConfig {
+            min_size: Rectangle::new(6.0, 5.0),
+            max_depth: 5,
+            jitter: 0.3,
+        };
+        let layout = config.generate(&dungeon(), 42);
+        assert!(layout.len() > 1 && layout.len() <= 32);
+        assert!(layout.tree().is_some());
+        assert_eq!(
+            verify_tiling(&dungeon(), layout.cells(), &Tolerance::default()),
+            Ok(())
+        );
+        for cell in layout.iter() {
+            assert!(cell.width() >= 6.0 - 1e-9 && cell.height() >= 5.0 - 1e-9);
+        }
+        // deterministic for the seed
+        assert_eq!(config.generate(&dungeon(), 42), layout);
+        assert_ne!(config.generate(&dungeon(), 43), layout);
+    }
+
+    #[test]
+    fn test_constraints() {
+        let halves = BspConfig {
+            min_size: Rectangle::new(1.0, 1.0),
+            max_depth: 2,
+            jitter: 0.0,
+        };
+        let layout = halves.generate(&dungeon(), 0);
+        assert_eq!(layout.len(), 4);
+        assert!(layout
+            .iter()
+            .all(|cell| cell.width() == 40.0 && cell.height() == 25.0));
+
+        let too_large = BspConfig {
+            min_size: Rectangle::new(50.0, 30.0),
+            max_depth: 10,
+            jitter: 0.5,
+        };
+        assert_eq!(too_large.generate(&dungeon(), 0).cells(), &[dungeon()][..]);
+    }
+}
@@ -0,0 +1,375 @@
+use std::cmp::Ordering;
+
+use num_traits::{Num, NumAssignOps, NumOps};
+
+use crate::axis_aligned_rectangle::AxisAlignedRectangle;
+use crate::component::Component;
+use crate::layout::Layout;
+use crate::point::Point;
+use crate::rectangle::RectangleSize;
+use crate::weight::normalize_weights;
+
+/// A weight with the label of its item, and optionally an opaque id to recognize the item by
+#[derive(Debug, PartialEq, Clone)]
+pub struct LabeledWeight<T> {
+    pub weight: T,
+    pub label: String,
+    pub id: Option<u64>,
+}
+
+impl<T> LabeledWeight<T> {
+    pub fn new(weight: T, label: impl Into<String>) -> Self {
+        Self {
+            weight,
+            label: label.into(),
+            id: None,
+        }
+    }
+
+    pub fn with_id(mut self, id: u64) -> Self {
+        self.id = Some(id);
+        self
+    }
+}
+
+/// Where to put the text of a label in its cell
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum LabelAnchor<T> {
+    /// the center of the cell
+    Center,
+    /// the left top corner, moved inward by the padding on both axes
+    TopLeft { padding: T },
+}
+
+impl<T> LabelAnchor<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps,
+{
+    pub fn point(&self, rect: &AxisAlignedRectangle<T>) -> Point<T> {
+        match self {
+            Self::Center => {
+                let two = T::one() + T::one();
+                Point::new(
+                    rect.x() + rect.width() / two,
+                    rect.y() + rect.height() / two,
+                )
+            }
+            Self::TopLeft { padding } => Point::new(rect.x() + *padding, rect.y() + *padding),
+        }
+    }
+}
+
+/// A divided cell with the label (and the id) of its item and where to draw the label
+#[derive(Debug, PartialEq, Clone)]
+pub struct Cell<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps,
+{
+    pub rect: AxisAlignedRectangle<T>,
+    /// the index of the item in the input, from the permutation of the layout (see `divide_labeled`)
+    pub index: usize,
+    /// the weight of the item divided by the sum of the weights
+    pub normalized_weight: T,
+    /// the paint order: cells with lower values are drawn first. the index of the item unless `assign_paint_order` changes it
+    pub z_index: usize,
+    pub label: String,
+    pub id: Option<u64>,
+    pub anchor: Point<T>,
+}
+
+/// In which order cells are painted, so that borders and highlights overlap the same way every time
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum PaintOrder {
+    /// larger cells first, so smaller ones are drawn on top of them
+    AreaDescending,
+    /// in the order of the items
+    InputOrder,
+    /// top to bottom, then left to right
+    ReadingOrder,
+}
+
+/// assign each cell its `z_index` by the paint order. ties keep the order of the items
+pub fn assign_paint_order<T>(cells: &mut [Cell<T>], order: PaintOrder)
+where
+    T: Copy + Num + NumAssignOps + NumOps + PartialOrd,
+{
+    let mut painted: Vec<usize> = (0..cells.len()).collect();
+    painted.sort_by(|a, b| {
+        let (a, b) = (&cells[*a], &cells[*b]);
+        let ordering = match order {
+            PaintOrder::AreaDescending => {
+                (b.rect.width() * b.rect.height()).partial_cmp(&(a.rect.width() * a.rect.height()))
+            }
+            PaintOrder::InputOrder => Some(Ordering::Equal),
+            PaintOrder::ReadingOrder => match a.rect.y().partial_cmp(&b.rect.y()) {
+                Some(Ordering::Equal) => a.rect.x().partial_cmp(&b.rect.x()),
+                ordering => ordering,
+            },
+        };
+        ordering
+            .unwrap_or(Ordering::Equal)
+            .then(a.index.cmp(&b.index))
+    });
+    for (z_index, i) in painted.into_iter().enumerate() {
+        cells[i].z_index = z_index;
+    }
+}
+
+/// A side of a rectangle
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Side {
+    Left,
+    Right,
+    Top,
+    Bottom,
+}
+
+/// Where a callout leaves its cell: the middle of the side of the cell facing the nearest outer edge of the container
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Callout<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps,
+{
+    pub side: Side,
+    pub point: Point<T>,
+}
+
+impl<T> Callout<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps + PartialOrd,
+{
+    /// the callout of the cell toward the nearest edge of the container (left, right, top, bottom on ties),
+    /// for labels too large for small cells that have to be drawn outside the container
+    pub fn toward_nearest_edge(
+        cell: &AxisAlignedRectangle<T>,
+        container: &AxisAlignedRectangle<T>,
+    ) -> Self {
+        let two = T::one() + T::one();
+        let (right, bottom) = (cell.x() + cell.width(), cell.y() + cell.height());
+        let distances = [
+            (Side::Left, cell.x() - container.x()),
+            (Side::Right, container.x() + container.width() - right),
+            (Side::Top, cell.y() - container.y()),
+            (Side::Bottom, container.y() + container.height() - bottom),
+        ];
+        let mut nearest = distances[0];
+        for (side, distance) in distances.into_iter().skip(1) {
+            if distance < nearest.1 {
+                nearest = (side, distance);
+            }
+        }
+        let (middle_x, middle_y) = (
+            cell.x() + cell.width() / two,
+            cell.y() + cell.height() / two,
+        );
+        let point = match nearest.0 {
+            Side::Left => Point::new(cell.x(), middle_y),
+            Side::Right => Point::new(right, middle_y),
+            Side::Top => Point::new(middle_x, cell.y()),
+            Side::Bottom => Point::new(middle_x, bottom),
+        };
+        Self {
+            side: nearest.0,
+            point,
+        }
+    }
+}
+
+impl<T> Layout<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps + PartialOrd,
+{
+    /// the callout of each cell toward the nearest edge of the bounds of the layout. see `Callout::toward_nearest_edge`
+    pub fn callouts(&self) -> Vec<Callout<T>> {
+        let Some(bounds) = self.bounds() else {
+            return vec![];
+        };
+        self.iter()
+            .map(|cell| Callout::toward_nearest_edge(cell, &bounds))
+            .collect()
+    }
+}
+
+impl<T> AxisAlignedRectangle<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps,
+{
+    /// dividing a rectangle for labeled items: `layout` divides it by the weights and returns the cells with the
+    /// permutation mapping the n-th cell to the index of its item (e.g. `Dividing::divide_sorted`, or the identity
+    /// for layouts that keep the order of the weights). the cells come in the order of the layout, and each gets
+    /// the index, the label and the id of its item, so they stay with their items whatever order the algorithm places them in
+    pub fn divide_labeled<L>(
+        &self,
+        items: &[LabeledWeight<T>],
+        anchor: LabelAnchor<T>,
+        layout: L,
+    ) -> Vec<Cell<T>>
+    where
+        T: for<'a> std::iter::Sum<&'a T>,
+        L: FnOnce(&Self, &[T]) -> (Vec<Self>, Vec<usize>),
+    {
+        let weights: Vec<T> = items.iter().map(|item| item.weight).collect();
+        let normalized_weights = normalize_weights(&weights);
+        let (rects, permutation) = layout(self, &weights);
+        assert_eq!(rects.len(), items.len(), "one cell for each item");
+        assert_eq!(permutation.len(), items.len(), "one index for each cell");
+        rects
+            .into_iter()
+            .zip(permutation)
+            .map(|(rect, index)| Cell {
+                anchor: anchor.point(&rect),
+                index,
+                normalized_weight: normalized_weights[index],
+                z_index: index,
+                label: items[index].label.clone(),
+                id: items[index].id,
+                rect,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::axis::Axis;
+    use crate::dividing::Dividing;
+    use crate::rectangle::Rectangle;
+
+    #[test]
+    fn test_label_anchor() {
+        let rect = AxisAlignedRectangle::new(&Point::new(10, 20), &Rectangle::new(4, 6));
+        assert_eq!(LabelAnchor::Center.point(&rect), Point::new(12, 23));
+        assert_eq!(
+            LabelAnchor::TopLeft { padding: 1 }.point(&rect),
+            Point::new(11, 21)
+        );
+    }
+
+    #[test]
+    fn test_divide_labeled() {
+        let rect = AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(60.0, 40.0));
+        let items = [
+            LabeledWeight::new(1.0, "a"),
+            LabeledWeight::new(3.0, "b"),
+            LabeledWeight::new(2.0, "c"),
+        ];
+        let cells = rect.divide_labeled(&items, LabelAnchor::Center, |r, w| {
+            (
+                r.divide_vertical_then_horizontal_with_weights(w, 1.0, true),
+                vec![0, 1, 2],
+            )
+        });
+        let rects = rect.divide_vertical_then_horizontal_with_weights(&[1.0, 3.0, 2.0], 1.0, true);
+        assert_eq!(cells.len(), 3);
+        for ((cell, item), r) in cells.iter().zip(items.iter()).zip(rects.iter()) {
+            assert_eq!(cell.label, item.label);
+            assert_eq!(&cell.rect, r);
+            assert_eq!(cell.anchor, LabelAnchor::Center.point(r));
+            assert_eq!(cell.id, None);
+        }
+        let indices: Vec<usize> = cells.iter().map(|c| c.index).collect();
+        assert_eq!(indices, vec![0, 1, 2]);
+        let shares: Vec<f64> = cells.iter().map(|c| c.normalized_weight).collect();
+        assert_eq!(shares, vec![1.0 / 6.0, 0.5, 1.0 / 3.0]);
+    }
+
+    #[test]
+    fn test_assign_paint_order() {
+        let rect = AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(60.0, 40.0));
+        let items = [
+            LabeledWeight::new(1.0, "a"),
+            LabeledWeight::new(3.0, "b"),
+            LabeledWeight::new(2.0, "c"),
+        ];
+        // a column of "a" above "b", then "c" on the right
+        let mut cells = rect.divide_labeled(&items, LabelAnchor::Center, |r, w| {
+            (
+                r.divide_vertical_then_horizontal_with_weights(w, 1.0, false),
+                vec![0, 1, 2],
+            )
+        });
+        let z_indices = |cells: &[Cell<f64>]| cells.iter().map(|c| c.z_index).collect::<Vec<_>>();
+        assert_eq!(z_indices(&cells), vec![0, 1, 2]);
+        assign_paint_order(&mut cells, PaintOrder::AreaDescending);
+        assert_eq!(z_indices(&cells), vec![2, 0, 1]);
+        assign_paint_order(&mut cells, PaintOrder::ReadingOrder);
+        assert_eq!(z_indices(&cells), vec![0, 2, 1]);
+        assign_paint_order(&mut cells, PaintOrder::InputOrder);
+        assert_eq!(z_indices(&cells), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_divide_labeled_ids() {
+        let rect = AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(60.0, 40.0));
+        let items = [
+            LabeledWeight::new(1.0, "a").with_id(100),
+            LabeledWeight::new(3.0, "b").with_id(7),
+            LabeledWeight::new(2.0, "c").with_id(42),
+        ];
+        // laid out largest first: the cells come in that order, each with its own item
+        let cells = rect.divide_labeled(&items, LabelAnchor::Center, |r, w| {
+            r.divide_sorted(w, |r, w| {
+                r.divide_vertical_then_horizontal_with_weights(w, 1.0, true)
+            })
+        });
+        let indices: Vec<usize> = cells.iter().map(|c| c.index).collect();
+        assert_eq!(indices, vec![1, 2, 0]);
+        let ids: Vec<Option<u64>> = cells.iter().map(|c| c.id).collect();
+        assert_eq!(ids, vec![Some(7), Some(42), Some(100)]);
+        let labels: Vec<&str> = cells.iter().map(|c| c.label.as_str()).collect();
+        assert_eq!(labels, vec!["b", "c", "a"]);
+        let shares: Vec<f64> = cells.iter().map(|c| c.normalized_weight).collect();
+        assert_eq!(shares, vec![0.5, 1.0 / 3.0, 1.0 / 6.0]);
+        // the largest item took the largest cell
+        let areas: Vec<f64> = cells
+            .iter()
+            .map(|c| c.rect.width() * c.rect.height())
+            .collect();
+        assert!(areas[0] > areas[1] && areas[1] > areas[2]);
+    }
+
+    #[test]
+    #[should_panic(expected = "one cell for each item")]
+    fn test_divide_labeled_missing_cell() {
+        let rect = AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(60.0, 40.0));
+        let items = [LabeledWeight::new(1.0, "a"), LabeledWeight::new(3.0, "b")];
+        rect.divide_labeled(&items, LabelAnchor::Center, |r, w| {
+            (
+                r.divide_by_weights_and_axis(&w[..1], Axis::Vertical),
+                vec![0],
+            )
+        });
+    }
+
+    #[test]
+    fn test_callouts() {
+        let rect = |x, y, width, height| {
+            AxisAlignedRectangle::new(&Point::new(x, y), &Rectangle::new(width, height))
+        };
+        let layout = Layout::new(vec![
+            rect(0, 0, 10, 4),
+            rect(0, 4, 4, 6),
+            rect(4, 4, 2, 2),
+            rect(6, 4, 4, 6),
+            rect(4, 6, 2, 4),
+        ]);
+        let callouts = layout.callouts();
+        let sides: Vec<Side> = callouts.iter().map(|c| c.side).collect();
+        assert_eq!(
+            sides,
+            vec![
+                Side::Left,
+                Side::Left,
+                Side::Left,
+                Side::Right,
+                Side::Bottom
+            ]
+        );
+        assert_eq!(callouts[0].point, Point::new(0, 2));
+        assert_eq!(callouts[2].point, Point::new(4, 5));
+        assert_eq!(callouts[3].point, Point::new(10, 7));
+        assert_eq!(callouts[4].point, Point::new(5, 10));
+        assert!(Layout::<i32>::new(vec![]).callouts().is_empty());
+    }
+}
@@ -0,0 +1,190 @@
+use num_traits::{Float, Num, NumAssignOps, NumOps};
+use serde::{Deserialize, Serialize};
+
+use crate::axis_aligned_rectangle::AxisAlignedRectangle;
+use crate::layout::Layout;
+
+/// A color with 8 bits per channel, alpha included
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub struct Rgba {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub a: u8,
+}
+
+impl Rgba {
+    pub fn new(r: u8, g: u8, b: u8, a: u8) -> Self {
+        Self { r, g, b, a }
+    }
+
+    /// an opaque color
+    pub fn rgb(r: u8, g: u8, b: u8) -> Self {
+        Self::new(r, g, b, u8::MAX)
+    }
+
+    /// the color at `t` (from 0 to 1) between this and the other color, channel by channel
+    pub fn lerp<T>(&self, other: &Self, t: T) -> Self
+    where
+        T: Float,
+    {
+        let channel = |from: u8, to: u8| {
+            let (from, to) = (T::from(from), T::from(to));
+            match (from, to) {
+                (Some(from), Some(to)) => (from + (to - from) * t).round().to_u8().unwrap_or(0),
+                _ => 0,
+            }
+        };
+        Self::new(
+            channel(self.r, other.r),
+            channel(self.g, other.g),
+            channel(self.b, other.b),
+            channel(self.a, other.a),
+        )
+    }
+}
+
+/// How values are spread over a color scale
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ScaleKind {
+    Linear,
+    /// by the logarithm of the values, for values spanning orders of magnitude. the domain must be positive
+    Log,
+}
+
+/// Maps values in a domain to colors between two colors. values outside the domain are clamped to it,
+/// so a fixed domain gives the same colors across frames whatever the values of each frame
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct ColorScale<T> {
+    pub kind: ScaleKind,
+    pub min: T,
+    pub max: T,
+    pub from: Rgba,
+    pub to: Rgba,
+}
+
+impl<T> ColorScale<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps + Float,
+{
+    pub fn linear(min: T, max: T, from: Rgba, to: Rgba) -> Self {
+        Self {
+            kind: ScaleKind::Linear,
+            min,
+            max,
+            from,
+            to,
+        }
+    }
+
+    pub fn log(min: T, max: T, from: Rgba, to: Rgba) -> Self {
+        Self {
+            kind: ScaleKind::Log,
+            min,
+            max,
+            from,
+            to,
+        }
+    }
+
+    /// a linear scale over the range of the values (0 to 1 for no values)
+    pub fn fit(values: &[T], from: Rgba, to: Rgba) -> Self {
+        let min = values.iter().copied().reduce(T::min).unwrap_or(T::zero());
+        let max = values.iter().copied().reduce(T::max).unwrap_or(T::one());
+        Self::linear(min, max, from, to)
+    }
+
+    /// where the value is on the scale, from 0 (at `min` or below) to 1 (at `max` or above).
+    /// 0 for an empty domain
+    pub fn position(&self, value: T) -> T {
+        let value = value.max(self.min).min(self.max);
+        let (value, min, max) = match self.kind {
+            ScaleKind::Linear => (value, self.min, self.max),
+            ScaleKind::Log => (value.ln(), self.min.ln(), self.max.ln()),
+        };
+        let position = (value - min) / (max - min);
+        match position.is_finite() {
+            true => position.max(T::zero()).min(T::one()),
+            false => T::zero(),
+        }
+    }
+
+    pub fn color(&self, value: T) -> Rgba {
+        self.from.lerp(&self.to, self.position(value))
+    }
+}
+
+impl<T> Layout<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps + Float,
+{
+    /// each cell with the color of its value, e.g. its weight. `values[n]` is the value of the n-th cell
+    pub fn colors(
+        &self,
+        scale: &ColorScale<T>,
+        values: &[T],
+    ) -> Vec<(AxisAlignedRectangle<T>, Rgba)> {
+        self.iter()
+            .zip(values.iter())
+            .map(|(cell, value)| (cell.clone(), scale.color(*value)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::point::Point;
+    use crate::rectangle::Rectangle;
+
+    const BLACK: Rgba = Rgba {
+        r: 0,
+        g: 0,
+        b: 0,
+        a: 255,
+    };
+    const WHITE: Rgba = Rgba {
+        r: 255,
+        g: 255,
+        b: 255,
+        a: 255,
+    };
+
+    #[test]
+    fn test_linear() {
+        let scale = ColorScale::linear(10.0, 20.0, BLACK, WHITE);
+        assert_eq!(scale.color(10.0), BLACK);
+        assert_eq!(scale.color(15.0), Rgba::rgb(128, 128, 128));
+        assert_eq!(scale.color(20.0), WHITE);
+        // clamped to the domain
+        assert_eq!(scale.color(-5.0), BLACK);
+        assert_eq!(scale.color(100.0), WHITE);
+        let empty = ColorScale::linear(1.0, 1.0, BLACK, WHITE);
+        assert_eq!(empty.color(1.0), BLACK);
+    }
+
+    #[test]
+    fn test_log() {
+        let scale = ColorScale::log(1.0, 100.0, BLACK, Rgba::new(200, 0, 0, 0));
+        assert_eq!(scale.position(10.0), 0.5);
+        assert_eq!(scale.color(10.0), Rgba::new(100, 0, 0, 128));
+        assert_eq!(scale.position(0.0), 0.0);
+    }
+
+    #[test]
+    fn test_layout_colors() {
+        let rect = |x| AxisAlignedRectangle::new(&Point::new(x, 0.0), &Rectangle::new(1.0, 1.0));
+        let layout = Layout::new(vec![rect(0.0), rect(1.0), rect(2.0)]);
+        let weights = [1.0, 3.0, 2.0];
+        let scale = ColorScale::fit(&weights, BLACK, WHITE);
+        let colors = layout.colors(&scale, &weights);
+        assert_eq!(
+            colors,
+            vec![
+                (rect(0.0), BLACK),
+                (rect(1.0), WHITE),
+                (rect(2.0), Rgba::rgb(128, 128, 128)),
+            ]
+        );
+    }
+}
@@ -1,4 +1,5 @@
-pub(crate) trait Component<T> {
+/// The x and y of a point, or of the origin of a rectangle
+pub trait Component<T> {
     fn x(&self) -> T;
     fn y(&self) -> T;
 }
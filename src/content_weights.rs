@@ -0,0 +1,129 @@
+//! Solving for the weights that give the requested proportions to the content of the cells.
+//!
+//! Gutters, headers, padding and minimum sizes take fixed space out of every cell, so the content areas
+//! drift from the weights: small cells lose a larger share to the insets than large ones. Rather than modeling
+//! every inset, the weights given to the layout are adjusted until the content areas match.
+use num_traits::{Float, Num, NumAssignOps, NumOps};
+
+use crate::area::Area;
+use crate::axis_aligned_rectangle::AxisAlignedRectangle;
+use crate::weight::normalize_weights;
+
+/// A layout whose content areas were solved for: the cells, their content and the weights that gave them
+#[derive(Debug, PartialEq, Clone)]
+pub struct ContentLayout<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps,
+{
+    /// the n-th cell corresponds to the n-th weight
+    pub cells: Vec<AxisAlignedRectangle<T>>,
+    /// the content of the n-th cell
+    pub content: Vec<AxisAlignedRectangle<T>>,
+    /// the weights the cells were divided by
+    pub effective_weights: Vec<T>,
+    /// the largest difference between the share of a content area (of the sum of the content areas)
+    /// and its normalized weight
+    pub error: T,
+}
+
+impl<T> AxisAlignedRectangle<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps + Float + for<'a> std::iter::Sum<&'a T>,
+{
+    /// dividing a rectangle so that the content areas, rather than the cells, are proportional to the weights.
+    /// `layout` divides the rectangle by weights (e.g. a `Divider` with a gutter) and `content` gives the part of a cell
+    /// left for its content (e.g. `|cell| cell.shrink_clamped(padding)`, or the cell without its header).
+    /// starting from the weights, each round scales every weight by how far its content share falls short of its
+    /// normalized weight. the best of up to `iterations` rounds is returned, so the error never exceeds
+    /// the one of the plain weights
+    pub fn divide_for_content<L, C>(
+        &self,
+        weights: &[T],
+        iterations: usize,
+        layout: L,
+        content: C,
+    ) -> ContentLayout<T>
+    where
+        L: Fn(&Self, &[T]) -> Vec<Self>,
+        C: Fn(&Self) -> Self,
+    {
+        let targets = normalize_weights(weights);
+        let solve = |effective_weights: Vec<T>| {
+            let cells = layout(self, &effective_weights);
+            let content: Vec<Self> = cells.iter().map(&content).collect();
+            let areas: Vec<T> = content.iter().map(|c| c.area()).collect();
+            let total: T = areas.iter().sum();
+            let shares: Vec<T> = areas
+                .iter()
+                .map(|a| match total > T::zero() {
+                    true => *a / total,
+                    false => T::zero(),
+                })
+                .collect();
+            let error = shares
+                .iter()
+                .zip(targets.iter())
+                .map(|(share, target)| (*share - *target).abs())
+                .fold(T::zero(), T::max);
+            (
+                ContentLayout {
+                    cells,
+                    content,
+                    effective_weights,
+                    error,
+                },
+                shares,
+            )
+        };
+        let (mut best, mut shares) = solve(targets.clone());
+        let mut current = best.effective_weights.clone();
+        for _ in 0..iterations {
+            let two = T::one() + T::one();
+            // a cell without content gets twice the weight, as it has no share to scale by
+            let next: Vec<T> = current
+                .iter()
+                .zip(shares.iter().zip(targets.iter()))
+                .map(|(w, (share, target))| match *share > T::zero() {
+                    true => *w * *target / *share,
+                    false => *w * two,
+                })
+                .collect();
+            let (candidate, candidate_shares) = solve(normalize_weights(&next));
+            current = candidate.effective_weights.clone();
+            shares = candidate_shares;
+            if candidate.error < best.error {
+                best = candidate;
+            }
+        }
+        best
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::axis::Axis;
+    use crate::dividing::Dividing;
+    use crate::point::Point;
+    use crate::rectangle::{Rectangle, RectangleSize};
+
+    #[test]
+    fn test_divide_for_content() {
+        let rect = AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(100.0, 20.0));
+        let weights = [1.0, 1.0, 2.0];
+        let layout = |r: &AxisAlignedRectangle<f64>, w: &[f64]| {
+            r.divide_by_weights_and_axis(w, Axis::Vertical)
+        };
+        let padding = |cell: &AxisAlignedRectangle<f64>| cell.shrink_clamped(5.0);
+        // the plain weights give the content 15, 15 and 40 of 70
+        let plain = rect.divide_for_content(&weights, 0, layout, padding);
+        assert!((plain.error - (40.0 / 70.0 - 0.5)).abs() < 1e-12);
+        // the content of 70 is split 17.5, 17.5 and 35
+        let solved = rect.divide_for_content(&weights, 50, layout, padding);
+        assert!(solved.error < 1e-9);
+        for (content, width) in solved.content.iter().zip([17.5, 17.5, 35.0]) {
+            assert!((content.width() - width).abs() < 1e-6);
+        }
+        assert!((solved.cells[2].width() - 45.0).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,34 @@
+/// The orientation of the y axis.
+/// `Screen` coordinates grow downward (the default of this crate), `Math` coordinates grow upward (OpenGL, SVG viewBox flipped, plotting)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CoordinateSystem {
+    #[default]
+    Screen,
+    Math,
+}
+
+impl CoordinateSystem {
+    /// whether "top" means smaller y values
+    pub fn is_y_down(&self) -> bool {
+        match self {
+            CoordinateSystem::Screen => true,
+            CoordinateSystem::Math => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default() {
+        assert_eq!(CoordinateSystem::default(), CoordinateSystem::Screen);
+    }
+
+    #[test]
+    fn test_is_y_down() {
+        assert!(CoordinateSystem::Screen.is_y_down());
+        assert!(!CoordinateSystem::Math.is_y_down());
+    }
+}
@@ -0,0 +1,156 @@
+//! Canonical benchmark corpus.
+//!
+//! A fixed set of weight distributions and containers, generated from fixed seeds, so that benchmarks,
+//! fuzzing seeds and examples compare performance and quality on the same inputs over time.
+use crate::axis_aligned_rectangle::AxisAlignedRectangle;
+use crate::point::Point;
+use crate::rectangle::Rectangle;
+use crate::testing::{random_weights, Distribution, Rng};
+
+/// The seed every corpus input is generated from
+pub const SEED: u64 = 0x5EED;
+
+/// The weight counts of the corpus
+pub const SIZES: [usize; 4] = [10, 100, 1000, 5000];
+
+/// The kinds of weights in the corpus
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum WeightSet {
+    /// uniform between 1 and 10
+    Uniform,
+    /// the i-th largest weight is 1 / i, in shuffled order
+    Zipf,
+    /// two weights as large as all the others together, the rest 1
+    TwoSpike,
+    /// every weight 1
+    Ones,
+}
+
+impl WeightSet {
+    pub const ALL: [WeightSet; 4] = [
+        WeightSet::Uniform,
+        WeightSet::Zipf,
+        WeightSet::TwoSpike,
+        WeightSet::Ones,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            WeightSet::Uniform => "uniform",
+            WeightSet::Zipf => "zipf",
+            WeightSet::TwoSpike => "two-spike",
+            WeightSet::Ones => "ones",
+        }
+    }
+
+    /// n weights of the set, the same for the same seed
+    pub fn weights(&self, n: usize, seed: u64) -> Vec<f64> {
+        match self {
+            WeightSet::Uniform => random_weights(seed, n, Distribution::default()),
+            WeightSet::Zipf => {
+                let mut weights: Vec<f64> = (1..=n).map(|i| 1.0 / i as f64).collect();
+                shuffle(&mut weights, seed);
+                weights
+            }
+            WeightSet::TwoSpike => {
+                let mut weights = vec![1.0; n];
+                if n > 0 {
+                    let spike = n.saturating_sub(2).max(1) as f64;
+                    let mut rng = Rng::new(seed);
+                    let first = (rng.next_u64() % n as u64) as usize;
+                    // a different index for the second one, when there is one
+                    let offset = 1 + (rng.next_u64() % (n as u64 - 1).max(1)) as usize;
+                    weights[first] = spike;
+                    weights[(first + offset) % n] = spike;
+                }
+                weights
+            }
+            WeightSet::Ones => vec![1.0; n],
+        }
+    }
+}
+
+/// The containers of the corpus: their names and sizes, from square to extreme aspect ratios
+pub const CONTAINERS: [(&str, f64, f64); 5] = [
+    ("square", 1000.0, 1000.0),
+    ("landscape", 1920.0, 1080.0),
+    ("portrait", 1080.0, 1920.0),
+    ("banner", 10000.0, 10.0),
+    ("column", 10.0, 10000.0),
+];
+
+/// An input of the corpus
+#[derive(Debug, PartialEq, Clone)]
+pub struct Case {
+    /// e.g. `zipf-100-landscape`
+    pub name: String,
+    pub container: AxisAlignedRectangle<f64>,
+    pub weights: Vec<f64>,
+}
+
+/// every combination of weight set, size and container
+pub fn corpus() -> Vec<Case> {
+    let mut cases = Vec::new();
+    for set in WeightSet::ALL {
+        for n in SIZES {
+            let weights = set.weights(n, SEED);
+            for (name, width, height) in CONTAINERS {
+                cases.push(Case {
+                    name: format!("{}-{}-{}", set.name(), n, name),
+                    container: AxisAlignedRectangle::new(
+                        &Point::new(0.0, 0.0),
+                        &Rectangle::new(width, height),
+                    ),
+                    weights: weights.clone(),
+                });
+            }
+        }
+    }
+    cases
+}
+
+/// Fisher-Yates
+fn shuffle<A>(values: &mut [A], seed: u64) {
+    let mut rng = Rng::new(seed);
+    for i in (1..values.len()).rev() {
+        let j = (rng.next_u64() % (i as u64 + 1)) as usize;
+        values.swap(i, j);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_weights() {
+        for set in WeightSet::ALL {
+            for n in [0, 1, 2, 3, 50] {
+                let weights = set.weights(n, SEED);
+                assert_eq!(weights.len(), n);
+                assert_eq!(weights, set.weights(n, SEED));
+                assert!(weights.iter().all(|w| *w > 0.0));
+            }
+        }
+        let mut zipf = WeightSet::Zipf.weights(5, SEED);
+        zipf.sort_by(|a, b| b.total_cmp(a));
+        assert_eq!(zipf, vec![1.0, 0.5, 1.0 / 3.0, 0.25, 0.2]);
+        let spikes = WeightSet::TwoSpike.weights(100, SEED);
+        assert_eq!(spikes.iter().filter(|w| **w == 98.0).count(), 2);
+        assert_eq!(spikes.iter().filter(|w| **w == 1.0).count(), 98);
+    }
+
+    #[test]
+    fn test_corpus() {
+        let cases = corpus();
+        assert_eq!(
+            cases.len(),
+            WeightSet::ALL.len() * SIZES.len() * CONTAINERS.len()
+        );
+        assert_eq!(cases[0].name, "uniform-10-square");
+        let names: std::collections::HashSet<&str> =
+            cases.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names.len(), cases.len());
+        assert_eq!(corpus(), cases);
+    }
+}
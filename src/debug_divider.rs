@@ -0,0 +1,345 @@
+//! Invariant-checking wrapper for the dividing methods.
+//!
+//! `DebugDivider` calls the same methods as `Dividing` on its container and re-verifies the result:
+//! the cells tile the container, each covers the share of the area of its weight, and (for the strip
+//! methods, which aim at an aspect ratio) the cells respect the aspect ratio. any violation panics with
+//! a report of every violation, the inputs and the cells, so a regression is caught where it happens.
+use std::fmt::Write;
+
+use num_traits::{Float, Num, NumAssignOps, NumOps};
+
+use crate::axis::Axis;
+use crate::axis_aligned_rectangle::AxisAlignedRectangle;
+use crate::dividing::Dividing;
+use crate::emission_order::EmissionOrder;
+use crate::objective::LayoutObjective;
+use crate::rectangle::RectangleSize;
+use crate::selection::Algorithm;
+use crate::testing::{aspect_ratio_tolerance, verify_tiling};
+use crate::tolerance::{DefaultTolerance, Tolerance};
+use crate::weight::normalize_weights;
+
+/// A container whose dividing methods panic when their result breaks the invariants
+#[derive(Debug, Clone)]
+pub struct DebugDivider<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps,
+{
+    container: AxisAlignedRectangle<T>,
+    tolerance: Tolerance<T>,
+    aspect_ratio_tolerance: Tolerance<T>,
+}
+
+impl<T> DebugDivider<T>
+where
+    T: Copy
+        + std::fmt::Debug
+        + std::fmt::Display
+        + Num
+        + NumAssignOps
+        + NumOps
+        + Float
+        + DefaultTolerance
+        + for<'a> std::iter::Sum<&'a T>,
+{
+    pub fn new(container: AxisAlignedRectangle<T>) -> Self {
+        Self {
+            container,
+            tolerance: Tolerance::default(),
+            aspect_ratio_tolerance: aspect_ratio_tolerance(),
+        }
+    }
+
+    /// the tolerance of the tiling and of the shares of the area (the default tolerance of the type by default)
+    pub fn with_tolerance(mut self, tolerance: Tolerance<T>) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// the tolerance of the aspect ratios of strip layouts (see `testing::aspect_ratio_tolerance`)
+    pub fn with_aspect_ratio_tolerance(mut self, tolerance: Tolerance<T>) -> Self {
+        self.aspect_ratio_tolerance = tolerance;
+        self
+    }
+
+    pub fn container(&self) -> &AxisAlignedRectangle<T> {
+        &self.container
+    }
+
+    /// see `Dividing::divide_by_weights_and_axis`
+    pub fn divide_by_weights_and_axis(
+        &self,
+        weights: &[T],
+        axis: Axis,
+    ) -> Vec<AxisAlignedRectangle<T>> {
+        let divided = self.container.divide_by_weights_and_axis(weights, axis);
+        let inputs = format!("axis: {:?}", axis);
+        self.check(
+            "divide_by_weights_and_axis",
+            &inputs,
+            weights,
+            None,
+            divided,
+        )
+    }
+
+    /// see `Dividing::divide_strips`
+    pub fn divide_strips(
+        &self,
+        weights: &[T],
+        primary_axis: Axis,
+        aspect_ratio: T,
+        order: EmissionOrder,
+    ) -> Vec<AxisAlignedRectangle<T>> {
+        let divided = self
+            .container
+            .divide_strips(weights, primary_axis, aspect_ratio, order);
+        let inputs = format!("primary axis: {:?}, order: {:?}", primary_axis, order);
+        self.check(
+            "divide_strips",
+            &inputs,
+            weights,
+            Some(aspect_ratio),
+            divided,
+        )
+    }
+
+    /// see `Dividing::divide_vertical_then_horizontal_with_weights`
+    pub fn divide_vertical_then_horizontal_with_weights(
+        &self,
+        weights: &[T],
+        aspect_ratio: T,
+        boustrophedon: bool,
+    ) -> Vec<AxisAlignedRectangle<T>> {
+        let divided = self.container.divide_vertical_then_horizontal_with_weights(
+            weights,
+            aspect_ratio,
+            boustrophedon,
+        );
+        let inputs = format!("boustrophedon: {}", boustrophedon);
+        self.check(
+            "divide_vertical_then_horizontal_with_weights",
+            &inputs,
+            weights,
+            Some(aspect_ratio),
+            divided,
+        )
+    }
+
+    /// see `Dividing::divide_horizontal_then_vertical_with_weights`
+    pub fn divide_horizontal_then_vertical_with_weights(
+        &self,
+        weights: &[T],
+        aspect_ratio: T,
+        boustrophedon: bool,
+    ) -> Vec<AxisAlignedRectangle<T>> {
+        let divided = self.container.divide_horizontal_then_vertical_with_weights(
+            weights,
+            aspect_ratio,
+            boustrophedon,
+        );
+        let inputs = format!("boustrophedon: {}", boustrophedon);
+        self.check(
+            "divide_horizontal_then_vertical_with_weights",
+            &inputs,
+            weights,
+            Some(aspect_ratio),
+            divided,
+        )
+    }
+
+    /// see `Dividing::divide_best`
+    pub fn divide_best<O>(
+        &self,
+        weights: &[T],
+        aspect_ratio: T,
+        objective: O,
+    ) -> (Algorithm, Vec<AxisAlignedRectangle<T>>)
+    where
+        O: LayoutObjective<T, AxisAlignedRectangle<T>>,
+    {
+        let (algorithm, divided) = self.container.divide_best(weights, aspect_ratio, objective);
+        let target = match algorithm {
+            Algorithm::VerticalStrips | Algorithm::HorizontalStrips => Some(aspect_ratio),
+            Algorithm::VerticalSlices
+            | Algorithm::HorizontalSlices
+            | Algorithm::Squarified
+            | Algorithm::Binary => None,
+        };
+        let inputs = format!("chosen algorithm: {:?}", algorithm);
+        let divided = self.check("divide_best", &inputs, weights, target, divided);
+        (algorithm, divided)
+    }
+
+    /// the broken invariants of the cells, empty when there are none
+    pub fn violations(
+        &self,
+        weights: &[T],
+        aspect_ratio: Option<T>,
+        divided: &[AxisAlignedRectangle<T>],
+    ) -> Vec<String> {
+        let mut violations = Vec::new();
+        if divided.len() != weights.len() {
+            violations.push(format!(
+                "{} cells for {} weights",
+                divided.len(),
+                weights.len()
+            ));
+        }
+        if let Err(e) = verify_tiling(&self.container, divided, &self.tolerance) {
+            violations.push(e.to_string());
+        }
+        let areas: Vec<T> = divided.iter().map(|c| c.width() * c.height()).collect();
+        let shares = normalize_weights(&areas);
+        let normalized_weights = normalize_weights(weights);
+        for (i, (w, share)) in normalized_weights.iter().zip(shares.iter()).enumerate() {
+            if !self.tolerance.approx_eq(*w, *share) {
+                violations.push(format!(
+                    "cell {} covers {} of the area for the normalized weight {}",
+                    i, share, w
+                ));
+            }
+        }
+        if let Some(target) = aspect_ratio {
+            for (i, (cell, w)) in divided.iter().zip(normalized_weights.iter()).enumerate() {
+                let actual = cell.width() / cell.height();
+                if !self
+                    .aspect_ratio_tolerance
+                    .approx_eq((actual - target).abs() * *w, T::zero())
+                {
+                    violations.push(format!(
+                        "cell {} has the aspect ratio {} for the target {}",
+                        i, actual, target
+                    ));
+                }
+            }
+        }
+        violations
+    }
+
+    fn check(
+        &self,
+        method: &str,
+        inputs: &str,
+        weights: &[T],
+        aspect_ratio: Option<T>,
+        divided: Vec<AxisAlignedRectangle<T>>,
+    ) -> Vec<AxisAlignedRectangle<T>> {
+        let violations = self.violations(weights, aspect_ratio, &divided);
+        if violations.is_empty() {
+            return divided;
+        }
+        let mut report = format!("{} broke its invariants:\n", method);
+        for violation in &violations {
+            let _ = writeln!(report, "  - {}", violation);
+        }
+        let _ = writeln!(report, "container: {:?}", self.container);
+        let _ = writeln!(report, "weights: {:?}", weights);
+        if let Some(aspect_ratio) = aspect_ratio {
+            let _ = writeln!(report, "aspect ratio: {}", aspect_ratio);
+        }
+        let _ = writeln!(report, "{}", inputs);
+        let _ = writeln!(report, "cells:");
+        for (i, cell) in divided.iter().enumerate() {
+            let _ = writeln!(report, "  {} {:?}", i, cell);
+        }
+        panic!("{}", report);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::objective::MaxAspectError;
+    use crate::point::Point;
+    use crate::rectangle::Rectangle;
+
+    fn divider() -> DebugDivider<f64> {
+        DebugDivider::new(AxisAlignedRectangle::new(
+            &Point::new(0.0, 0.0),
+            &Rectangle::new(600.0, 400.0),
+        ))
+    }
+
+    #[test]
+    fn test_passes_through() {
+        let divider = divider();
+        let weights = [3.0, 2.0, 1.0, 1.0, 1.0];
+        assert_eq!(
+            divider.divide_strips(&weights, Axis::Vertical, 1.5, EmissionOrder::Forward),
+            divider.container().divide_strips(
+                &weights,
+                Axis::Vertical,
+                1.5,
+                EmissionOrder::Forward
+            )
+        );
+        assert_eq!(
+            divider
+                .divide_by_weights_and_axis(&weights, Axis::Horizontal)
+                .len(),
+            5
+        );
+        assert_eq!(
+            divider
+                .divide_vertical_then_horizontal_with_weights(&weights, 1.5, true)
+                .len(),
+            5
+        );
+        assert_eq!(
+            divider
+                .divide_horizontal_then_vertical_with_weights(&weights, 1.5, false)
+                .len(),
+            5
+        );
+        let (_, divided) = divider.divide_best(&weights, 1.5, MaxAspectError { target: 1.5 });
+        assert_eq!(divided.len(), 5);
+    }
+
+    #[test]
+    fn test_violations() {
+        let divider = divider();
+        let weights = [1.0, 1.0];
+        let overlapping = [
+            AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(400.0, 400.0)),
+            AxisAlignedRectangle::new(&Point::new(300.0, 0.0), &Rectangle::new(300.0, 400.0)),
+        ];
+        assert_eq!(
+            divider.violations(&weights, None, &overlapping)[0],
+            "cells 0 and 1 overlap"
+        );
+        let uneven = [
+            AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(510.0, 400.0)),
+            AxisAlignedRectangle::new(&Point::new(510.0, 0.0), &Rectangle::new(90.0, 400.0)),
+        ];
+        assert_eq!(
+            divider.violations(&weights, Some(1.0), &uneven),
+            vec![
+                "cell 0 covers 0.85 of the area for the normalized weight 0.5".to_string(),
+                "cell 1 covers 0.15 of the area for the normalized weight 0.5".to_string(),
+            ]
+        );
+        assert_eq!(
+            divider.violations(&weights[..1], None, &uneven),
+            vec![
+                "2 cells for 1 weights".to_string(),
+                "cell 0 covers 0.85 of the area for the normalized weight 1".to_string(),
+            ]
+        );
+        assert!(divider
+            .violations(
+                &weights,
+                None,
+                &divider.divide_by_weights_and_axis(&weights, Axis::Vertical)
+            )
+            .is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "divide_strips broke its invariants")]
+    fn test_panics_with_report() {
+        // an impossible aspect ratio tolerance makes any strip layout a violation
+        let divider = divider().with_aspect_ratio_tolerance(Tolerance::absolute(-1.0));
+        divider.divide_strips(&[1.0, 2.0], Axis::Vertical, 1.0, EmissionOrder::Forward);
+    }
+}
@@ -0,0 +1,83 @@
+//! Decimal (arbitrary precision) backend.
+//!
+//! Binary floats cannot represent most monetary amounts exactly, so weights like `0.1` drift
+//! while being normalized. `Decimal` keeps 28 significant digits in base 10, and every dividing
+//! method works with it as the coordinate and weight type.
+pub use rust_decimal::Decimal;
+
+use crate::axis_aligned_rectangle::AxisAlignedRectangle;
+use crate::component::Component;
+use crate::point::Point;
+use crate::rectangle::{Rectangle, RectangleSize};
+
+impl Rectangle<Decimal> {
+    /// round width and height to the given number of decimal places
+    pub fn round_dp(&self, dp: u32) -> Self {
+        Self::new(self.width().round_dp(dp), self.height().round_dp(dp))
+    }
+}
+
+impl AxisAlignedRectangle<Decimal> {
+    /// round the edges to the given number of decimal places.
+    /// the right bottom edge is rounded independently so that adjacent rectangles still share edges
+    pub fn round_dp(&self, dp: u32) -> Self {
+        let x = self.x().round_dp(dp);
+        let y = self.y().round_dp(dp);
+        let right = (self.x() + self.width()).round_dp(dp);
+        let bottom = (self.y() + self.height()).round_dp(dp);
+        Self::new(&Point::new(x, y), &Rectangle::new(right - x, bottom - y))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::area::Area;
+    use crate::axis::Axis;
+    use crate::dividing::Dividing;
+
+    #[test]
+    fn test_divide_by_weights_is_exact() {
+        let rect = Rectangle::new(Decimal::new(100, 0), Decimal::new(1, 0));
+        let weights = vec![Decimal::new(1, 1), Decimal::new(2, 1), Decimal::new(7, 1)];
+        let divided = rect.divide_by_weights_and_axis(&weights, Axis::Vertical);
+        assert_eq!(divided[0].width(), Decimal::new(10, 0));
+        assert_eq!(divided[1].width(), Decimal::new(20, 0));
+        assert_eq!(divided[2].width(), Decimal::new(70, 0));
+    }
+
+    #[test]
+    fn test_divide_with_weights_keeps_total_area() {
+        let rect = AxisAlignedRectangle::new(
+            &Point::new(Decimal::ZERO, Decimal::ZERO),
+            &Rectangle::new(Decimal::new(300, 0), Decimal::new(200, 0)),
+        );
+        let weights = vec![
+            Decimal::new(4, 0),
+            Decimal::new(3, 0),
+            Decimal::new(2, 0),
+            Decimal::new(1, 0),
+        ];
+        let divided =
+            rect.divide_vertical_then_horizontal_with_weights(&weights, Decimal::ONE, false);
+        assert_eq!(divided.len(), 4);
+        let total: Decimal = divided.iter().map(|r| r.area()).sum();
+        assert_eq!(total.round_dp(20), rect.area());
+    }
+
+    #[test]
+    fn test_round_dp() {
+        let rect = AxisAlignedRectangle::new(
+            &Point::new(Decimal::new(1234, 3), Decimal::new(1, 0)),
+            &Rectangle::new(Decimal::new(1004, 3), Decimal::new(3333, 3)),
+        );
+        let rounded = rect.round_dp(2);
+        assert_eq!(rounded.x(), Decimal::new(123, 2));
+        assert_eq!(rounded.width(), Decimal::new(101, 2));
+        assert_eq!(rounded.height(), Decimal::new(333, 2));
+        assert_eq!(
+            Rectangle::new(Decimal::new(1005, 3), Decimal::new(1, 0)).round_dp(2),
+            Rectangle::new(Decimal::new(100, 2), Decimal::new(1, 0))
+        );
+    }
+}
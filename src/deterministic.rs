@@ -0,0 +1,123 @@
+//! Deterministic cross-platform float mode.
+//!
+//! Rust never fuses `a * b + c` into an FMA on its own, and the dividing algorithms only use
+//! the basic IEEE 754 operations (which are correctly rounded everywhere) in a fixed order.
+//! Computing everything in `f64` and quantizing the edges afterwards therefore gives
+//! bit-identical layouts on x86, ARM and wasm, regardless of the caller's coordinate type.
+use num_traits::{Float, Num, NumAssignOps, NumOps};
+
+use crate::axis_aligned_rectangle::AxisAlignedRectangle;
+use crate::component::Component;
+use crate::dividing::Dividing;
+use crate::point::Point;
+use crate::rectangle::{Rectangle, RectangleSize};
+
+/// round a value to the nearest multiple of `step`. a non-positive step leaves the value as is
+pub fn quantize(value: f64, step: f64) -> f64 {
+    if step <= 0.0 {
+        return value;
+    }
+    (value / step).round() * step
+}
+
+/// quantize the edges (not the sizes) of a rectangle so that adjacent rectangles keep sharing edges
+pub fn quantize_rect(rect: &AxisAlignedRectangle<f64>, step: f64) -> AxisAlignedRectangle<f64> {
+    let x = quantize(rect.x(), step);
+    let y = quantize(rect.y(), step);
+    let right = quantize(rect.x() + rect.width(), step);
+    let bottom = quantize(rect.y() + rect.height(), step);
+    AxisAlignedRectangle::new(&Point::new(x, y), &Rectangle::new(right - x, bottom - y))
+}
+
+/// dividing a rectangle by weights with all intermediate computation in f64,
+/// quantizing every output edge to a multiple of `precision`
+pub fn divide_deterministic<T>(
+    rect: &AxisAlignedRectangle<T>,
+    weights: &[T],
+    aspect_ratio: T,
+    vertical_first: bool,
+    boustrophedon: bool,
+    precision: f64,
+) -> Vec<AxisAlignedRectangle<T>>
+where
+    T: Copy + Num + NumAssignOps + NumOps + Float,
+{
+    let rect = AxisAlignedRectangle::new(
+        &Point::new(to_f64(rect.x()), to_f64(rect.y())),
+        &Rectangle::new(to_f64(rect.width()), to_f64(rect.height())),
+    );
+    let weights: Vec<f64> = weights.iter().map(|w| to_f64(*w)).collect();
+    let aspect_ratio = to_f64(aspect_ratio);
+    let divided = match vertical_first {
+        true => {
+            rect.divide_vertical_then_horizontal_with_weights(&weights, aspect_ratio, boustrophedon)
+        }
+        false => {
+            rect.divide_horizontal_then_vertical_with_weights(&weights, aspect_ratio, boustrophedon)
+        }
+    };
+    divided
+        .iter()
+        .map(|r| quantize_rect(r, precision))
+        .map(|r| {
+            AxisAlignedRectangle::new(
+                &Point::new(from_f64(r.x()), from_f64(r.y())),
+                &Rectangle::new(from_f64(r.width()), from_f64(r.height())),
+            )
+        })
+        .collect()
+}
+
+fn to_f64<T: Float>(value: T) -> f64 {
+    value.to_f64().unwrap_or(f64::NAN)
+}
+
+fn from_f64<T: Float>(value: f64) -> T {
+    T::from(value).unwrap_or_else(T::nan)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quantize() {
+        assert_eq!(quantize(1.234, 0.5), 1.0);
+        assert_eq!(quantize(1.26, 0.5), 1.5);
+        assert_eq!(quantize(1.234, 0.0), 1.234);
+        assert_eq!(quantize(-1.26, 0.5), -1.5);
+    }
+
+    #[test]
+    fn test_quantize_rect_keeps_shared_edges() {
+        let a = AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(3.3, 1.0));
+        let b = AxisAlignedRectangle::new(&Point::new(3.3, 0.0), &Rectangle::new(3.3, 1.0));
+        let a = quantize_rect(&a, 1.0);
+        let b = quantize_rect(&b, 1.0);
+        assert_eq!(a.x() + a.width(), b.x());
+        assert_eq!(b.x() + b.width(), 7.0);
+    }
+
+    #[test]
+    fn test_divide_deterministic() {
+        let rect = AxisAlignedRectangle::new(&Point::new(0.0f32, 0.0), &Rectangle::new(9.0, 8.0));
+        let weights = vec![4.0f32, 4.0, 1.0, 1.0, 1.0, 1.0];
+        let divided = divide_deterministic(&rect, &weights, 1.5, true, false, 1.0);
+        let expected = rect.divide_vertical_then_horizontal_with_weights(&weights, 1.5, false);
+        assert_eq!(divided.len(), expected.len());
+        for (d, e) in divided.iter().zip(expected.iter()) {
+            assert_eq!(*d, e.round());
+        }
+
+        // same input gives bit-identical output, for f32 and f64 alike
+        let rect64 = AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(9.0, 8.0));
+        let weights64: Vec<f64> = weights.iter().map(|w| *w as f64).collect();
+        let divided64 = divide_deterministic(&rect64, &weights64, 1.5, true, false, 1.0);
+        for (d, e) in divided.iter().zip(divided64.iter()) {
+            assert_eq!(d.x() as f64, e.x());
+            assert_eq!(d.y() as f64, e.y());
+            assert_eq!(d.width() as f64, e.width());
+            assert_eq!(d.height() as f64, e.height());
+        }
+    }
+}
@@ -0,0 +1,261 @@
+use crate::axis::Axis;
+use crate::coordinate_system::CoordinateSystem;
+
+/// The direction in which divided rectangles are emitted
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FlowDirection {
+    LeftToRight,
+    RightToLeft,
+    TopToBottom,
+    BottomToTop,
+}
+
+impl FlowDirection {
+    /// the forward (left to right, top to bottom) direction along the axis
+    pub fn forward(axis: Axis) -> Self {
+        match axis {
+            Axis::Vertical => FlowDirection::LeftToRight,
+            Axis::Horizontal => FlowDirection::TopToBottom,
+        }
+    }
+
+    /// the backward (right to left, bottom to top) direction along the axis
+    pub fn backward(axis: Axis) -> Self {
+        Self::forward(axis).reverse()
+    }
+
+    /// the axis of the dividing lines. flowing left to right means cutting with vertical lines
+    pub fn axis(&self) -> Axis {
+        match self {
+            FlowDirection::LeftToRight | FlowDirection::RightToLeft => Axis::Vertical,
+            FlowDirection::TopToBottom | FlowDirection::BottomToTop => Axis::Horizontal,
+        }
+    }
+
+    /// whether the direction flows with increasing coordinates
+    pub fn is_forward(&self) -> bool {
+        match self {
+            FlowDirection::LeftToRight | FlowDirection::TopToBottom => true,
+            FlowDirection::RightToLeft | FlowDirection::BottomToTop => false,
+        }
+    }
+
+    pub fn reverse(&self) -> Self {
+        match self {
+            FlowDirection::LeftToRight => FlowDirection::RightToLeft,
+            FlowDirection::RightToLeft => FlowDirection::LeftToRight,
+            FlowDirection::TopToBottom => FlowDirection::BottomToTop,
+            FlowDirection::BottomToTop => FlowDirection::TopToBottom,
+        }
+    }
+
+    /// the direction expressed in screen coordinates (y grows downward)
+    pub fn to_screen(&self, coordinate_system: CoordinateSystem) -> Self {
+        match (self.axis(), coordinate_system) {
+            (Axis::Horizontal, CoordinateSystem::Math) => self.reverse(),
+            _ => *self,
+        }
+    }
+}
+
+/// The corner where the first divided rectangle is placed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StartCorner {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+impl StartCorner {
+    /// the direction of flow along the axis when starting from this corner
+    pub fn direction_for_axis(&self, axis: Axis) -> FlowDirection {
+        match axis {
+            Axis::Vertical => self.horizontal_direction(),
+            Axis::Horizontal => self.vertical_direction(),
+        }
+    }
+
+    /// left to right when starting from the left, otherwise right to left
+    pub fn horizontal_direction(&self) -> FlowDirection {
+        match self {
+            StartCorner::TopLeft | StartCorner::BottomLeft => FlowDirection::LeftToRight,
+            StartCorner::TopRight | StartCorner::BottomRight => FlowDirection::RightToLeft,
+        }
+    }
+
+    /// top to bottom when starting from the top, otherwise bottom to top
+    pub fn vertical_direction(&self) -> FlowDirection {
+        match self {
+            StartCorner::TopLeft | StartCorner::TopRight => FlowDirection::TopToBottom,
+            StartCorner::BottomLeft | StartCorner::BottomRight => FlowDirection::BottomToTop,
+        }
+    }
+
+    /// the corner expressed in screen coordinates (y grows downward).
+    /// the top left corner in math coordinates is the bottom left corner on the screen
+    pub fn to_screen(&self, coordinate_system: CoordinateSystem) -> Self {
+        match coordinate_system {
+            CoordinateSystem::Screen => *self,
+            CoordinateSystem::Math => match self {
+                StartCorner::TopLeft => StartCorner::BottomLeft,
+                StartCorner::TopRight => StartCorner::BottomRight,
+                StartCorner::BottomLeft => StartCorner::TopLeft,
+                StartCorner::BottomRight => StartCorner::TopRight,
+            },
+        }
+    }
+
+    /// the corresponding corner after swapping x and y (see `QuarterRotation`)
+    pub fn transpose(&self) -> Self {
+        match self {
+            StartCorner::TopLeft => StartCorner::TopLeft,
+            StartCorner::TopRight => StartCorner::BottomLeft,
+            StartCorner::BottomLeft => StartCorner::TopRight,
+            StartCorner::BottomRight => StartCorner::BottomRight,
+        }
+    }
+}
+
+/// The reading direction of a layout. right to left layouts start from the top right corner
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TextDirection {
+    #[default]
+    Ltr,
+    Rtl,
+}
+
+impl TextDirection {
+    pub fn start_corner(&self) -> StartCorner {
+        match self {
+            TextDirection::Ltr => StartCorner::TopLeft,
+            TextDirection::Rtl => StartCorner::TopRight,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_axis() {
+        assert_eq!(FlowDirection::LeftToRight.axis(), Axis::Vertical);
+        assert_eq!(FlowDirection::RightToLeft.axis(), Axis::Vertical);
+        assert_eq!(FlowDirection::TopToBottom.axis(), Axis::Horizontal);
+        assert_eq!(FlowDirection::BottomToTop.axis(), Axis::Horizontal);
+    }
+
+    #[test]
+    fn test_forward_and_backward() {
+        assert_eq!(
+            FlowDirection::forward(Axis::Vertical),
+            FlowDirection::LeftToRight
+        );
+        assert_eq!(
+            FlowDirection::backward(Axis::Horizontal),
+            FlowDirection::BottomToTop
+        );
+        for axis in [Axis::Vertical, Axis::Horizontal] {
+            assert!(FlowDirection::forward(axis).is_forward());
+            assert!(!FlowDirection::backward(axis).is_forward());
+            assert_eq!(FlowDirection::forward(axis).axis(), axis);
+            assert_eq!(FlowDirection::backward(axis).axis(), axis);
+        }
+    }
+
+    #[test]
+    fn test_reverse() {
+        assert_eq!(
+            FlowDirection::LeftToRight.reverse(),
+            FlowDirection::RightToLeft
+        );
+        assert_eq!(
+            FlowDirection::BottomToTop.reverse(),
+            FlowDirection::TopToBottom
+        );
+        assert_eq!(
+            FlowDirection::TopToBottom.reverse().reverse(),
+            FlowDirection::TopToBottom
+        );
+    }
+
+    #[test]
+    fn test_start_corner_directions() {
+        assert_eq!(
+            StartCorner::TopLeft.direction_for_axis(Axis::Vertical),
+            FlowDirection::LeftToRight
+        );
+        assert_eq!(
+            StartCorner::TopLeft.direction_for_axis(Axis::Horizontal),
+            FlowDirection::TopToBottom
+        );
+        assert_eq!(
+            StartCorner::BottomRight.direction_for_axis(Axis::Vertical),
+            FlowDirection::RightToLeft
+        );
+        assert_eq!(
+            StartCorner::BottomRight.direction_for_axis(Axis::Horizontal),
+            FlowDirection::BottomToTop
+        );
+        assert_eq!(
+            StartCorner::TopRight.vertical_direction(),
+            FlowDirection::TopToBottom
+        );
+        assert_eq!(
+            StartCorner::BottomLeft.horizontal_direction(),
+            FlowDirection::LeftToRight
+        );
+    }
+
+    #[test]
+    fn test_start_corner_transpose() {
+        for corner in [
+            StartCorner::TopLeft,
+            StartCorner::TopRight,
+            StartCorner::BottomLeft,
+            StartCorner::BottomRight,
+        ] {
+            assert_eq!(corner.transpose().transpose(), corner);
+            assert_eq!(
+                corner.transpose().horizontal_direction().is_forward(),
+                corner.vertical_direction().is_forward()
+            );
+        }
+    }
+
+    #[test]
+    fn test_to_screen() {
+        assert_eq!(
+            StartCorner::TopLeft.to_screen(CoordinateSystem::Screen),
+            StartCorner::TopLeft
+        );
+        assert_eq!(
+            StartCorner::TopLeft.to_screen(CoordinateSystem::Math),
+            StartCorner::BottomLeft
+        );
+        assert_eq!(
+            StartCorner::BottomRight.to_screen(CoordinateSystem::Math),
+            StartCorner::TopRight
+        );
+        assert_eq!(
+            FlowDirection::TopToBottom.to_screen(CoordinateSystem::Math),
+            FlowDirection::BottomToTop
+        );
+        assert_eq!(
+            FlowDirection::LeftToRight.to_screen(CoordinateSystem::Math),
+            FlowDirection::LeftToRight
+        );
+        assert_eq!(
+            FlowDirection::TopToBottom.to_screen(CoordinateSystem::Screen),
+            FlowDirection::TopToBottom
+        );
+    }
+
+    #[test]
+    fn test_text_direction() {
+        assert_eq!(TextDirection::default(), TextDirection::Ltr);
+        assert_eq!(TextDirection::Ltr.start_corner(), StartCorner::TopLeft);
+        assert_eq!(TextDirection::Rtl.start_corner(), StartCorner::TopRight);
+    }
+}
@@ -0,0 +1,176 @@
+use num_traits::{Num, NumAssignOps, NumOps};
+
+use crate::axis::Axis;
+use crate::axis_aligned_rectangle::AxisAlignedRectangle;
+use crate::component::Component;
+use crate::dividing::Dividing;
+use crate::emission_order::EmissionOrder;
+use crate::point::Point;
+use crate::rectangle::{Rectangle, RectangleSize};
+
+/// A reusable dividing configuration, built by `Divider::builder()`
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Divider<T> {
+    aspect_ratio: T,
+    vertical_first: bool,
+    boustrophedon: bool,
+    gutter: T,
+}
+
+/// Builds a `Divider`. by default: aspect ratio 1, vertical first, no boustrophedon and no gutter
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct DividerBuilder<T> {
+    divider: Divider<T>,
+}
+
+impl<T> Divider<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps,
+{
+    pub fn builder() -> DividerBuilder<T> {
+        DividerBuilder {
+            divider: Self {
+                aspect_ratio: T::one(),
+                vertical_first: true,
+                boustrophedon: false,
+                gutter: T::zero(),
+            },
+        }
+    }
+
+    pub fn aspect_ratio(&self) -> T {
+        self.aspect_ratio
+    }
+
+    pub fn vertical_first(&self) -> bool {
+        self.vertical_first
+    }
+
+    pub fn boustrophedon(&self) -> bool {
+        self.boustrophedon
+    }
+
+    pub fn gutter(&self) -> T {
+        self.gutter
+    }
+}
+
+impl<T> Divider<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps + PartialOrd + for<'a> std::iter::Sum<&'a T>,
+{
+    /// dividing the rectangle by the weights with the configuration
+    /// (see `divide_vertical_then_horizontal_with_weights`). the n-th cell corresponds to the n-th weight.
+    /// with a gutter, adjacent cells are the gutter apart and the outer cells touch the container:
+    /// the container grown by the gutter is divided, and each cell gives the gutter back on its right and bottom
+    pub fn divide(
+        &self,
+        rect: &AxisAlignedRectangle<T>,
+        weights: &[T],
+    ) -> Vec<AxisAlignedRectangle<T>> {
+        let primary_axis = match self.vertical_first {
+            true => Axis::Vertical,
+            false => Axis::Horizontal,
+        };
+        let order = EmissionOrder::from_boustrophedon(self.boustrophedon);
+        if self.gutter == T::zero() {
+            return rect.divide_strips(weights, primary_axis, self.aspect_ratio, order);
+        }
+        let grown = AxisAlignedRectangle::new(
+            &rect.origin(),
+            &Rectangle::new(rect.width() + self.gutter, rect.height() + self.gutter),
+        );
+        let shrink = |size: T| match size > self.gutter {
+            true => size - self.gutter,
+            false => T::zero(),
+        };
+        grown
+            .divide_strips(weights, primary_axis, self.aspect_ratio, order)
+            .into_iter()
+            .map(|cell| {
+                AxisAlignedRectangle::new(
+                    &Point::new(cell.x(), cell.y()),
+                    &Rectangle::new(shrink(cell.width()), shrink(cell.height())),
+                )
+            })
+            .collect()
+    }
+}
+
+impl<T> DividerBuilder<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps,
+{
+    /// the aspect ratio (width / height) the cells aim at
+    pub fn aspect_ratio(mut self, aspect_ratio: T) -> Self {
+        self.divider.aspect_ratio = aspect_ratio;
+        self
+    }
+
+    /// whether the container is first cut into columns (or rows)
+    pub fn vertical_first(mut self, vertical_first: bool) -> Self {
+        self.divider.vertical_first = vertical_first;
+        self
+    }
+
+    /// whether every other strip is filled in the reverse direction
+    pub fn boustrophedon(mut self, boustrophedon: bool) -> Self {
+        self.divider.boustrophedon = boustrophedon;
+        self
+    }
+
+    /// the space between adjacent cells
+    pub fn gutter(mut self, gutter: T) -> Self {
+        self.divider.gutter = gutter;
+        self
+    }
+
+    pub fn build(self) -> Divider<T> {
+        self.divider
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_divide() {
+        let rect = AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(60.0, 40.0));
+        let weights = [3.0, 1.0, 2.0, 2.0];
+        let divider = Divider::builder()
+            .aspect_ratio(1.5)
+            .vertical_first(false)
+            .boustrophedon(true)
+            .build();
+        assert_eq!(divider.aspect_ratio(), 1.5);
+        assert!(!divider.vertical_first());
+        assert_eq!(
+            divider.divide(&rect, &weights),
+            rect.divide_horizontal_then_vertical_with_weights(&weights, 1.5, true)
+        );
+        // reusable
+        assert_eq!(
+            divider.divide(&rect, &weights),
+            divider.divide(&rect, &weights)
+        );
+        assert_eq!(
+            Divider::builder().build().divide(&rect, &weights),
+            rect.divide_vertical_then_horizontal_with_weights(&weights, 1.0, false)
+        );
+    }
+
+    #[test]
+    fn test_gutter() {
+        let rect = AxisAlignedRectangle::new(&Point::new(10.0, 0.0), &Rectangle::new(98.0, 50.0));
+        let divider = Divider::builder().aspect_ratio(0.1).gutter(2.0).build();
+        let divided = divider.divide(&rect, &[1.0, 1.0]);
+        assert_eq!(
+            divided,
+            vec![
+                AxisAlignedRectangle::new(&Point::new(10.0, 0.0), &Rectangle::new(48.0, 50.0)),
+                AxisAlignedRectangle::new(&Point::new(60.0, 0.0), &Rectangle::new(48.0, 50.0)),
+            ]
+        );
+    }
+}
@@ -1,11 +1,25 @@
-use num_traits::{Num, NumAssignOps, NumOps};
+use num_traits::{Float, Num, NumAssignOps, NumCast, NumOps};
 
 use crate::{
     area::Area,
     axis::{Axis, SizeForAxis},
+    coordinate_system::CoordinateSystem,
+    direction::{FlowDirection, StartCorner},
+    emission_order::EmissionOrder,
+    fill_order::{FillOrder, SPIRAL_SIDES},
+    hierarchy::LayoutNode,
+    metrics::worst_aspect_error,
+    objective::{LayoutObjective, MeanAspectError},
+    pivot::PivotStrategy,
     rectangle::RectangleSize,
     rotate::QuarterRotation,
-    weight::normalize_weights,
+    selection::Algorithm,
+    split_tree::SplitTree,
+    trace::{StripDecision, StripOutcome},
+    weight::{
+        check_percentages, normalize_weights, restore_order, sort_weights_by,
+        sort_weights_descending, PercentageError,
+    },
 };
 
 pub trait Dividing<T> {
@@ -19,6 +33,22 @@ pub trait Dividing<T> {
     where
         Self: Sized;
 
+    /// dividing a rectangle into two rectangles (horizontal), returning the top one first.
+    /// the top one has the height `y`, where "top" follows the coordinate system
+    fn divide_horizontal_in(&self, y: T, coordinate_system: CoordinateSystem) -> (Self, Self)
+    where
+        Self: Sized + RectangleSize<T>,
+        T: Copy + Num + NumAssignOps + NumOps,
+    {
+        match coordinate_system {
+            CoordinateSystem::Screen => self.divide_horizontal(y),
+            CoordinateSystem::Math => {
+                let (bottom, top) = self.divide_horizontal(self.height() - y);
+                (top, bottom)
+            }
+        }
+    }
+
     /// dividing a rectangle into two rectangles specified by axis
     fn divide(&self, v: T, axis: Axis) -> (Self, Self)
     where
@@ -67,64 +97,371 @@ pub trait Dividing<T> {
         self.divide_by_values_and_axis(&values, axis)
     }
 
-    fn divide_vertical_then_horizontal_with_weights(
+    /// same as `divide_by_weights_and_axis`, for percentages. they are checked first (see `check_percentages`),
+    /// so that a layout from a configuration file that does not add up is reported rather than stretched.
+    /// each cell is `percentage * size / 100` long (the last one takes the rest), so integer sizes divide as expected
+    fn divide_by_percentages(
+        &self,
+        percentages: &[T],
+        axis: Axis,
+    ) -> Result<Vec<Self>, PercentageError<T>>
+    where
+        Self: Sized + RectangleSize<T> + Clone + SizeForAxis<T>,
+        T: Copy
+            + for<'a> std::iter::Sum<&'a T>
+            + Num
+            + NumAssignOps
+            + NumOps
+            + NumCast
+            + PartialOrd,
+    {
+        check_percentages(percentages)?;
+        if percentages.len() <= 1 {
+            return Ok(self.divide_by_weights_and_axis(percentages, axis));
+        }
+        let hundred = T::from(100).unwrap_or_else(T::one);
+        let size: T = self.size_for_axis(axis);
+        let values: Vec<T> = percentages[..percentages.len() - 1]
+            .iter()
+            .map(|p| *p * size / hundred)
+            .collect();
+        Ok(self.divide_by_values_and_axis(&values, axis))
+    }
+
+    /// dividing a rectangle into specified weights of rectangles flowing in the direction.
+    /// the n-th rectangle always corresponds to the n-th weight, and the first one touches the start edge
+    fn divide_by_weights_and_direction(&self, weights: &[T], direction: FlowDirection) -> Vec<Self>
+    where
+        Self: Sized + RectangleSize<T> + Clone + SizeForAxis<T>,
+        T: Copy + for<'a> std::iter::Sum<&'a T> + Num + NumAssignOps + NumOps,
+    {
+        if direction.is_forward() {
+            return self.divide_by_weights_and_axis(weights, direction.axis());
+        }
+        let reversed_weights: Vec<T> = weights.iter().rev().copied().collect();
+        let mut divided = self.divide_by_weights_and_axis(&reversed_weights, direction.axis());
+        divided.reverse();
+        divided
+    }
+
+    /// dividing a rectangle by weights into strips (columns when the primary axis is vertical, rows when horizontal).
+    /// weights are picked in order into a strip until its first cell reaches the aspect ratio (width / height),
+    /// then each strip is divided by its own weights along the other axis
+    fn divide_strips(
         &self,
         weights: &[T],
+        primary_axis: Axis,
         aspect_ratio: T,
-        boustrophedon: bool,
+        order: EmissionOrder,
     ) -> Vec<Self>
     where
         Self: Sized + RectangleSize<T> + Clone + SizeForAxis<T> + Area<T>,
         T: Copy + for<'a> std::iter::Sum<&'a T> + Num + NumAssignOps + std::cmp::PartialOrd,
     {
-        let norm_weights = normalize_weights(weights);
-        let total_area = self.area();
-        let height = self.height();
+        self.divide_strips_from_corner(
+            weights,
+            primary_axis,
+            aspect_ratio,
+            order,
+            StartCorner::TopLeft,
+        )
+    }
 
-        let mut dividing_weights: Vec<Vec<T>> = Vec::new();
+    /// same as `divide_strips`, but the first weight is placed at the corner
+    fn divide_strips_from_corner(
+        &self,
+        weights: &[T],
+        primary_axis: Axis,
+        aspect_ratio: T,
+        order: EmissionOrder,
+        corner: StartCorner,
+    ) -> Vec<Self>
+    where
+        Self: Sized + RectangleSize<T> + Clone + SizeForAxis<T> + Area<T>,
+        T: Copy + for<'a> std::iter::Sum<&'a T> + Num + NumAssignOps + std::cmp::PartialOrd,
+    {
+        let direction = corner.direction_for_axis(primary_axis.opposite());
+        self.divide_strips_with_directions(
+            weights,
+            primary_axis,
+            aspect_ratio,
+            order,
+            corner,
+            |n| match order.is_strip_reversed(n) {
+                true => direction.reverse(),
+                false => direction,
+            },
+        )
+    }
+
+    /// same as `divide_strips`, also returning the cuts made as a split tree
+    fn divide_strips_with_tree(
+        &self,
+        weights: &[T],
+        primary_axis: Axis,
+        aspect_ratio: T,
+        order: EmissionOrder,
+    ) -> (Vec<Self>, SplitTree<T>)
+    where
+        Self: Sized + RectangleSize<T> + Clone + SizeForAxis<T> + Area<T>,
+        T: Copy + for<'a> std::iter::Sum<&'a T> + Num + NumAssignOps + std::cmp::PartialOrd,
+    {
+        let groups = self.strip_groups(weights, primary_axis, aspect_ratio);
+        (
+            self.divide_strips(weights, primary_axis, aspect_ratio, order),
+            SplitTree::strips(weights, &groups, primary_axis, order),
+        )
+    }
 
-        let mut remaining_weights = norm_weights;
-        let mut picked_weights: Vec<T> = Vec::new();
+    /// same as `divide_strips_from_corner`, but the cells of the n-th strip flow in `strip_direction(n)`
+    /// instead of following the corner and the emission order (e.g. newspaper-like column flows).
+    /// the strips themselves are still placed by the corner and the emission order
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "debug", skip_all, fields(weights = weights.len(), ?primary_axis))
+    )]
+    fn divide_strips_with_directions<F>(
+        &self,
+        weights: &[T],
+        primary_axis: Axis,
+        aspect_ratio: T,
+        order: EmissionOrder,
+        corner: StartCorner,
+        mut strip_direction: F,
+    ) -> Vec<Self>
+    where
+        Self: Sized + RectangleSize<T> + Clone + SizeForAxis<T> + Area<T>,
+        T: Copy + for<'a> std::iter::Sum<&'a T> + Num + NumAssignOps + std::cmp::PartialOrd,
+        F: FnMut(usize) -> FlowDirection,
+    {
+        let norm_weights = normalize_weights(weights);
+        let dividing_weights: Vec<Vec<T>> = self
+            .strip_groups(weights, primary_axis, aspect_ratio)
+            .iter()
+            .map(|group| group.iter().map(|i| norm_weights[*i]).collect())
+            .collect();
         let mut divided: Vec<Self> = Vec::new();
 
-        remaining_weights.reverse(); // pop() removes item from the end of the vector, so reverse it
-                                     // pick weights until the aspect ratio is satisfied
-        while let Some(picked_weight) = remaining_weights.pop() {
-            picked_weights.push(picked_weight);
-            let weights_in_group = picked_weights.iter().sum::<T>();
+        let group_weights: Vec<T> = dividing_weights.iter().map(|w| w.iter().sum()).collect();
+        // place each strip at its slot, then pick them up again in the order of the weights
+        let slots = order.strip_slots(group_weights.len());
+        let mut slot_weights = group_weights.clone();
+        for (weight, slot) in group_weights.iter().zip(slots.iter()) {
+            slot_weights[*slot] = *weight;
+        }
+        let strips = self.divide_by_weights_and_direction(
+            &slot_weights,
+            corner.direction_for_axis(primary_axis),
+        );
+        for (n, (slot, weights)) in slots.iter().zip(dividing_weights.iter()).enumerate() {
+            divided
+                .extend(strips[*slot].divide_by_weights_and_direction(weights, strip_direction(n)));
+        }
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            strips = strips.len(),
+            cells = divided.len(),
+            "divided into strips"
+        );
+        divided
+    }
+
+    /// the strips `divide_strips` makes: the indices of the weights in each strip, in order.
+    /// weights are picked in order into a strip until its first cell reaches the aspect ratio (width / height)
+    fn strip_groups(&self, weights: &[T], primary_axis: Axis, aspect_ratio: T) -> Vec<Vec<usize>>
+    where
+        Self: Sized + SizeForAxis<T> + Area<T>,
+        T: Copy + for<'a> std::iter::Sum<&'a T> + Num + NumAssignOps + std::cmp::PartialOrd,
+    {
+        self.strip_groups_traced(weights, primary_axis, aspect_ratio, |_| {})
+    }
+
+    /// same as `strip_groups`, reporting every weight picked to `trace`
+    fn strip_groups_traced<F>(
+        &self,
+        weights: &[T],
+        primary_axis: Axis,
+        aspect_ratio: T,
+        mut trace: F,
+    ) -> Vec<Vec<usize>>
+    where
+        Self: Sized + SizeForAxis<T> + Area<T>,
+        T: Copy + for<'a> std::iter::Sum<&'a T> + Num + NumAssignOps + std::cmp::PartialOrd,
+        F: FnMut(&StripDecision<T>),
+    {
+        let norm_weights = normalize_weights(weights);
+        let total_area = self.area();
+        let cross_size = self.size_for_axis(primary_axis.opposite());
+        // the ratio of strip thickness to the first cell's length, which is width / height for columns
+        let target_ratio = match primary_axis {
+            Axis::Vertical => aspect_ratio,
+            Axis::Horizontal => T::one() / aspect_ratio,
+        };
+
+        let mut groups: Vec<Vec<usize>> = Vec::new();
+        let mut picked: Vec<usize> = Vec::new();
+        let mut weights_in_group = T::zero();
+        // pick weights until the aspect ratio is satisfied
+        for (i, weight) in norm_weights.iter().enumerate() {
+            picked.push(i);
+            weights_in_group += *weight;
             let picked_area: T = total_area * weights_in_group;
-            let width = picked_area / height;
-            let first_item_height = picked_weights[0] / weights_in_group * height;
-            let first_item_aspect_ratio = width / first_item_height;
-            if first_item_aspect_ratio >= aspect_ratio {
-                dividing_weights.push(picked_weights.clone());
-                picked_weights = Vec::new();
+            let thickness = picked_area / cross_size;
+            let first_item_length = norm_weights[picked[0]] / weights_in_group * cross_size;
+            let first_item_ratio = thickness / first_item_length;
+            let outcome = match (
+                first_item_ratio >= target_ratio,
+                i + 1 == norm_weights.len(),
+            ) {
+                (true, _) => StripOutcome::ReachedAspectRatio,
+                (false, true) => StripOutcome::OutOfWeights,
+                (false, false) => StripOutcome::Open,
+            };
+            trace(&StripDecision {
+                index: i,
+                strip: groups.len(),
+                first_cell_aspect_ratio: match primary_axis {
+                    Axis::Vertical => first_item_ratio,
+                    Axis::Horizontal => T::one() / first_item_ratio,
+                },
+                target_aspect_ratio: aspect_ratio,
+                outcome,
+            });
+            if first_item_ratio >= target_ratio {
+                groups.push(picked);
+                picked = Vec::new();
+                weights_in_group = T::zero();
             }
         }
-        if !picked_weights.is_empty() {
-            dividing_weights.push(picked_weights.clone());
+        if !picked.is_empty() {
+            groups.push(picked);
         }
+        groups
+    }
 
-        let group_weights: Vec<T> = dividing_weights.iter().map(|w| w.iter().sum()).collect();
-        let vertical_divided = self.divide_by_weights_and_axis(&group_weights, Axis::Vertical);
-        let mut forward = true;
-        for (divided_part, weights) in vertical_divided.iter().zip(dividing_weights.iter_mut()) {
-            if !forward {
-                weights.reverse();
+    /// dividing a rectangle into strips along the primary axis, one for each group of weight indices,
+    /// and each strip by the weights of its group. the n-th rectangle corresponds to the n-th weight,
+    /// so the groups must have every index of the weights exactly once; panics otherwise
+    fn divide_by_strip_groups(
+        &self,
+        weights: &[T],
+        groups: &[Vec<usize>],
+        primary_axis: Axis,
+    ) -> Vec<Self>
+    where
+        Self: Sized + RectangleSize<T> + Clone + SizeForAxis<T>,
+        T: Copy + for<'a> std::iter::Sum<&'a T> + Num + NumAssignOps + NumOps,
+    {
+        let mut indices: Vec<usize> = groups.iter().flatten().copied().collect();
+        indices.sort_unstable();
+        assert!(
+            indices.into_iter().eq(0..weights.len()),
+            "the groups must have every weight index exactly once"
+        );
+        let group_weights: Vec<Vec<T>> = groups
+            .iter()
+            .map(|group| group.iter().map(|i| weights[*i]).collect())
+            .collect();
+        let strip_weights: Vec<T> = group_weights.iter().map(|w| w.iter().sum()).collect();
+        let strips = self.divide_by_weights_and_axis(&strip_weights, primary_axis);
+        let mut cells: Vec<Option<Self>> = vec![None; weights.len()];
+        for ((strip, group), w) in strips.iter().zip(groups.iter()).zip(group_weights.iter()) {
+            let divided = strip.divide_by_weights_and_axis(w, primary_axis.opposite());
+            for (cell, i) in divided.into_iter().zip(group.iter()) {
+                cells[*i] = Some(cell);
             }
-            let mut horizontal_divided =
-                divided_part.divide_by_weights_and_axis(weights, Axis::Horizontal);
-            if !forward {
-                horizontal_divided.reverse();
+        }
+        cells.into_iter().flatten().collect()
+    }
+
+    fn divide_vertical_then_horizontal_with_weights(
+        &self,
+        weights: &[T],
+        aspect_ratio: T,
+        boustrophedon: bool,
+    ) -> Vec<Self>
+    where
+        Self: Sized + RectangleSize<T> + Clone + SizeForAxis<T> + Area<T>,
+        T: Copy + for<'a> std::iter::Sum<&'a T> + Num + NumAssignOps + std::cmp::PartialOrd,
+    {
+        self.divide_strips(
+            weights,
+            Axis::Vertical,
+            aspect_ratio,
+            EmissionOrder::from_boustrophedon(boustrophedon),
+        )
+    }
+
+    /// dividing a rectangle by weights into strips placed in the fill order. with `FillOrder::Strips` this is
+    /// `divide_strips`; with `FillOrder::Spiral` every strip runs along a side of what is left of the rectangle,
+    /// taking weights in order until its first cell reaches the aspect ratio (as in `strip_groups`), and the last
+    /// strip takes the whole of what is left. the n-th rectangle corresponds to the n-th weight
+    fn divide_with_fill_order(&self, weights: &[T], aspect_ratio: T, fill: FillOrder) -> Vec<Self>
+    where
+        Self: Sized + RectangleSize<T> + Clone + SizeForAxis<T> + Area<T>,
+        T: Copy + for<'a> std::iter::Sum<&'a T> + Num + NumAssignOps + std::cmp::PartialOrd,
+    {
+        if let FillOrder::Strips {
+            primary_axis,
+            order,
+        } = fill
+        {
+            return self.divide_strips(weights, primary_axis, aspect_ratio, order);
+        }
+        let mut divided = Vec::with_capacity(weights.len());
+        let mut remaining = self.clone();
+        let mut start = 0;
+        for (axis, direction) in SPIRAL_SIDES.iter().cycle() {
+            if start >= weights.len() {
+                break;
             }
-            divided.extend(horizontal_divided);
-            if boustrophedon {
-                forward = !forward;
+            let rest = &weights[start..];
+            let group_len = remaining
+                .strip_groups(rest, *axis, aspect_ratio)
+                .first()
+                .map_or(rest.len(), |group| group.len());
+            let group = &rest[..group_len];
+            if group_len == rest.len() {
+                divided.extend(remaining.divide_by_weights_and_direction(group, *direction));
+                break;
             }
+            let group_weight: T = group.iter().sum();
+            let rest_weight: T = rest.iter().sum();
+            let size = remaining.size_for_axis(*axis);
+            let thickness = size * group_weight / rest_weight;
+            // the top and left strips are the first part of the cut, the right and bottom ones the second
+            let (strip, rest_of_rect) = match direction {
+                FlowDirection::LeftToRight | FlowDirection::BottomToTop => {
+                    remaining.divide(thickness, *axis)
+                }
+                _ => {
+                    let (rest_of_rect, strip) = remaining.divide(size - thickness, *axis);
+                    (strip, rest_of_rect)
+                }
+            };
+            divided.extend(strip.divide_by_weights_and_direction(group, *direction));
+            remaining = rest_of_rect;
+            start += group_len;
         }
         divided
     }
 
+    /// same as `divide_vertical_then_horizontal_with_weights`, but the first weight is placed at the corner
+    /// and the columns are emitted in the emission order
+    fn divide_vertical_then_horizontal_with_weights_from_corner(
+        &self,
+        weights: &[T],
+        aspect_ratio: T,
+        order: EmissionOrder,
+        corner: StartCorner,
+    ) -> Vec<Self>
+    where
+        Self: Sized + RectangleSize<T> + Clone + SizeForAxis<T> + Area<T>,
+        T: Copy + for<'a> std::iter::Sum<&'a T> + Num + NumAssignOps + std::cmp::PartialOrd,
+    {
+        self.divide_strips_from_corner(weights, Axis::Vertical, aspect_ratio, order, corner)
+    }
+
     fn divide_horizontal_then_vertical_with_weights(
         &self,
         weights: &[T],
@@ -132,27 +469,555 @@ pub trait Dividing<T> {
         boustrophedon: bool,
     ) -> Vec<Self>
     where
-        Self: Sized + RectangleSize<T> + Clone + SizeForAxis<T> + Area<T> + QuarterRotation,
+        Self: Sized + RectangleSize<T> + Clone + SizeForAxis<T> + Area<T>,
+        T: Copy + for<'a> std::iter::Sum<&'a T> + Num + NumAssignOps + std::cmp::PartialOrd,
+    {
+        self.divide_strips(
+            weights,
+            Axis::Horizontal,
+            aspect_ratio,
+            EmissionOrder::from_boustrophedon(boustrophedon),
+        )
+    }
+
+    /// same as `divide_horizontal_then_vertical_with_weights`, but the first weight is placed at the corner
+    /// and the rows are emitted in the emission order
+    fn divide_horizontal_then_vertical_with_weights_from_corner(
+        &self,
+        weights: &[T],
+        aspect_ratio: T,
+        order: EmissionOrder,
+        corner: StartCorner,
+    ) -> Vec<Self>
+    where
+        Self: Sized + RectangleSize<T> + Clone + SizeForAxis<T> + Area<T>,
+        T: Copy + for<'a> std::iter::Sum<&'a T> + Num + NumAssignOps + std::cmp::PartialOrd,
+    {
+        self.divide_strips_from_corner(weights, Axis::Horizontal, aspect_ratio, order, corner)
+    }
+
+    /// dividing a rectangle into a slice-and-dice treemap of a hierarchy: the rectangle is sliced along the start axis
+    /// by the weights of the children of the root, and each slice is diced along the other axis by the weights of the
+    /// children of its node, alternating the axis at every level. the rectangles are those of the leaves, depth first;
+    /// a root without children gets the whole rectangle (see `AxisAlignedRectangle::divide_hierarchy` for every level)
+    fn divide_slice_and_dice(&self, root: &LayoutNode<T>, start_axis: Axis) -> Vec<Self>
+    where
+        Self: Sized + RectangleSize<T> + Clone + SizeForAxis<T>,
+        T: Copy + for<'a> std::iter::Sum<&'a T> + Num + NumAssignOps + NumOps,
+    {
+        if root.children.is_empty() {
+            return vec![self.clone()];
+        }
+        let weights: Vec<T> = root.children.iter().map(|child| child.weight).collect();
+        self.divide_by_weights_and_axis(&weights, start_axis)
+            .iter()
+            .zip(root.children.iter())
+            .flat_map(|(slice, child)| slice.divide_slice_and_dice(child, start_axis.opposite()))
+            .collect()
+    }
+
+    /// dividing a rectangle into a mosaic (Marimekko) layout: rows by the row weights, and the n-th row
+    /// into cells by `cell_weights[n]`. the n-th row of the result has the cells of the n-th row,
+    /// and a row without cell weights has none
+    fn divide_mosaic(&self, row_weights: &[T], cell_weights: &[Vec<T>]) -> Vec<Vec<Self>>
+    where
+        Self: Sized + RectangleSize<T> + Clone + SizeForAxis<T>,
+        T: Copy + for<'a> std::iter::Sum<&'a T> + Num + NumAssignOps + NumOps,
+    {
+        self.divide_by_weights_and_axis(row_weights, Axis::Horizontal)
+            .iter()
+            .enumerate()
+            .map(|(n, row)| match cell_weights.get(n) {
+                Some(weights) => row.divide_by_weights_and_axis(weights, Axis::Vertical),
+                None => vec![],
+            })
+            .collect()
+    }
+
+    /// dividing a rectangle into a squarified treemap (Bruls, Huizing and van Wijk): weights are laid out in rows
+    /// along the shorter side of the remaining rectangle, and a row takes the next weight as long as that does not
+    /// make its worst cell further from a square. the weights are taken in the given order and the n-th rectangle
+    /// corresponds to the n-th weight; sorting them in descending order first (`divide_sorted`) gives the classic layout
+    fn divide_squarified(&self, weights: &[T]) -> Vec<Self>
+    where
+        Self: Sized + RectangleSize<T> + Clone + SizeForAxis<T> + Area<T>,
         T: Copy
+            + for<'a> std::iter::Sum<&'a T>
             + Num
+            + NumAssignOps
             + NumOps
+            + std::cmp::PartialOrd,
+    {
+        if weights.is_empty() {
+            return vec![];
+        }
+        let normalized_weights = normalize_weights(weights);
+        let mut divided = Vec::with_capacity(weights.len());
+        let mut remaining = self.clone();
+        let mut start = 0;
+        while start < normalized_weights.len() {
+            // a row along the shorter side: a column in a wide rectangle, a row in a tall one
+            let (axis, side) = match remaining.width() >= remaining.height() {
+                true => (Axis::Vertical, remaining.height()),
+                false => (Axis::Horizontal, remaining.width()),
+            };
+            let rest: T = normalized_weights[start..].iter().sum();
+            let scale = remaining.area() / rest;
+            let mut end = start + 1;
+            let mut worst = worst_row_ratio(&normalized_weights[start..end], scale, side);
+            while end < normalized_weights.len() {
+                let candidate = worst_row_ratio(&normalized_weights[start..end + 1], scale, side);
+                if candidate > worst {
+                    break;
+                }
+                worst = candidate;
+                end += 1;
+            }
+            let row = &normalized_weights[start..end];
+            if end == normalized_weights.len() {
+                divided.extend(remaining.divide_by_weights_and_axis(row, axis.opposite()));
+                break;
+            }
+            let row_weight: T = row.iter().sum();
+            let thickness = remaining.size_for_axis(axis) * row_weight / rest;
+            let (strip, rest_of_rect) = remaining.divide(thickness, axis);
+            divided.extend(strip.divide_by_weights_and_axis(row, axis.opposite()));
+            remaining = rest_of_rect;
+            start = end;
+        }
+        divided
+    }
+
+    /// dividing a rectangle into a strip treemap (Bederson, Shneiderman and Wattenberg): horizontal strips
+    /// filled in the order of the weights, where a strip takes the next weight as long as that lowers the average
+    /// aspect error of its cells (how far their aspect ratios are from the target, as a factor), and a new strip is
+    /// started otherwise. the n-th rectangle corresponds to the n-th weight, and reading the strips from top to bottom
+    /// and each from left to right gives the weights in order
+    fn divide_strip(&self, weights: &[T], target_aspect: T) -> Vec<Self>
+    where
+        Self: Sized + RectangleSize<T> + Clone + SizeForAxis<T> + Area<T>,
+        T: Copy
+            + for<'a> std::iter::Sum<&'a T>
+            + Num
             + NumAssignOps
-            + std::cmp::PartialOrd
-            + for<'a> std::iter::Sum<&'a T>,
+            + NumOps
+            + std::cmp::PartialOrd,
     {
-        // rotate, divide vertical, rotate back again means divide horizontal
-        let rotated = self.rotate_clockwise();
-        let rotated_aspect_ratio = T::one() / aspect_ratio;
-        let divided = rotated.divide_vertical_then_horizontal_with_weights(
-            weights,
-            rotated_aspect_ratio,
-            boustrophedon,
-        );
+        if weights.is_empty() {
+            return vec![];
+        }
+        let normalized_weights = normalize_weights(weights);
+        let (area, width) = (self.area(), self.width());
+        let mut groups: Vec<Vec<usize>> = Vec::new();
+        let mut strip: Vec<usize> = vec![0];
+        for i in 1..normalized_weights.len() {
+            let current =
+                average_strip_error(&normalized_weights, &strip, area, width, target_aspect);
+            strip.push(i);
+            let candidate =
+                average_strip_error(&normalized_weights, &strip, area, width, target_aspect);
+            if candidate > current {
+                strip.pop();
+                groups.push(strip);
+                strip = vec![i];
+            }
+        }
+        groups.push(strip);
+        self.divide_by_strip_groups(weights, &groups, Axis::Horizontal)
+    }
+
+    /// dividing a rectangle into an ordered pivot treemap (Shneiderman and Wattenberg). the pivot weight
+    /// (see `PivotStrategy`) splits the others into the ones before it and the ones after it. along the longer side,
+    /// the ones before it fill the first part of the rectangle, the pivot and the first of the ones after it share
+    /// the middle part, the pivot on the start side, and the rest of the ones after it fill the last part.
+    /// how many go to the middle part is chosen to make the pivot's cell as square as possible, and each part
+    /// is divided the same way. the n-th rectangle corresponds to the n-th weight, and neighbouring weights stay close
+    fn divide_pivot(&self, weights: &[T], pivot: PivotStrategy) -> Vec<Self>
+    where
+        Self: Sized + RectangleSize<T> + Clone + SizeForAxis<T>,
+        T: Copy
+            + for<'a> std::iter::Sum<&'a T>
+            + Num
+            + NumAssignOps
+            + NumOps
+            + std::cmp::PartialOrd,
+    {
+        let mut cells: Vec<Option<Self>> = vec![None; weights.len()];
+        divide_pivot_into(self, weights, 0, pivot, &mut cells);
+        cells.into_iter().flatten().collect()
+    }
+
+    /// dividing a rectangle into a binary partition treemap: the weights are split in two where their running sum
+    /// is the closest to half, the rectangle is cut across its longer side in proportion, and both halves are divided
+    /// the same way. the n-th rectangle corresponds to the n-th weight
+    fn divide_binary(&self, weights: &[T]) -> Vec<Self>
+    where
+        Self: Sized + RectangleSize<T> + Clone + SizeForAxis<T>,
+        T: Copy
+            + for<'a> std::iter::Sum<&'a T>
+            + Num
+            + NumAssignOps
+            + NumOps
+            + std::cmp::PartialOrd,
+    {
+        let mut divided = Vec::with_capacity(weights.len());
+        divide_binary_into(self, weights, &mut divided);
         divided
+    }
+
+    /// dividing a rectangle by recursive bisection, like a KD-tree: the weights are sorted in descending order,
+    /// and the rectangle is cut across its longer side at the weighted median, so that both halves get about
+    /// the same weight, and both halves are divided the same way (see `divide_binary`). unlike `divide_binary`
+    /// the order of the weights is not kept on the plane, which keeps the cells balanced for many items.
+    /// the n-th rectangle corresponds to the n-th weight
+    fn divide_bisection(&self, weights: &[T]) -> Vec<Self>
+    where
+        Self: Sized + RectangleSize<T> + Clone + SizeForAxis<T>,
+        T: Copy
+            + for<'a> std::iter::Sum<&'a T>
+            + Num
+            + NumAssignOps
+            + NumOps
+            + std::cmp::PartialOrd,
+    {
+        let (divided, permutation) = self.divide_sorted(weights, |rect, sorted| {
+            let mut divided = Vec::with_capacity(sorted.len());
+            divide_binary_into(rect, sorted, &mut divided);
+            divided
+        });
+        restore_order(&divided, &permutation)
+    }
+
+    /// dividing a rectangle into `n` regions like a golden spiral: a golden section (the length over the golden ratio)
+    /// is cut off the longer side, then the axes alternate, winding inward, and the last region is what is left.
+    /// a wide rectangle is cut off the left, then off the top of what is left, off the right and off the bottom;
+    /// a tall one (the transpose) off the top, the left, the bottom and the right. a golden rectangle is cut into squares
+    fn divide_golden(&self, n: usize) -> Vec<Self>
+    where
+        Self: Sized + RectangleSize<T> + Clone + SizeForAxis<T>,
+        T: Copy + Num + NumAssignOps + NumOps + Float,
+    {
+        let two = T::one() + T::one();
+        let five = two + two + T::one();
+        let golden_ratio = (T::one() + five.sqrt()) / two;
+        let mut divided = Vec::with_capacity(n);
+        let mut remaining = self.clone();
+        let axes = match self.height() > self.width() {
+            true => [Axis::Horizontal, Axis::Vertical],
+            false => [Axis::Vertical, Axis::Horizontal],
+        };
+        let sides = axes
             .iter()
-            .map(|r| r.rotate_counter_clockwise())
-            .collect()
+            .cycle()
+            .zip([true, true, false, false].iter().cycle());
+        for (axis, from_start) in sides.take(n.saturating_sub(1)) {
+            let length = remaining.size_for_axis(*axis);
+            let section = length / golden_ratio;
+            let (section, rest) = match from_start {
+                true => remaining.divide(section, *axis),
+                false => {
+                    let (rest, section) = remaining.divide(length - section, *axis);
+                    (section, rest)
+                }
+            };
+            divided.push(section);
+            remaining = rest;
+        }
+        if n > 0 {
+            divided.push(remaining);
+        }
+        divided
     }
+
+    /// dividing a rectangle with every available algorithm (strips, slices, squarified and binary, see `Algorithm::ALL`)
+    /// and returning the layout with the lowest score for the objective
+    fn divide_best<O>(&self, weights: &[T], aspect_ratio: T, objective: O) -> (Algorithm, Vec<Self>)
+    where
+        Self: Sized + RectangleSize<T> + Clone + SizeForAxis<T> + Area<T>,
+        T: Copy
+            + for<'a> std::iter::Sum<&'a T>
+            + Num
+            + NumAssignOps
+            + NumOps
+            + std::cmp::PartialOrd,
+        O: LayoutObjective<T, Self>,
+    {
+        let mut best_algorithm = Algorithm::ALL[0];
+        let mut best = best_algorithm.divide(self, weights, aspect_ratio);
+        let mut best_score = objective.score(&best, weights);
+        for algorithm in Algorithm::ALL.iter().skip(1) {
+            let divided = algorithm.divide(self, weights, aspect_ratio);
+            let score = objective.score(&divided, weights);
+            if score < best_score {
+                best_algorithm = *algorithm;
+                best = divided;
+                best_score = score;
+            }
+        }
+        (best_algorithm, best)
+    }
+
+    /// dividing a rectangle with every available algorithm (vertical-first and horizontal-first strips, slices,
+    /// squarified and binary, see `Algorithm`) and returning the one whose cells are the closest to squares on average.
+    /// the score is `MeanAspectError` with the target 1 (`metrics::mean_aspect_error`), so other layouts can be
+    /// compared with it; `divide_best` takes another objective
+    fn auto_divide(&self, weights: &[T]) -> (Algorithm, Vec<Self>)
+    where
+        Self: Sized + RectangleSize<T> + Clone + SizeForAxis<T> + Area<T>,
+        T: Copy
+            + for<'a> std::iter::Sum<&'a T>
+            + Num
+            + NumAssignOps
+            + NumOps
+            + std::cmp::PartialOrd,
+    {
+        self.divide_best(weights, T::one(), MeanAspectError { target: T::one() })
+    }
+
+    /// dividing a rectangle into strips with each candidate `aspect_ratio` parameter, and returning the parameter
+    /// (and its layout) whose worst cell is the closest to the target aspect ratio. `None` if there are no candidates
+    fn sweep_aspect_ratio(
+        &self,
+        weights: &[T],
+        primary_axis: Axis,
+        candidates: &[T],
+        target: T,
+    ) -> Option<(T, Vec<Self>)>
+    where
+        Self: Sized + RectangleSize<T> + Clone + SizeForAxis<T> + Area<T>,
+        T: Copy
+            + for<'a> std::iter::Sum<&'a T>
+            + Num
+            + NumAssignOps
+            + NumOps
+            + std::cmp::PartialOrd,
+    {
+        let mut best: Option<(T, T, Vec<Self>)> = None;
+        for candidate in candidates {
+            let divided =
+                self.divide_strips(weights, primary_axis, *candidate, EmissionOrder::Forward);
+            let error = worst_aspect_error(&divided, target);
+            let is_better = match &best {
+                Some((_, best_error, _)) => error < *best_error,
+                None => true,
+            };
+            if is_better {
+                best = Some((*candidate, error, divided));
+            }
+        }
+        best.map(|(candidate, _, divided)| (candidate, divided))
+    }
+
+    /// dividing a rectangle after sorting the weights in descending order (see `weight::sort_weights_descending`
+    /// and `divide_sorted_by`)
+    fn divide_sorted<L>(&self, weights: &[T], layout: L) -> (Vec<Self>, Vec<usize>)
+    where
+        Self: Sized,
+        T: Copy + std::cmp::PartialOrd,
+        L: FnOnce(&Self, &[T]) -> Vec<Self>,
+    {
+        let (sorted, permutation) = sort_weights_descending(weights);
+        (layout(self, &sorted), permutation)
+    }
+
+    /// dividing a rectangle after sorting the weights by the comparator.
+    /// `layout` receives the sorted weights; the returned permutation maps the n-th divided rectangle
+    /// back to the index of its weight in the original order (see `weight::restore_order`)
+    fn divide_sorted_by<F, L>(
+        &self,
+        weights: &[T],
+        compare: F,
+        layout: L,
+    ) -> (Vec<Self>, Vec<usize>)
+    where
+        Self: Sized,
+        T: Copy,
+        F: FnMut(&T, &T) -> std::cmp::Ordering,
+        L: FnOnce(&Self, &[T]) -> Vec<Self>,
+    {
+        let (sorted, permutation) = sort_weights_by(weights, compare);
+        (layout(self, &sorted), permutation)
+    }
+}
+
+/// the worst aspect ratio (the longer side over the shorter one) of a row of cells along a side,
+/// where the cells have the areas of the weights times the scale. cells without area are left out
+fn worst_row_ratio<T>(row: &[T], scale: T, side: T) -> T
+where
+    T: Copy + for<'a> std::iter::Sum<&'a T> + Num + NumAssignOps + NumOps + std::cmp::PartialOrd,
+{
+    let row_weight: T = row.iter().sum();
+    let thickness = row_weight * scale / side;
+    let mut worst = T::one();
+    for w in row {
+        let length = *w * scale / thickness;
+        if length <= T::zero() || thickness <= T::zero() {
+            continue;
+        }
+        let ratio = match length > thickness {
+            true => length / thickness,
+            false => thickness / length,
+        };
+        if ratio > worst {
+            worst = ratio;
+        }
+    }
+    worst
+}
+
+/// the average over the cells of a horizontal strip of how far their aspect ratios are from the target,
+/// as the factor (at least 1) between them. the strip spans the width and the cells have the areas of
+/// the normalized weights of the indices times the area
+fn average_strip_error<T>(
+    normalized_weights: &[T],
+    strip: &[usize],
+    area: T,
+    width: T,
+    target_aspect: T,
+) -> T
+where
+    T: Copy + for<'a> std::iter::Sum<&'a T> + Num + NumAssignOps + NumOps + std::cmp::PartialOrd,
+{
+    let strip_weight: T = strip.iter().map(|i| &normalized_weights[*i]).sum();
+    let height = strip_weight * area / width;
+    let mut total = T::zero();
+    let mut count = T::zero();
+    for i in strip {
+        let aspect = normalized_weights[*i] * area / height / height;
+        let ratio = aspect / target_aspect;
+        total += match ratio > T::one() || ratio <= T::zero() {
+            true => ratio,
+            false => T::one() / ratio,
+        };
+        count += T::one();
+    }
+    total / count
+}
+
+/// `Dividing::divide_pivot` of the weights, which start at `start` among all of them, into `cells`
+fn divide_pivot_into<T, D>(
+    rect: &D,
+    weights: &[T],
+    start: usize,
+    pivot: PivotStrategy,
+    cells: &mut [Option<D>],
+) where
+    D: Dividing<T> + Sized + RectangleSize<T> + Clone + SizeForAxis<T>,
+    T: Copy + for<'a> std::iter::Sum<&'a T> + Num + NumAssignOps + NumOps + std::cmp::PartialOrd,
+{
+    let Some(p) = pivot.pivot(weights) else {
+        return;
+    };
+    if weights.len() == 1 {
+        cells[start] = Some(rect.clone());
+        return;
+    }
+    let axis = match rect.width() >= rect.height() {
+        true => Axis::Vertical,
+        false => Axis::Horizontal,
+    };
+    let (length, cross) = (
+        rect.size_for_axis(axis),
+        rect.size_for_axis(axis.opposite()),
+    );
+    let total: T = weights.iter().sum();
+    let mut rest = rect.clone();
+    if p > 0 {
+        let before: T = weights[..p].iter().sum();
+        let (first, remaining) = rest.divide(length * before / total, axis);
+        divide_pivot_into(&first, &weights[..p], start, pivot, cells);
+        rest = remaining;
+    }
+    // how many of the weights after the pivot share the middle part with it
+    let pivot_weight = weights[p];
+    let after = &weights[p + 1..];
+    let mut shared = 0;
+    let mut best: Option<T> = None;
+    let mut middle_weight = pivot_weight;
+    for k in 0..=after.len() {
+        if k > 0 {
+            middle_weight += after[k - 1];
+        }
+        let thickness = length * middle_weight / total;
+        let pivot_length = cross * pivot_weight / middle_weight;
+        let ratio = match thickness > pivot_length {
+            true => thickness / pivot_length,
+            false => pivot_length / thickness,
+        };
+        if best.is_none_or(|b| ratio < b) {
+            best = Some(ratio);
+            shared = k;
+        }
+    }
+    let middle_weight = pivot_weight + after[..shared].iter().sum();
+    let (middle, last) = match shared < after.len() {
+        true => {
+            let (middle, last) = rest.divide(length * middle_weight / total, axis);
+            (middle, Some(last))
+        }
+        false => (rest, None),
+    };
+    match shared > 0 {
+        true => {
+            let (pivot_cell, second) =
+                middle.divide(cross * pivot_weight / middle_weight, axis.opposite());
+            cells[start + p] = Some(pivot_cell);
+            divide_pivot_into(&second, &after[..shared], start + p + 1, pivot, cells);
+        }
+        false => cells[start + p] = Some(middle),
+    }
+    if let Some(last) = last {
+        divide_pivot_into(
+            &last,
+            &after[shared..],
+            start + p + 1 + shared,
+            pivot,
+            cells,
+        );
+    }
+}
+
+/// `Dividing::divide_binary` of the weights, appended to `divided`
+fn divide_binary_into<T, D>(rect: &D, weights: &[T], divided: &mut Vec<D>)
+where
+    D: Dividing<T> + Sized + RectangleSize<T> + Clone + SizeForAxis<T>,
+    T: Copy + for<'a> std::iter::Sum<&'a T> + Num + NumAssignOps + NumOps + std::cmp::PartialOrd,
+{
+    match weights.len() {
+        0 => return,
+        1 => {
+            divided.push(rect.clone());
+            return;
+        }
+        _ => {}
+    }
+    let total: T = weights.iter().sum();
+    let two = T::one() + T::one();
+    let distance = |a: T, b: T| match a > b {
+        true => a - b,
+        false => b - a,
+    };
+    // the split after the first weight at least, and before the last one at most
+    let mut split = 1;
+    let mut sum = weights[0];
+    let mut best = distance(sum * two, total);
+    let mut running = sum;
+    for (i, w) in weights.iter().enumerate().take(weights.len() - 1).skip(1) {
+        running += *w;
+        let d = distance(running * two, total);
+        if d < best {
+            best = d;
+            split = i + 1;
+            sum = running;
+        }
+    }
+    let axis = match rect.width() >= rect.height() {
+        true => Axis::Vertical,
+        false => Axis::Horizontal,
+    };
+    let (first, second) = rect.divide(rect.size_for_axis(axis) * sum / total, axis);
+    divide_binary_into(&first, &weights[..split], divided);
+    divide_binary_into(&second, &weights[split..], divided);
 }
 
 pub(crate) trait VerticalDividingHelper<T> {
@@ -180,15 +1045,19 @@ where
 
 #[cfg(test)]
 mod tests {
-    use num_traits::Float;
-
     use super::*;
     use crate::aspect_ratio::AspectRatio;
     use crate::axis_aligned_rectangle::AxisAlignedRectangle;
     use crate::component::Component;
+    use crate::direction::TextDirection;
+    use crate::objective::MaxAspectError;
     use crate::point::Point;
     use crate::rectangle::Rectangle;
-    use crate::weight::normalize_weights;
+    use crate::rotate::Transpose;
+    use crate::testing::{
+        assert_no_overlaps, assert_respect_aspect_ratio, assert_weights_dividing,
+    };
+    use crate::tolerance::{ApproxEq, Tolerance};
 
     #[test]
     fn test_divide_vertical() {
@@ -228,6 +1097,24 @@ mod tests {
         assert_eq!(rect_b.rect(), Rectangle::new(4, 3));
     }
 
+    #[test]
+    fn test_divide_horizontal_in() {
+        let point = Point::new(2, 3);
+        let rect = Rectangle::new(4, 5);
+        let a_rect = AxisAlignedRectangle::new(&point, &rect);
+        assert_eq!(
+            a_rect.divide_horizontal_in(1, CoordinateSystem::Screen),
+            a_rect.divide_horizontal(1)
+        );
+
+        // y grows upward: the top rectangle has the larger y
+        let (top, bottom) = a_rect.divide_horizontal_in(1, CoordinateSystem::Math);
+        assert_eq!(top.origin(), Point::new(2, 7));
+        assert_eq!(top.rect(), Rectangle::new(4, 1));
+        assert_eq!(bottom.origin(), point);
+        assert_eq!(bottom.rect(), Rectangle::new(4, 4));
+    }
+
     #[test]
     fn test_divide_nth() {
         // test vertical
@@ -291,6 +1178,62 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_divide_by_percentages() {
+        let rect = AxisAlignedRectangle::new(&Point::new(0, 0), &Rectangle::new(200, 10));
+        let divided = rect.divide_by_percentages(&[25, 25, 50], Axis::Vertical);
+        let widths: Vec<i32> = divided.unwrap().iter().map(|d| d.width()).collect();
+        assert_eq!(widths, vec![50, 50, 100]);
+        assert_eq!(
+            rect.divide_by_percentages(&[25, 50], Axis::Vertical),
+            Err(PercentageError::Sum { sum: 75 })
+        );
+        let rect = Rectangle::new(10.0, 300.0);
+        let divided = rect
+            .divide_by_percentages(&[33.33, 33.33, 33.33], Axis::Horizontal)
+            .unwrap();
+        let heights: Vec<f64> = divided.iter().map(|d| d.height()).collect();
+        assert_eq!(heights[..2], [99.99, 99.99]);
+        assert!((heights[2] - 100.02).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_divide_by_weights_and_direction() {
+        let rect = AxisAlignedRectangle::new(&Point::new(1.0, 2.0), &Rectangle::new(6.0, 3.0));
+        let weights = vec![1.0, 2.0, 3.0];
+        let divided = rect.divide_by_weights_and_direction(&weights, FlowDirection::LeftToRight);
+        assert_eq!(
+            divided,
+            rect.divide_by_weights_and_axis(&weights, Axis::Vertical)
+        );
+
+        let divided = rect.divide_by_weights_and_direction(&weights, FlowDirection::RightToLeft);
+        assert_no_overlaps(&rect, &divided);
+        assert_eq!(
+            divided[0],
+            AxisAlignedRectangle::new(&Point::new(6.0, 2.0), &Rectangle::new(1.0, 3.0))
+        );
+        assert_eq!(
+            divided[1],
+            AxisAlignedRectangle::new(&Point::new(4.0, 2.0), &Rectangle::new(2.0, 3.0))
+        );
+        assert_eq!(
+            divided[2],
+            AxisAlignedRectangle::new(&Point::new(1.0, 2.0), &Rectangle::new(3.0, 3.0))
+        );
+
+        let divided = rect.divide_by_weights_and_direction(&weights, FlowDirection::BottomToTop);
+        assert_no_overlaps(&rect, &divided);
+        assert_eq!(
+            divided[0],
+            AxisAlignedRectangle::new(&Point::new(1.0, 4.5), &Rectangle::new(6.0, 0.5))
+        );
+        assert_eq!(
+            divided[2],
+            AxisAlignedRectangle::new(&Point::new(1.0, 2.0), &Rectangle::new(6.0, 1.5))
+        );
+    }
+
     #[test]
     fn test_divide_vertical_then_horizontal_with_weights() {
         let rect = Rectangle::new(100.0, 100.0);
@@ -489,6 +1432,187 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_divide_strips() {
+        let rect = AxisAlignedRectangle::new(&Point::new(3.0, 5.0), &Rectangle::new(300.0, 200.0));
+        let weights = vec![4.0, 3.0, 2.0, 1.0, 1.0];
+        for order in [
+            EmissionOrder::Forward,
+            EmissionOrder::Boustrophedon,
+            EmissionOrder::Reverse,
+            EmissionOrder::CenterOut,
+        ] {
+            for aspect_ratio in [0.5, 1.0, 2.0] {
+                for axis in [Axis::Vertical, Axis::Horizontal] {
+                    let divided = rect.divide_strips(&weights, axis, aspect_ratio, order);
+                    assert_weights_dividing(&rect, &divided, &weights);
+                    assert_no_overlaps(&rect, &divided);
+                }
+
+                // horizontal strips are the transposed vertical strips of the transposed rectangle
+                let transposed = rect.transpose_within(&rect);
+                let expected: Vec<_> = transposed
+                    .divide_strips(&weights, Axis::Vertical, 1.0 / aspect_ratio, order)
+                    .iter()
+                    .map(|r| r.transpose_within(&transposed))
+                    .collect();
+                let divided = rect.divide_strips(&weights, Axis::Horizontal, aspect_ratio, order);
+                for (d, e) in divided.iter().zip(expected.iter()) {
+                    assert_rect_approx_eq(d, e);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_strip_groups_traced() {
+        let rect = AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(4.0, 4.0));
+        let weights = vec![1.0, 1.0, 1.0, 1.0, 1.0];
+        for axis in [Axis::Vertical, Axis::Horizontal] {
+            let mut decisions = vec![];
+            let groups = rect.strip_groups_traced(&weights, axis, 1.0, |d| decisions.push(*d));
+            assert_eq!(groups, rect.strip_groups(&weights, axis, 1.0));
+            assert_eq!(groups, vec![vec![0, 1, 2], vec![3, 4]]);
+            let outcomes: Vec<StripOutcome> = decisions.iter().map(|d| d.outcome).collect();
+            assert_eq!(
+                outcomes,
+                vec![
+                    StripOutcome::Open,
+                    StripOutcome::Open,
+                    StripOutcome::ReachedAspectRatio,
+                    StripOutcome::Open,
+                    StripOutcome::OutOfWeights,
+                ]
+            );
+            let strips: Vec<usize> = decisions.iter().map(|d| d.strip).collect();
+            assert_eq!(strips, vec![0, 0, 0, 1, 1]);
+            // thickness / length, which is width / height for columns
+            let aspect_ratio = |thickness_per_length: f64| match axis {
+                Axis::Vertical => thickness_per_length,
+                Axis::Horizontal => 1.0 / thickness_per_length,
+            };
+            // one weight: the first cell is the whole strip, 0.8 thick and 4 long
+            assert!((decisions[0].first_cell_aspect_ratio - aspect_ratio(0.2)).abs() < 1e-9);
+            // three weights: the strip is 2.4 thick, the first cell 4 / 3 long
+            assert!((decisions[2].first_cell_aspect_ratio - aspect_ratio(1.8)).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_divide_strips_with_tree() {
+        let rect = AxisAlignedRectangle::new(&Point::new(3.0, 5.0), &Rectangle::new(300.0, 200.0));
+        let weights = vec![4.0, 3.0, 2.0, 1.0, 1.0];
+        for order in [
+            EmissionOrder::Forward,
+            EmissionOrder::Boustrophedon,
+            EmissionOrder::Reverse,
+            EmissionOrder::CenterOut,
+        ] {
+            let (divided, tree) =
+                rect.divide_strips_with_tree(&weights, Axis::Vertical, 1.0, order);
+            assert_eq!(
+                divided,
+                rect.divide_strips(&weights, Axis::Vertical, 1.0, order)
+            );
+            // the leaves are ordered like the cells from left to right, then top to bottom
+            let mut by_position: Vec<usize> = (0..divided.len()).collect();
+            by_position.sort_by(|a, b| {
+                (divided[*a].x(), divided[*a].y())
+                    .partial_cmp(&(divided[*b].x(), divided[*b].y()))
+                    .unwrap()
+            });
+            assert_eq!(tree.leaves(), by_position);
+        }
+    }
+
+    #[test]
+    fn test_divide_strips_with_directions() {
+        let rect = AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(12.0, 4.0));
+        let weights = vec![1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0];
+        let pattern = [
+            FlowDirection::TopToBottom,
+            FlowDirection::BottomToTop,
+            FlowDirection::BottomToTop,
+        ];
+        let divided = rect.divide_strips_with_directions(
+            &weights,
+            Axis::Vertical,
+            1.5,
+            EmissionOrder::Forward,
+            StartCorner::TopLeft,
+            |n| pattern[n % pattern.len()],
+        );
+        assert_weights_dividing(&rect, &divided, &weights);
+        assert_no_overlaps(&rect, &divided);
+        let origins: Vec<_> = divided.iter().map(|d| (d.x(), d.y())).collect();
+        assert_eq!(
+            origins,
+            vec![
+                (0.0, 0.0),
+                (0.0, 2.0),
+                (3.0, 2.0),
+                (3.0, 0.0),
+                (6.0, 2.0),
+                (6.0, 0.0),
+                (9.0, 0.0),
+                (9.0, 2.0)
+            ]
+        );
+
+        // the same directions as the emission order give the same result
+        let divided = rect.divide_strips_with_directions(
+            &weights,
+            Axis::Vertical,
+            1.5,
+            EmissionOrder::Boustrophedon,
+            StartCorner::TopLeft,
+            |n| match n % 2 {
+                0 => FlowDirection::TopToBottom,
+                _ => FlowDirection::BottomToTop,
+            },
+        );
+        assert_eq!(
+            divided,
+            rect.divide_strips(&weights, Axis::Vertical, 1.5, EmissionOrder::Boustrophedon)
+        );
+    }
+
+    #[test]
+    fn test_strip_groups() {
+        let rect = AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(9.0, 8.0));
+        let weights = vec![4.0, 4.0, 1.0, 1.0, 1.0, 1.0];
+        let groups = rect.strip_groups(&weights, Axis::Vertical, 1.5);
+        assert_eq!(groups, vec![vec![0, 1], vec![2, 3, 4, 5]]);
+        assert_eq!(
+            rect.divide_by_strip_groups(&weights, &groups, Axis::Vertical),
+            rect.divide_strips(&weights, Axis::Vertical, 1.5, EmissionOrder::Forward)
+        );
+
+        // groups out of order still give the n-th rectangle for the n-th weight
+        let groups = vec![vec![2, 3, 4, 5], vec![1, 0]];
+        let divided = rect.divide_by_strip_groups(&weights, &groups, Axis::Vertical);
+        assert_weights_dividing(&rect, &divided, &weights);
+        assert_no_overlaps(&rect, &divided);
+        assert_eq!(divided[2].round().origin(), Point::new(0.0, 0.0));
+        assert_eq!(divided[1].round().origin(), Point::new(3.0, 0.0));
+        assert_eq!(divided[0].round().origin(), Point::new(3.0, 4.0));
+    }
+
+    #[test]
+    #[should_panic(expected = "every weight index exactly once")]
+    fn test_divide_by_strip_groups_missing_index() {
+        let rect = AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(9.0, 8.0));
+        rect.divide_by_strip_groups(&[1.0, 1.0, 1.0], &[vec![0], vec![2]], Axis::Vertical);
+    }
+
+    #[test]
+    #[should_panic(expected = "every weight index exactly once")]
+    fn test_divide_by_strip_groups_duplicate_index() {
+        // 1 twice and 5 out of range, 0 missing
+        let rect = AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(9.0, 8.0));
+        rect.divide_by_strip_groups(&[1.0, 1.0], &[vec![1], vec![1, 5]], Axis::Vertical);
+    }
+
     #[test]
     fn test_divide_many() {
         // various pattern
@@ -573,92 +1697,578 @@ mod tests {
         );
     }
 
-    fn assert_weights_dividing<T, D>(original: &D, divided: &[D], weights: &[T])
-    where
-        D: Dividing<T> + Area<T>,
-        T: Copy
-            + std::fmt::Debug
-            + Num
-            + NumAssignOps
-            + NumOps
-            + std::iter::Sum<T>
-            + for<'a> std::iter::Sum<&'a T>
-            + std::cmp::PartialOrd<f64>,
-    {
-        // check that the number of divided rectangles is equal to the number of weights
-        assert_eq!(divided.len(), weights.len());
-
-        // check that the sum of divided areas is equal to the original area
-        let original_area = original.area();
-        let divided_area: T = divided.iter().map(|r| r.area()).sum();
-        // assert_eq!(original_area, divided_area);
-        assert!((original_area - divided_area) < 0.1);
-
-        // check that the sum of divided weights is equal to the original weight
-        let original_normalized_weights = normalize_weights(weights);
-        let divided_areas: Vec<T> = divided.iter().map(|r| r.area()).collect();
-        let divided_area_by_weights = normalize_weights(&divided_areas);
-
-        // assert_eq!(original_normalized_weights, divided_area_by_weights);
-        let diffs: Vec<T> = original_normalized_weights
-            .iter()
-            .zip(divided_area_by_weights.iter())
-            .map(|(w1, w2)| (*w1 - *w2) * (*w1 - *w2))
-            .collect();
+    #[test]
+    fn test_start_corner() {
+        let rect = AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(9.0, 8.0));
+        let weights = vec![4.0, 4.0, 1.0, 1.0, 1.0, 1.0];
+        let divided = rect.divide_vertical_then_horizontal_with_weights_from_corner(
+            &weights,
+            1.5,
+            EmissionOrder::Forward,
+            StartCorner::TopLeft,
+        );
+        assert_eq!(
+            divided,
+            rect.divide_vertical_then_horizontal_with_weights(&weights, 1.5, false)
+        );
 
-        for diff in diffs {
-            assert!(diff < 0.1);
+        let divided = rect.divide_vertical_then_horizontal_with_weights_from_corner(
+            &weights,
+            1.5,
+            EmissionOrder::Forward,
+            StartCorner::BottomRight,
+        );
+        assert_weights_dividing(&rect, &divided, &weights);
+        assert_no_overlaps(&rect, &divided);
+        let expected = [
+            (3.0, 4.0, 6.0, 4.0),
+            (3.0, 0.0, 6.0, 4.0),
+            (0.0, 6.0, 3.0, 2.0),
+            (0.0, 4.0, 3.0, 2.0),
+            (0.0, 2.0, 3.0, 2.0),
+            (0.0, 0.0, 3.0, 2.0),
+        ];
+        for (d, (x, y, w, h)) in divided.iter().zip(expected.iter()) {
+            assert_eq!(
+                d.round(),
+                AxisAlignedRectangle::new(&Point::new(*x, *y), &Rectangle::new(*w, *h))
+            );
         }
 
-        // assert_eq!(original_normalized_weights, divided_area_by_weights);
+        let rect = AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(8.0, 9.0));
+        let divided = rect.divide_horizontal_then_vertical_with_weights_from_corner(
+            &weights,
+            1.0 / 1.5,
+            EmissionOrder::Boustrophedon,
+            StartCorner::TopRight,
+        );
+        assert_weights_dividing(&rect, &divided, &weights);
+        assert_no_overlaps(&rect, &divided);
+        let expected = [
+            (4.0, 0.0, 4.0, 6.0),
+            (0.0, 0.0, 4.0, 6.0),
+            (0.0, 6.0, 2.0, 3.0),
+            (2.0, 6.0, 2.0, 3.0),
+            (4.0, 6.0, 2.0, 3.0),
+            (6.0, 6.0, 2.0, 3.0),
+        ];
+        for (d, (x, y, w, h)) in divided.iter().zip(expected.iter()) {
+            assert_eq!(
+                d.round(),
+                AxisAlignedRectangle::new(&Point::new(*x, *y), &Rectangle::new(*w, *h))
+            );
+        }
     }
 
-    fn assert_no_overlaps<T>(
-        original: &AxisAlignedRectangle<T>,
-        divided: &[AxisAlignedRectangle<T>],
-    ) where
-        T: Copy
-            + std::fmt::Debug
-            + Num
-            + NumAssignOps
-            + NumOps
-            + Float
-            + std::iter::Sum<T>
-            + for<'a> std::iter::Sum<&'a T>,
-    {
-        // check all divided rectangles are inside the original rectangle
-        for d in divided {
-            assert!(original.enclodes(&d.round()));
+    #[test]
+    fn test_rtl_is_mirrored_ltr() {
+        let rects = vec![
+            AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(900.0, 800.0)),
+            AxisAlignedRectangle::new(&Point::new(30.0, 10.0), &Rectangle::new(100.0, 300.0)),
+        ];
+        let weights = vec![8.0, 5.0, 4.0, 4.0, 3.0, 2.0, 1.0, 1.0, 1.0];
+        let rtl = TextDirection::Rtl.start_corner();
+        for rect in &rects {
+            for aspect_ratio in [0.5, 1.0, 1.5] {
+                for boustrophedon in [false, true] {
+                    let ltr = rect.divide_vertical_then_horizontal_with_weights(
+                        &weights,
+                        aspect_ratio,
+                        boustrophedon,
+                    );
+                    let divided = rect.divide_vertical_then_horizontal_with_weights_from_corner(
+                        &weights,
+                        aspect_ratio,
+                        EmissionOrder::from_boustrophedon(boustrophedon),
+                        rtl,
+                    );
+                    assert_weights_dividing(rect, &divided, &weights);
+                    for (d, l) in divided.iter().zip(ltr.iter()) {
+                        assert_rect_approx_eq(d, &l.mirror_horizontally(rect));
+                    }
+
+                    let ltr = rect.divide_horizontal_then_vertical_with_weights(
+                        &weights,
+                        aspect_ratio,
+                        boustrophedon,
+                    );
+                    let divided = rect.divide_horizontal_then_vertical_with_weights_from_corner(
+                        &weights,
+                        aspect_ratio,
+                        EmissionOrder::from_boustrophedon(boustrophedon),
+                        rtl,
+                    );
+                    assert_weights_dividing(rect, &divided, &weights);
+                    for (d, l) in divided.iter().zip(ltr.iter()) {
+                        assert_rect_approx_eq(d, &l.mirror_horizontally(rect));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_emission_order() {
+        let rect = AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(12.0, 4.0));
+        // four columns of two cells each
+        let weights = vec![1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0];
+        let columns = |order| {
+            let divided = rect.divide_vertical_then_horizontal_with_weights_from_corner(
+                &weights,
+                1.5,
+                order,
+                StartCorner::TopLeft,
+            );
+            assert_weights_dividing(&rect, &divided, &weights);
+            assert_no_overlaps(&rect, &divided);
+            divided
+                .iter()
+                .map(|d| (d.round().x(), d.round().y()))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            columns(EmissionOrder::Forward),
+            vec![
+                (0.0, 0.0),
+                (0.0, 2.0),
+                (3.0, 0.0),
+                (3.0, 2.0),
+                (6.0, 0.0),
+                (6.0, 2.0),
+                (9.0, 0.0),
+                (9.0, 2.0)
+            ]
+        );
+        assert_eq!(
+            columns(EmissionOrder::Boustrophedon),
+            vec![
+                (0.0, 0.0),
+                (0.0, 2.0),
+                (3.0, 2.0),
+                (3.0, 0.0),
+                (6.0, 0.0),
+                (6.0, 2.0),
+                (9.0, 2.0),
+                (9.0, 0.0)
+            ]
+        );
+        assert_eq!(
+            columns(EmissionOrder::Reverse),
+            vec![
+                (9.0, 0.0),
+                (9.0, 2.0),
+                (6.0, 0.0),
+                (6.0, 2.0),
+                (3.0, 0.0),
+                (3.0, 2.0),
+                (0.0, 0.0),
+                (0.0, 2.0)
+            ]
+        );
+        assert_eq!(
+            columns(EmissionOrder::CenterOut),
+            vec![
+                (3.0, 0.0),
+                (3.0, 2.0),
+                (6.0, 0.0),
+                (6.0, 2.0),
+                (0.0, 0.0),
+                (0.0, 2.0),
+                (9.0, 0.0),
+                (9.0, 2.0)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_divide_best() {
+        let rect = AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(100.0, 10.0));
+        let weights = vec![1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0];
+        // a wide container with equal weights is best divided into a single row of squares
+        let (algorithm, divided) = rect.divide_best(&weights, 1.0, MaxAspectError { target: 1.0 });
+        assert_eq!(algorithm, Algorithm::VerticalSlices);
+        assert_weights_dividing(&rect, &divided, &weights);
+        assert_no_overlaps(&rect, &divided);
+        assert_eq!(worst_aspect_error(&divided, 1.0), 0.0);
+
+        let rect = AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(100.0, 100.0));
+        let weights = vec![4.0, 3.0, 2.0, 1.0, 1.0, 1.0];
+        let (algorithm, divided) = rect.divide_best(&weights, 1.0, MaxAspectError { target: 1.0 });
+        assert_weights_dividing(&rect, &divided, &weights);
+        assert_eq!(divided, algorithm.divide(&rect, &weights, 1.0));
+        for other in Algorithm::ALL {
+            let score = worst_aspect_error(&other.divide(&rect, &weights, 1.0), 1.0);
+            assert!(worst_aspect_error(&divided, 1.0) <= score);
         }
-        // check no overlap between divided rectangles
-        for (d1, d2) in divided.iter().zip(divided.iter().skip(1)) {
-            assert!(!d1.round().overlaps(&d2.round()));
+    }
+
+    #[test]
+    fn test_divide_best_binary() {
+        // large weights at both ends: halving the weights beats every row, column and strip layout
+        let rect = AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(100.0, 100.0));
+        let weights = vec![3.0, 1.0, 1.0, 1.0, 1.0, 3.0];
+        let (algorithm, divided) = rect.divide_best(&weights, 1.0, MaxAspectError { target: 1.0 });
+        assert_eq!(algorithm, Algorithm::Binary);
+        assert_eq!(divided, rect.divide_binary(&weights));
+    }
+
+    #[test]
+    fn test_auto_divide() {
+        let rect = AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(600.0, 400.0));
+        let weights = vec![6.0, 6.0, 4.0, 3.0, 2.0, 2.0, 1.0];
+        let (algorithm, divided) = rect.auto_divide(&weights);
+        assert_eq!(divided, algorithm.divide(&rect, &weights, 1.0));
+        let score = MeanAspectError { target: 1.0 }.score(&divided, &weights);
+        for other in Algorithm::ALL {
+            let cells = other.divide(&rect, &weights, 1.0);
+            assert!(score <= MeanAspectError { target: 1.0 }.score(&cells, &weights));
         }
+        let rect = AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(160.0, 100.0));
+        let (algorithm, _) = rect.auto_divide(&[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+        assert_eq!(algorithm, Algorithm::Binary);
     }
 
-    fn assert_respect_aspect_ratio<T>(
-        divided: &[AxisAlignedRectangle<T>],
-        weights: &[T],
-        aspect_ratio: T,
-    ) where
-        T: Copy
-            + std::fmt::Debug
-            + std::cmp::PartialEq
-            + for<'a> std::iter::Sum<&'a T>
-            + Num
-            + NumAssignOps
-            + NumOps
-            + Float
-            + std::cmp::PartialOrd<f64>,
-    {
-        let normalized_weights = normalize_weights(weights);
-        for (d, w) in divided.iter().zip(normalized_weights.iter()) {
-            let asis_aspect_ratio = d.aspect_ratio();
-            let diff = (asis_aspect_ratio - aspect_ratio).abs();
-            // ideal diff must be 1.0 (same aspect ratio) but the real diff is not 1.0
-            // assert that the diff is not too big, not too small
-            // larger weights are expected to the diff must be smaller, smaller weights are expected to the diff must be smaller
-            assert!(diff * *w < 0.5);
+    #[test]
+    fn test_sweep_aspect_ratio() {
+        let rect = AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(300.0, 200.0));
+        let weights = vec![6.0, 5.0, 4.0, 3.0, 2.0, 2.0, 1.0, 1.0];
+        let candidates = vec![0.25, 0.5, 0.75, 1.0, 1.5, 2.0, 3.0];
+        let (parameter, divided) = rect
+            .sweep_aspect_ratio(&weights, Axis::Vertical, &candidates, 1.0)
+            .unwrap();
+        assert!(candidates.contains(&parameter));
+        assert_weights_dividing(&rect, &divided, &weights);
+        assert_eq!(
+            divided,
+            rect.divide_strips(&weights, Axis::Vertical, parameter, EmissionOrder::Forward)
+        );
+        for candidate in &candidates {
+            let other =
+                rect.divide_strips(&weights, Axis::Vertical, *candidate, EmissionOrder::Forward);
+            assert!(worst_aspect_error(&divided, 1.0) <= worst_aspect_error(&other, 1.0));
+        }
+
+        assert!(rect
+            .sweep_aspect_ratio(&weights, Axis::Vertical, &[], 1.0)
+            .is_none());
+    }
+
+    #[test]
+    fn test_divide_sorted_by() {
+        let rect = AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(9.0, 8.0));
+        let weights = vec![1.0, 4.0, 1.0, 1.0, 4.0, 1.0];
+        let (divided, permutation) = rect.divide_sorted(&weights, |r, w| {
+            r.divide_vertical_then_horizontal_with_weights(w, 1.5, false)
+        });
+        assert_eq!(permutation, vec![1, 4, 0, 2, 3, 5]);
+        assert_eq!(
+            divided,
+            rect.divide_vertical_then_horizontal_with_weights(
+                &[4.0, 4.0, 1.0, 1.0, 1.0, 1.0],
+                1.5,
+                false
+            )
+        );
+        // back in the original order, each rectangle matches its weight
+        let restored = restore_order(&divided, &permutation);
+        assert_weights_dividing(&rect, &restored, &weights);
+        assert_eq!(restored[1], divided[0]);
+        assert_eq!(restored[4], divided[1]);
+
+        // ascending by a custom comparator
+        let (divided, permutation) = rect.divide_sorted_by(
+            &weights,
+            |a: &f64, b: &f64| a.partial_cmp(b).unwrap(),
+            |r, w| r.divide_by_weights_and_axis(w, Axis::Vertical),
+        );
+        assert_eq!(permutation, vec![0, 2, 3, 5, 1, 4]);
+        assert_weights_dividing(&rect, &restore_order(&divided, &permutation), &weights);
+    }
+
+    #[test]
+    fn test_divide_slice_and_dice() {
+        let rect = Rectangle::new(6.0, 4.0);
+        let root = LayoutNode::new(1.0, vec![LayoutNode::leaf(1.0), LayoutNode::leaf(2.0)]);
+        assert_eq!(
+            rect.divide_slice_and_dice(&root, Axis::Vertical),
+            vec![Rectangle::new(2.0, 4.0), Rectangle::new(4.0, 4.0)]
+        );
+        assert_eq!(
+            rect.divide_slice_and_dice(&LayoutNode::leaf(1.0), Axis::Vertical),
+            vec![rect]
+        );
+        let rect = AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(6.0, 4.0));
+        let root = LayoutNode::new(
+            1.0,
+            vec![
+                LayoutNode::new(
+                    4.0,
+                    vec![
+                        LayoutNode::leaf(1.0),
+                        LayoutNode::new(3.0, vec![LayoutNode::leaf(1.0), LayoutNode::leaf(1.0)]),
+                    ],
+                ),
+                LayoutNode::leaf(2.0),
+                LayoutNode::new(2.0, vec![LayoutNode::leaf(1.0), LayoutNode::leaf(1.0)]),
+            ],
+        );
+        let divided = rect.divide_slice_and_dice(&root, Axis::Horizontal);
+        // rows of 4, 2 and 2 of 8, diced into columns, and the second column of the first row sliced into rows again
+        assert_eq!(
+            divided,
+            vec![
+                AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(1.5, 2.0)),
+                AxisAlignedRectangle::new(&Point::new(1.5, 0.0), &Rectangle::new(4.5, 1.0)),
+                AxisAlignedRectangle::new(&Point::new(1.5, 1.0), &Rectangle::new(4.5, 1.0)),
+                AxisAlignedRectangle::new(&Point::new(0.0, 2.0), &Rectangle::new(6.0, 1.0)),
+                AxisAlignedRectangle::new(&Point::new(0.0, 3.0), &Rectangle::new(3.0, 1.0)),
+                AxisAlignedRectangle::new(&Point::new(3.0, 3.0), &Rectangle::new(3.0, 1.0)),
+            ]
+        );
+        assert_weights_dividing(&rect, &divided, &[1.0, 1.5, 1.5, 2.0, 1.0, 1.0]);
+    }
+
+    #[test]
+    fn test_divide_strip() {
+        let rect = AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(4.0, 4.0));
+        let weights = vec![1.0; 16];
+        let divided = rect.divide_strip(&weights, 1.0);
+        // four rows of four unit squares
+        for (i, cell) in divided.iter().enumerate() {
+            assert_rect_approx_eq(
+                cell,
+                &AxisAlignedRectangle::new(
+                    &Point::new((i % 4) as f64, (i / 4) as f64),
+                    &Rectangle::new(1.0, 1.0),
+                ),
+            );
+        }
+
+        let rect = AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(300.0, 200.0));
+        let weights: Vec<f64> = vec![5.0, 1.0, 3.0, 2.0, 8.0, 1.0, 1.0, 4.0, 2.0];
+        let divided = rect.divide_strip(&weights, 1.0);
+        assert_weights_dividing(&rect, &divided, &weights);
+        assert_no_overlaps(&rect, &divided);
+        // the order is kept: each cell is right of the previous one, or starts a lower strip
+        for pair in divided.windows(2) {
+            let next_in_strip = (pair[1].y() - pair[0].y()).abs() < 1e-9
+                && (pair[1].x() - pair[0].x() - pair[0].width()).abs() < 1e-9;
+            let next_strip =
+                (pair[1].y() - pair[0].y() - pair[0].height()).abs() < 1e-9 && pair[1].x() == 0.0;
+            assert!(next_in_strip || next_strip, "{:?}", pair);
+        }
+    }
+
+    #[test]
+    fn test_divide_pivot() {
+        let rect = AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(2.0, 2.0));
+        let divided = rect.divide_pivot(&[1.0; 4], PivotStrategy::Middle);
+        // 0 and 1 on the left, the pivot 2 on top of 3 on the right
+        let expected = [(0.0, 0.0), (0.0, 1.0), (1.0, 0.0), (1.0, 1.0)];
+        for (cell, (x, y)) in divided.iter().zip(expected) {
+            assert_rect_approx_eq(
+                cell,
+                &AxisAlignedRectangle::new(&Point::new(x, y), &Rectangle::new(1.0, 1.0)),
+            );
+        }
+
+        let rect = AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(300.0, 200.0));
+        let weights = vec![5.0, 1.0, 3.0, 2.0, 8.0, 1.0, 1.0, 4.0, 2.0, 6.0];
+        for pivot in [PivotStrategy::Middle, PivotStrategy::Size] {
+            let divided = rect.divide_pivot(&weights, pivot);
+            assert_weights_dividing(&rect, &divided, &weights);
+            assert_no_overlaps(&rect, &divided);
+        }
+        assert!(rect.divide_pivot(&[], PivotStrategy::Size).is_empty());
+    }
+
+    #[test]
+    fn test_divide_binary() {
+        let rect = AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(4.0, 2.0));
+        let divided = rect.divide_binary(&[3.0, 1.0, 2.0, 2.0]);
+        // 3 + 1 against 2 + 2 side by side, then each square half is cut the same way
+        assert_eq!(
+            divided,
+            vec![
+                AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(1.5, 2.0)),
+                AxisAlignedRectangle::new(&Point::new(1.5, 0.0), &Rectangle::new(0.5, 2.0)),
+                AxisAlignedRectangle::new(&Point::new(2.0, 0.0), &Rectangle::new(1.0, 2.0)),
+                AxisAlignedRectangle::new(&Point::new(3.0, 0.0), &Rectangle::new(1.0, 2.0)),
+            ]
+        );
+
+        let rect = AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(300.0, 200.0));
+        let weights = vec![5.0, 1.0, 3.0, 2.0, 8.0, 1.0, 1.0, 4.0, 2.0, 6.0];
+        let divided = rect.divide_binary(&weights);
+        assert_weights_dividing(&rect, &divided, &weights);
+        assert_no_overlaps(&rect, &divided);
+        assert!(rect.divide_binary(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_divide_bisection() {
+        let rect = AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(4.0, 2.0));
+        let divided = rect.divide_bisection(&[1.0, 4.0, 2.0, 1.0]);
+        // 4 against 2 + 1 + 1 side by side, then 2 against 1 + 1 in the right square
+        assert_eq!(
+            divided,
+            vec![
+                AxisAlignedRectangle::new(&Point::new(3.0, 0.0), &Rectangle::new(1.0, 1.0)),
+                AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(2.0, 2.0)),
+                AxisAlignedRectangle::new(&Point::new(2.0, 0.0), &Rectangle::new(1.0, 2.0)),
+                AxisAlignedRectangle::new(&Point::new(3.0, 1.0), &Rectangle::new(1.0, 1.0)),
+            ]
+        );
+
+        let rect = AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(300.0, 200.0));
+        let weights: Vec<f64> = (1..=200).map(|i| ((i * 37) % 11 + 1) as f64).collect();
+        let divided = rect.divide_bisection(&weights);
+        assert_weights_dividing(&rect, &divided, &weights);
+        assert_no_overlaps(&rect, &divided);
+        assert!(rect.divide_bisection(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_divide_with_fill_order() {
+        let rect = AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(3.0, 3.0));
+        let weights = vec![1.0; 9];
+        assert_eq!(
+            rect.divide_with_fill_order(
+                &weights,
+                1.0,
+                FillOrder::from_boustrophedon(Axis::Vertical, true)
+            ),
+            rect.divide_vertical_then_horizontal_with_weights(&weights, 1.0, true)
+        );
+        // a 3x3 grid wound clockwise from the top left, ending in the middle
+        let divided = rect.divide_with_fill_order(&weights, 1.0, FillOrder::Spiral);
+        let expected = [
+            (0, 0),
+            (1, 0),
+            (2, 0),
+            (2, 1),
+            (2, 2),
+            (1, 2),
+            (0, 2),
+            (0, 1),
+            (1, 1),
+        ];
+        for (cell, (x, y)) in divided.iter().zip(expected) {
+            assert_rect_approx_eq(
+                cell,
+                &AxisAlignedRectangle::new(
+                    &Point::new(x as f64, y as f64),
+                    &Rectangle::new(1.0, 1.0),
+                ),
+            );
+        }
+
+        let rect = AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(300.0, 200.0));
+        let weights = vec![5.0, 1.0, 3.0, 2.0, 8.0, 1.0, 1.0, 4.0, 2.0, 6.0];
+        let divided = rect.divide_with_fill_order(&weights, 1.0, FillOrder::Spiral);
+        assert_weights_dividing(&rect, &divided, &weights);
+        assert_no_overlaps(&rect, &divided);
+    }
+
+    #[test]
+    fn test_divide_mosaic() {
+        let rect = AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(4.0, 4.0));
+        let divided = rect.divide_mosaic(&[1.0, 3.0, 1.0], &[vec![1.0, 1.0], vec![1.0, 3.0]]);
+        assert_eq!(
+            divided,
+            vec![
+                vec![
+                    AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(2.0, 0.8)),
+                    AxisAlignedRectangle::new(&Point::new(2.0, 0.0), &Rectangle::new(2.0, 0.8)),
+                ],
+                vec![
+                    AxisAlignedRectangle::new(&Point::new(0.0, 0.8), &Rectangle::new(1.0, 2.4)),
+                    AxisAlignedRectangle::new(&Point::new(1.0, 0.8), &Rectangle::new(3.0, 2.4)),
+                ],
+                vec![],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_divide_golden() {
+        let phi = (1.0 + 5.0_f64.sqrt()) / 2.0;
+        let rect = AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(phi, 1.0));
+        let divided = rect.divide_golden(5);
+        assert_eq!(divided.len(), 5);
+        assert_weights_dividing(
+            &rect,
+            &divided,
+            &divided.iter().map(|d| d.area()).collect::<Vec<f64>>(),
+        );
+        assert_no_overlaps(&rect, &divided);
+        // squares on the left, the top, the right and the bottom
+        let squares = [(0.0, 0.0), (1.0, 0.0), (phi - 1.0 / phi / phi, 1.0 / phi)];
+        for (cell, (x, y)) in divided.iter().zip(squares) {
+            assert!((cell.width() - cell.height()).abs() < 1e-12);
+            assert!((cell.x() - x).abs() < 1e-12 && (cell.y() - y).abs() < 1e-12);
         }
+        assert!(divided[3].y() > divided[2].y());
+        assert!(rect.divide_golden(0).is_empty());
+        assert_eq!(rect.divide_golden(1), vec![rect.clone()]);
+
+        // a tall rectangle: the transpose, with the first square on top, then on the left
+        let tall = AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(1.0, phi));
+        let divided = tall.divide_golden(5);
+        assert_no_overlaps(&tall, &divided);
+        for (cell, (y, x)) in divided.iter().zip(squares) {
+            assert!((cell.width() - cell.height()).abs() < 1e-12);
+            assert!((cell.x() - x).abs() < 1e-12 && (cell.y() - y).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_divide_squarified() {
+        // the example of Bruls et al.
+        let rect = AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(6.0, 4.0));
+        let weights = vec![6.0, 6.0, 4.0, 3.0, 2.0, 2.0, 1.0];
+        let divided = rect.divide_squarified(&weights);
+        assert_weights_dividing(&rect, &divided, &weights);
+        assert_no_overlaps(&rect, &divided);
+        // a column of 6 and 6, a row of 4 and 3 on top of the rest, then 2, 2 and 1 one by one
+        assert_rect_approx_eq(
+            &divided[0],
+            &AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(3.0, 2.0)),
+        );
+        assert_rect_approx_eq(
+            &divided[1],
+            &AxisAlignedRectangle::new(&Point::new(0.0, 2.0), &Rectangle::new(3.0, 2.0)),
+        );
+        assert_rect_approx_eq(
+            &divided[2],
+            &AxisAlignedRectangle::new(
+                &Point::new(3.0, 0.0),
+                &Rectangle::new(12.0 / 7.0, 7.0 / 3.0),
+            ),
+        );
+        assert_rect_approx_eq(
+            &divided[4],
+            &AxisAlignedRectangle::new(
+                &Point::new(3.0, 7.0 / 3.0),
+                &Rectangle::new(1.2, 5.0 / 3.0),
+            ),
+        );
+        // squarer than strips for the same weights
+        assert!(
+            worst_aspect_error(&divided, 1.0)
+                < worst_aspect_error(
+                    &rect.divide_vertical_then_horizontal_with_weights(&weights, 1.0, false),
+                    1.0
+                )
+        );
+        assert!(rect.divide_squarified(&[]).is_empty());
+        assert_eq!(
+            Rectangle::new(2.0, 3.0).divide_squarified(&[5.0]),
+            vec![Rectangle::new(2.0, 3.0)]
+        );
+    }
+
+    fn assert_rect_approx_eq(a: &AxisAlignedRectangle<f64>, b: &AxisAlignedRectangle<f64>) {
+        assert!(a.approx_eq(b, &Tolerance::default()), "{:?} != {:?}", a, b);
     }
 }
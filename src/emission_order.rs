@@ -0,0 +1,104 @@
+/// The order in which the strips (columns or rows) of a weighted layout are emitted
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EmissionOrder {
+    /// strips are placed from the start edge and every strip flows in the same direction
+    #[default]
+    Forward,
+    /// strips are placed from the start edge and every other strip flows backward
+    Boustrophedon,
+    /// strips are placed from the far edge, so the first strip ends up last
+    Reverse,
+    /// the first strip is placed in the middle and the following ones alternate after and before it.
+    /// with weights sorted in descending order, the largest items end up in the middle strips
+    CenterOut,
+}
+
+impl EmissionOrder {
+    pub fn from_boustrophedon(boustrophedon: bool) -> Self {
+        match boustrophedon {
+            true => EmissionOrder::Boustrophedon,
+            false => EmissionOrder::Forward,
+        }
+    }
+
+    /// the slot (counted from the start edge) of each of `n` strips
+    pub fn strip_slots(&self, n: usize) -> Vec<usize> {
+        match self {
+            EmissionOrder::Forward | EmissionOrder::Boustrophedon => (0..n).collect(),
+            EmissionOrder::Reverse => (0..n).rev().collect(),
+            EmissionOrder::CenterOut => {
+                if n == 0 {
+                    return vec![];
+                }
+                // the slots after the center are never fewer than the slots before it
+                let center = (n - 1) / 2;
+                let mut slots = vec![center];
+                let mut after = center + 1;
+                let mut before = center;
+                while slots.len() < n {
+                    if after < n {
+                        slots.push(after);
+                        after += 1;
+                    }
+                    if slots.len() < n && before > 0 {
+                        before -= 1;
+                        slots.push(before);
+                    }
+                }
+                slots
+            }
+        }
+    }
+
+    /// whether the n-th strip (in emission order) flows backward
+    pub fn is_strip_reversed(&self, n: usize) -> bool {
+        match self {
+            EmissionOrder::Boustrophedon => n % 2 == 1,
+            EmissionOrder::Forward | EmissionOrder::Reverse | EmissionOrder::CenterOut => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_boustrophedon() {
+        assert_eq!(
+            EmissionOrder::from_boustrophedon(true),
+            EmissionOrder::Boustrophedon
+        );
+        assert_eq!(
+            EmissionOrder::from_boustrophedon(false),
+            EmissionOrder::Forward
+        );
+    }
+
+    #[test]
+    fn test_strip_slots() {
+        assert_eq!(EmissionOrder::Forward.strip_slots(3), vec![0, 1, 2]);
+        assert_eq!(EmissionOrder::Boustrophedon.strip_slots(3), vec![0, 1, 2]);
+        assert_eq!(EmissionOrder::Reverse.strip_slots(3), vec![2, 1, 0]);
+        assert_eq!(EmissionOrder::CenterOut.strip_slots(0), Vec::<usize>::new());
+        assert_eq!(EmissionOrder::CenterOut.strip_slots(1), vec![0]);
+        assert_eq!(EmissionOrder::CenterOut.strip_slots(2), vec![0, 1]);
+        assert_eq!(EmissionOrder::CenterOut.strip_slots(4), vec![1, 2, 0, 3]);
+        assert_eq!(EmissionOrder::CenterOut.strip_slots(5), vec![2, 3, 1, 4, 0]);
+        for n in 0..10 {
+            let mut slots = EmissionOrder::CenterOut.strip_slots(n);
+            slots.sort();
+            assert_eq!(slots, (0..n).collect::<Vec<_>>());
+        }
+    }
+
+    #[test]
+    fn test_is_strip_reversed() {
+        assert!(!EmissionOrder::Forward.is_strip_reversed(1));
+        assert!(!EmissionOrder::Boustrophedon.is_strip_reversed(0));
+        assert!(EmissionOrder::Boustrophedon.is_strip_reversed(1));
+        assert!(!EmissionOrder::Boustrophedon.is_strip_reversed(2));
+        assert!(!EmissionOrder::Reverse.is_strip_reversed(1));
+        assert!(!EmissionOrder::CenterOut.is_strip_reversed(1));
+    }
+}
@@ -0,0 +1,147 @@
+//! Exhaustive search over guillotine layouts for small inputs.
+//!
+//! Every order-preserving guillotine layout is a binary tree of cuts: each node splits a run of
+//! consecutive weights in two, with a vertical or a horizontal cut placed proportionally to the
+//! weights on each side. For n weights there are Catalan(n - 1) * 2^(n - 1) of them
+//! (about 2.5 million for n = 10), so the search is limited to `MAX_EXHAUSTIVE_WEIGHTS`.
+use num_traits::{Num, NumAssignOps, NumOps};
+
+use crate::axis::{Axis, SizeForAxis};
+use crate::dividing::Dividing;
+use crate::objective::LayoutObjective;
+use crate::rectangle::RectangleSize;
+
+pub const MAX_EXHAUSTIVE_WEIGHTS: usize = 10;
+
+/// the best order-preserving guillotine layout for the objective.
+/// the n-th rectangle corresponds to the n-th weight.
+/// `None` when there are no weights or more than `MAX_EXHAUSTIVE_WEIGHTS`
+pub fn divide_optimal<T, D, O>(rect: &D, weights: &[T], objective: O) -> Option<Vec<D>>
+where
+    D: Dividing<T> + Sized + RectangleSize<T> + Clone + SizeForAxis<T>,
+    T: Copy + Num + NumAssignOps + NumOps + PartialOrd,
+    O: LayoutObjective<T, D>,
+{
+    if weights.is_empty() || weights.len() > MAX_EXHAUSTIVE_WEIGHTS {
+        return None;
+    }
+    let mut search = Search {
+        weights,
+        objective,
+        cells: vec![None; weights.len()],
+        best: None,
+    };
+    search.run(&mut vec![(rect.clone(), 0, weights.len())]);
+    search.best.map(|(cells, _)| cells)
+}
+
+struct Search<'a, T, D, O> {
+    weights: &'a [T],
+    objective: O,
+    cells: Vec<Option<D>>,
+    best: Option<(Vec<D>, T)>,
+}
+
+impl<T, D, O> Search<'_, T, D, O>
+where
+    D: Dividing<T> + Sized + RectangleSize<T> + Clone + SizeForAxis<T>,
+    T: Copy + Num + NumAssignOps + NumOps + PartialOrd,
+    O: LayoutObjective<T, D>,
+{
+    /// `pending` holds the rectangles still to be divided with their runs of weights (start, end)
+    fn run(&mut self, pending: &mut Vec<(D, usize, usize)>) {
+        let Some((rect, start, end)) = pending.pop() else {
+            self.evaluate();
+            return;
+        };
+        if end - start == 1 {
+            self.cells[start] = Some(rect.clone());
+            self.run(pending);
+        } else {
+            let total = self.sum(start, end);
+            for middle in start + 1..end {
+                let ratio = self.sum(start, middle) / total;
+                for axis in [Axis::Vertical, Axis::Horizontal] {
+                    let (first, second) = rect.divide(rect.size_for_axis(axis) * ratio, axis);
+                    pending.push((second, middle, end));
+                    pending.push((first, start, middle));
+                    self.run(pending);
+                    pending.pop();
+                    pending.pop();
+                }
+            }
+        }
+        pending.push((rect, start, end));
+    }
+
+    fn evaluate(&mut self) {
+        let cells: Vec<D> = self.cells.iter().flatten().cloned().collect();
+        let score = self.objective.score(&cells, self.weights);
+        let is_better = match &self.best {
+            Some((_, best_score)) => score < *best_score,
+            None => true,
+        };
+        if is_better {
+            self.best = Some((cells, score));
+        }
+    }
+
+    fn sum(&self, start: usize, end: usize) -> T {
+        self.weights[start..end]
+            .iter()
+            .fold(T::zero(), |sum, w| sum + *w)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::area::Area;
+    use crate::axis_aligned_rectangle::AxisAlignedRectangle;
+    use crate::metrics::worst_aspect_error;
+    use crate::objective::MaxAspectError;
+    use crate::point::Point;
+    use crate::rectangle::Rectangle;
+    use crate::selection::Algorithm;
+
+    fn objective(cells: &[AxisAlignedRectangle<f64>], _: &[f64]) -> f64 {
+        worst_aspect_error(cells, 1.0)
+    }
+
+    #[test]
+    fn test_divide_optimal() {
+        let rect = AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(2.0, 2.0));
+        let weights = vec![1.0, 1.0, 1.0, 1.0];
+        let divided = divide_optimal(&rect, &weights, objective).unwrap();
+        assert_eq!(divided.len(), 4);
+        assert_eq!(objective(&divided, &weights), 0.0);
+        for cell in &divided {
+            assert_eq!(cell.rect(), Rectangle::new(1.0, 1.0));
+        }
+    }
+
+    #[test]
+    fn test_divide_optimal_is_ground_truth() {
+        let rect = AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(300.0, 200.0));
+        let weights = vec![6.0, 4.0, 3.0, 2.0, 1.0, 1.0];
+        let divided = divide_optimal(&rect, &weights, objective).unwrap();
+        let total: f64 = weights.iter().sum();
+        for (cell, weight) in divided.iter().zip(weights.iter()) {
+            assert!((cell.area() / rect.area() - weight / total).abs() < 1e-9);
+        }
+        let optimal = objective(&divided, &weights);
+        for algorithm in Algorithm::ALL {
+            let heuristic = algorithm.divide(&rect, &weights, 1.0);
+            assert!(optimal <= objective(&heuristic, &weights));
+        }
+    }
+
+    #[test]
+    fn test_limits() {
+        let rect = Rectangle::new(1.0, 1.0);
+        let objective = MaxAspectError { target: 1.0 };
+        assert!(divide_optimal(&rect, &[], objective).is_none());
+        assert!(divide_optimal(&rect, &[1.0; MAX_EXHAUSTIVE_WEIGHTS + 1], objective).is_none());
+        assert_eq!(divide_optimal(&rect, &[1.0], objective), Some(vec![rect]));
+    }
+}
@@ -0,0 +1,56 @@
+use crate::axis::Axis;
+use crate::direction::FlowDirection;
+use crate::emission_order::EmissionOrder;
+
+/// How `Dividing::divide_with_fill_order` places the groups of weights
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FillOrder {
+    /// strips along the primary axis, emitted in the order (see `Dividing::divide_strips`)
+    Strips {
+        primary_axis: Axis,
+        order: EmissionOrder,
+    },
+    /// strips around the perimeter, winding clockwise inward: along the top edge from the left,
+    /// down the right edge, back along the bottom edge and up the left edge, then around what is left
+    Spiral,
+}
+
+impl FillOrder {
+    /// the fill order of the `divide_*_with_weights` methods
+    pub fn from_boustrophedon(primary_axis: Axis, boustrophedon: bool) -> Self {
+        FillOrder::Strips {
+            primary_axis,
+            order: EmissionOrder::from_boustrophedon(boustrophedon),
+        }
+    }
+}
+
+/// The sides of a spiral in the order they are filled, with the axis of the strip along each
+/// (rows have horizontal cuts) and the direction its cells flow in
+pub(crate) const SPIRAL_SIDES: [(Axis, FlowDirection); 4] = [
+    (Axis::Horizontal, FlowDirection::LeftToRight),
+    (Axis::Vertical, FlowDirection::TopToBottom),
+    (Axis::Horizontal, FlowDirection::RightToLeft),
+    (Axis::Vertical, FlowDirection::BottomToTop),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_boustrophedon() {
+        assert_eq!(
+            FillOrder::from_boustrophedon(Axis::Vertical, true),
+            FillOrder::Strips {
+                primary_axis: Axis::Vertical,
+                order: EmissionOrder::Boustrophedon
+            }
+        );
+        // every side flows clockwise: away from the corner the previous side ended at
+        for pair in SPIRAL_SIDES.windows(2) {
+            assert_ne!(pair[0].0, pair[1].0);
+            assert_eq!(pair[1].1.axis(), pair[0].0);
+        }
+    }
+}
@@ -0,0 +1,122 @@
+use num_traits::{Num, NumAssignOps, NumOps};
+
+use crate::layout::Layout;
+use crate::split_tree::{GroupKind, StripSummary};
+
+impl<T> Layout<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps + PartialOrd,
+{
+    /// the groups of the cells, where `group_ids[n]` is the group of the n-th cell and `kind` tells what each group id
+    /// stands for. the groups are in ascending order of their ids, their members ascending; cells without a group id are left out
+    pub fn groups<F>(&self, group_ids: &[usize], weights: &[T], kind: F) -> Vec<StripSummary<T>>
+    where
+        F: Fn(usize) -> GroupKind,
+    {
+        let mut ids: Vec<usize> = group_ids.iter().take(self.len()).copied().collect();
+        ids.sort_unstable();
+        ids.dedup();
+        ids.into_iter()
+            .filter_map(|id| {
+                let members: Vec<usize> = (0..self.len())
+                    .filter(|i| group_ids.get(*i) == Some(&id))
+                    .collect();
+                let cells = members.iter().map(|i| self.cells()[*i].clone()).collect();
+                let rect = Layout::new(cells).bounds()?;
+                let mut weight = T::zero();
+                for i in &members {
+                    if let Some(w) = weights.get(*i) {
+                        weight += *w;
+                    }
+                }
+                Some(StripSummary {
+                    id,
+                    kind: kind(id),
+                    rect,
+                    members,
+                    weight,
+                })
+            })
+            .collect()
+    }
+
+    /// the strips recorded with `with_strips` as groups, identified by their strip (in emission order).
+    /// unlike `strip_summaries` this needs no split tree
+    pub fn groups_by_strip(&self, weights: &[T]) -> Vec<StripSummary<T>> {
+        match self.strips() {
+            Some(strips) => self.groups(strips, weights, |_| GroupKind::Strip),
+            None => vec![],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::axis::Axis;
+    use crate::axis_aligned_rectangle::AxisAlignedRectangle;
+    use crate::emission_order::EmissionOrder;
+    use crate::point::Point;
+    use crate::rectangle::Rectangle;
+
+    fn rect(x: f64, y: f64, width: f64, height: f64) -> AxisAlignedRectangle<f64> {
+        AxisAlignedRectangle::new(&Point::new(x, y), &Rectangle::new(width, height))
+    }
+
+    #[test]
+    fn test_groups() {
+        let layout = Layout::new(vec![
+            rect(0.0, 0.0, 2.0, 2.0),
+            rect(2.0, 0.0, 2.0, 2.0),
+            rect(0.0, 2.0, 4.0, 1.0),
+        ]);
+        // group 5 is the "other" bucket
+        let groups = layout.groups(&[5, 1, 5], &[1.0, 2.0, 3.0], |id| match id {
+            5 => GroupKind::Other,
+            _ => GroupKind::Parent,
+        });
+        assert_eq!(
+            groups,
+            vec![
+                StripSummary {
+                    id: 1,
+                    kind: GroupKind::Parent,
+                    rect: rect(2.0, 0.0, 2.0, 2.0),
+                    members: vec![1],
+                    weight: 2.0,
+                },
+                StripSummary {
+                    id: 5,
+                    kind: GroupKind::Other,
+                    rect: rect(0.0, 0.0, 4.0, 3.0),
+                    members: vec![0, 2],
+                    weight: 4.0,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_groups_by_strip() {
+        let container = rect(0.0, 0.0, 60.0, 40.0);
+        let weights = [3.0, 1.0, 1.0, 1.0];
+        let layout = Layout::divide_strips(
+            &container,
+            &weights,
+            Axis::Vertical,
+            1.0,
+            EmissionOrder::Forward,
+        );
+        let groups = layout.groups_by_strip(&weights);
+        let strips = layout.strips().unwrap();
+        assert_eq!(groups.len(), strips.iter().max().unwrap() + 1);
+        assert!(groups.iter().all(|g| g.kind == GroupKind::Strip));
+        let members: Vec<usize> = groups.iter().flat_map(|g| g.members.clone()).collect();
+        assert_eq!(members.len(), weights.len());
+        let total: f64 = groups.iter().map(|g| g.weight).sum();
+        assert_eq!(total, 6.0);
+        assert!(Layout::new(vec![rect(0.0, 0.0, 1.0, 1.0)])
+            .groups_by_strip(&[1.0])
+            .is_empty());
+    }
+}
@@ -0,0 +1,419 @@
+//! Dividing a whole hierarchy at once, e.g. file sizes: each cell is divided by the weights of its children.
+use num_traits::{Num, NumAssignOps, NumOps};
+
+use crate::axis::Axis;
+use crate::axis_aligned_rectangle::AxisAlignedRectangle;
+use crate::dividing::Dividing;
+use crate::emission_order::EmissionOrder;
+use crate::rectangle::RectangleSize;
+
+/// A weighted tree for `AxisAlignedRectangle::divide_hierarchy`. the weight of an inner node is its share among its siblings
+#[derive(Debug, PartialEq, Clone)]
+pub struct LayoutNode<T> {
+    pub weight: T,
+    pub children: Vec<LayoutNode<T>>,
+}
+
+impl<T> LayoutNode<T> {
+    pub fn leaf(weight: T) -> Self {
+        Self {
+            weight,
+            children: Vec::new(),
+        }
+    }
+
+    pub fn new(weight: T, children: Vec<LayoutNode<T>>) -> Self {
+        Self { weight, children }
+    }
+}
+
+/// How the children of a node are laid out in its cell
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HierarchyLayout {
+    /// `Dividing::divide_strips` with columns
+    Strips,
+    /// `Dividing::divide_squarified`
+    Squarified,
+    /// `Dividing::divide_binary`
+    Binary,
+    /// slices along alternating axes: the root's children side by side, theirs stacked, and so on
+    /// (see `Dividing::divide_slice_and_dice` for the leaves alone)
+    SliceAndDice,
+}
+
+/// The options of `AxisAlignedRectangle::divide_hierarchy`
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct HierarchyOptions<T> {
+    pub layout: HierarchyLayout,
+    /// the aspect ratio (width / height) the cells aim at, for `HierarchyLayout::Strips`
+    pub aspect_ratio: T,
+    pub nested: NestedOptions<T>,
+}
+
+/// The space a group (a cell with children) keeps for itself before its children are laid out in the rest
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct NestedOptions<T> {
+    /// the padding on every side of a group, at every level, so nested groups are the sum of the paddings apart
+    pub padding_per_level: T,
+    /// the height of the band at the top of a group (inside the padding), e.g. for its label
+    pub header_height: Option<T>,
+}
+
+impl<T> Default for NestedOptions<T>
+where
+    T: Copy + Num,
+{
+    /// no padding and no header
+    fn default() -> Self {
+        Self {
+            padding_per_level: T::zero(),
+            header_height: None,
+        }
+    }
+}
+
+/// A cell of a divided hierarchy, with the cells of the children of its node in their order
+#[derive(Debug, PartialEq, Clone)]
+pub struct HierarchyCell<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps,
+{
+    pub rect: AxisAlignedRectangle<T>,
+    /// 0 for the root
+    pub depth: usize,
+    /// the header band of a group, with `NestedOptions::header_height`
+    pub header: Option<AxisAlignedRectangle<T>>,
+    pub children: Vec<HierarchyCell<T>>,
+}
+
+impl<T> HierarchyCell<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps,
+{
+    /// this cell and all of its descendants, depth first
+    pub fn flatten(&self) -> Vec<&HierarchyCell<T>> {
+        let mut cells = vec![self];
+        for child in &self.children {
+            cells.extend(child.flatten());
+        }
+        cells
+    }
+
+    /// the cells without children
+    pub fn leaves(&self) -> Vec<&HierarchyCell<T>> {
+        self.flatten()
+            .into_iter()
+            .filter(|cell| cell.children.is_empty())
+            .collect()
+    }
+}
+
+impl<T> AxisAlignedRectangle<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps + PartialOrd + for<'a> std::iter::Sum<&'a T>,
+{
+    /// dividing a rectangle by a hierarchy: the root gets the whole rectangle, and every cell is divided by the weights
+    /// of the children of its node with the layout of the options, down to the leaves. the children of a group are
+    /// laid out in what is left of its cell after the padding and the header of the nested options
+    pub fn divide_hierarchy(
+        &self,
+        root: &LayoutNode<T>,
+        options: &HierarchyOptions<T>,
+    ) -> HierarchyCell<T> {
+        self.divide_node(root, options, 0)
+    }
+
+    fn divide_node(
+        &self,
+        node: &LayoutNode<T>,
+        options: &HierarchyOptions<T>,
+        depth: usize,
+    ) -> HierarchyCell<T> {
+        let (header, cells) = self.divide_group(node, options, depth);
+        HierarchyCell {
+            rect: self.clone(),
+            depth,
+            header,
+            children: node
+                .children
+                .iter()
+                .zip(cells.iter())
+                .map(|(child, cell)| cell.divide_node(child, options, depth + 1))
+                .collect(),
+        }
+    }
+
+    /// the header and the cells of the children of a node laid out in this cell (none for a leaf)
+    fn divide_group(
+        &self,
+        node: &LayoutNode<T>,
+        options: &HierarchyOptions<T>,
+        depth: usize,
+    ) -> (Option<Self>, Vec<Self>) {
+        if node.children.is_empty() {
+            return (None, vec![]);
+        }
+        let inner = self.shrink_clamped(options.nested.padding_per_level);
+        let (header, inner) = match options.nested.header_height {
+            Some(height) => {
+                let height = match height < inner.height() {
+                    true => height,
+                    false => inner.height(),
+                };
+                let (header, rest) = inner.divide_horizontal(height);
+                (Some(header), rest)
+            }
+            None => (None, inner),
+        };
+        let weights: Vec<T> = node.children.iter().map(|c| c.weight).collect();
+        let cells = match options.layout {
+            HierarchyLayout::Strips => inner.divide_strips(
+                &weights,
+                Axis::Vertical,
+                options.aspect_ratio,
+                EmissionOrder::Forward,
+            ),
+            HierarchyLayout::Squarified => inner.divide_squarified(&weights),
+            HierarchyLayout::Binary => inner.divide_binary(&weights),
+            HierarchyLayout::SliceAndDice => {
+                let axis = match depth % 2 {
+                    0 => Axis::Vertical,
+                    _ => Axis::Horizontal,
+                };
+                inner.divide_by_weights_and_axis(&weights, axis)
+            }
+        };
+        (header, cells)
+    }
+}
+
+#[cfg(feature = "wasm-threads")]
+impl<T> AxisAlignedRectangle<T>
+where
+    T: Copy
+        + Num
+        + NumAssignOps
+        + NumOps
+        + PartialOrd
+        + Send
+        + Sync
+        + for<'a> std::iter::Sum<&'a T>,
+{
+    /// same as `divide_hierarchy`, dividing the subtrees on the rayon thread pool. the result is the same
+    pub fn par_divide_hierarchy(
+        &self,
+        root: &LayoutNode<T>,
+        options: &HierarchyOptions<T>,
+    ) -> HierarchyCell<T> {
+        self.par_divide_node(root, options, 0)
+    }
+
+    fn par_divide_node(
+        &self,
+        node: &LayoutNode<T>,
+        options: &HierarchyOptions<T>,
+        depth: usize,
+    ) -> HierarchyCell<T> {
+        let (header, cells) = self.divide_group(node, options, depth);
+        let pairs: Vec<(&LayoutNode<T>, &Self)> = node.children.iter().zip(cells.iter()).collect();
+        HierarchyCell {
+            rect: self.clone(),
+            depth,
+            header,
+            children: par_divide_children(&pairs, options, depth + 1),
+        }
+    }
+}
+
+/// the cells of the children, splitting them in halves with `rayon::join` down to single subtrees
+#[cfg(feature = "wasm-threads")]
+fn par_divide_children<T>(
+    pairs: &[(&LayoutNode<T>, &AxisAlignedRectangle<T>)],
+    options: &HierarchyOptions<T>,
+    depth: usize,
+) -> Vec<HierarchyCell<T>>
+where
+    T: Copy
+        + Num
+        + NumAssignOps
+        + NumOps
+        + PartialOrd
+        + Send
+        + Sync
+        + for<'a> std::iter::Sum<&'a T>,
+{
+    match pairs {
+        [] => vec![],
+        [(child, cell)] => vec![cell.par_divide_node(child, options, depth)],
+        _ => {
+            let (first, second) = pairs.split_at(pairs.len() / 2);
+            let (mut cells, rest) = rayon::join(
+                || par_divide_children(first, options, depth),
+                || par_divide_children(second, options, depth),
+            );
+            cells.extend(rest);
+            cells
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::area::Area;
+    use crate::component::Component;
+    use crate::point::Point;
+    use crate::rectangle::Rectangle;
+    use crate::testing::verify_tiling;
+    use crate::tolerance::Tolerance;
+
+    fn tree() -> LayoutNode<f64> {
+        LayoutNode::new(
+            1.0,
+            vec![
+                LayoutNode::new(3.0, vec![LayoutNode::leaf(1.0), LayoutNode::leaf(2.0)]),
+                LayoutNode::leaf(1.0),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_divide_hierarchy() {
+        let rect = AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(8.0, 3.0));
+        let options = HierarchyOptions {
+            layout: HierarchyLayout::SliceAndDice,
+            aspect_ratio: 1.0,
+            nested: NestedOptions::default(),
+        };
+        let root = rect.divide_hierarchy(&tree(), &options);
+        assert_eq!(root.rect, rect);
+        let depths: Vec<usize> = root.flatten().iter().map(|c| c.depth).collect();
+        assert_eq!(depths, vec![0, 1, 2, 2, 1]);
+        // side by side, then stacked
+        assert_eq!(
+            root.children[0].rect,
+            AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(6.0, 3.0))
+        );
+        assert_eq!(
+            root.children[0].children[1].rect,
+            AxisAlignedRectangle::new(&Point::new(0.0, 1.0), &Rectangle::new(6.0, 2.0))
+        );
+        assert_eq!(root.leaves().len(), 3);
+    }
+
+    #[test]
+    fn test_divide_hierarchy_layouts() {
+        let rect = AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(8.0, 3.0));
+        for layout in [
+            HierarchyLayout::Strips,
+            HierarchyLayout::Squarified,
+            HierarchyLayout::Binary,
+            HierarchyLayout::SliceAndDice,
+        ] {
+            let options = HierarchyOptions {
+                layout,
+                aspect_ratio: 1.0,
+                nested: NestedOptions::default(),
+            };
+            let root = rect.divide_hierarchy(&tree(), &options);
+            let leaves: Vec<AxisAlignedRectangle<f64>> =
+                root.leaves().iter().map(|c| c.rect.clone()).collect();
+            assert_eq!(verify_tiling(&rect, &leaves, &Tolerance::default()), Ok(()));
+            assert!((leaves[1].area() - 12.0).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_padding_larger_than_group() {
+        let rect = AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(3.0, 3.0));
+        let options = HierarchyOptions {
+            layout: HierarchyLayout::SliceAndDice,
+            aspect_ratio: 1.0,
+            nested: NestedOptions {
+                padding_per_level: 1.0,
+                header_height: None,
+            },
+        };
+        let root = rect.divide_hierarchy(&tree(), &options);
+        // the group is 0.75x1, too small for its padding: its children collapse at its center
+        let group = &root.children[0];
+        assert_eq!(
+            group.rect,
+            AxisAlignedRectangle::new(&Point::new(1.0, 1.0), &Rectangle::new(0.75, 1.0))
+        );
+        for child in &group.children {
+            assert_eq!(child.rect.origin(), Point::new(1.375, 1.5));
+        }
+        fn assert_within(cell: &HierarchyCell<f64>) {
+            for child in &cell.children {
+                assert!(child.rect.x() >= cell.rect.x() && child.rect.y() >= cell.rect.y());
+                assert!(child.rect.x() + child.rect.width() <= cell.rect.x() + cell.rect.width());
+                assert!(child.rect.y() + child.rect.height() <= cell.rect.y() + cell.rect.height());
+                assert_within(child);
+            }
+        }
+        assert_within(&root);
+    }
+
+    #[test]
+    fn test_nested_options() {
+        let rect = AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(20.0, 12.0));
+        let options = HierarchyOptions {
+            layout: HierarchyLayout::SliceAndDice,
+            aspect_ratio: 1.0,
+            nested: NestedOptions {
+                padding_per_level: 1.0,
+                header_height: Some(2.0),
+            },
+        };
+        let root = rect.divide_hierarchy(&tree(), &options);
+        // the root keeps 1 of padding and a header of 2, leaving 18x8 from (1, 3)
+        assert_eq!(
+            root.header,
+            Some(AxisAlignedRectangle::new(
+                &Point::new(1.0, 1.0),
+                &Rectangle::new(18.0, 2.0)
+            ))
+        );
+        let group = &root.children[0];
+        assert_eq!(
+            group.rect,
+            AxisAlignedRectangle::new(&Point::new(1.0, 3.0), &Rectangle::new(13.5, 8.0))
+        );
+        // the group does the same inside: 11.5x4 from (2, 6)
+        assert_eq!(
+            group.children[0].rect,
+            AxisAlignedRectangle::new(&Point::new(2.0, 6.0), &Rectangle::new(11.5, 4.0 / 3.0))
+        );
+        // leaves keep their whole cell
+        assert_eq!(root.children[1].header, None);
+        assert_eq!(
+            root.children[1].rect,
+            AxisAlignedRectangle::new(&Point::new(14.5, 3.0), &Rectangle::new(4.5, 8.0))
+        );
+    }
+
+    #[cfg(feature = "wasm-threads")]
+    #[test]
+    fn test_par_divide_hierarchy() {
+        let rect = AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(20.0, 12.0));
+        let options = HierarchyOptions {
+            layout: HierarchyLayout::Strips,
+            aspect_ratio: 1.0,
+            nested: NestedOptions {
+                padding_per_level: 1.0,
+                header_height: Some(2.0),
+            },
+        };
+        let wide = LayoutNode::new(
+            1.0,
+            (1..=9)
+                .map(|w| LayoutNode::new(w as f64, tree().children))
+                .collect(),
+        );
+        assert_eq!(
+            rect.par_divide_hierarchy(&wide, &options),
+            rect.divide_hierarchy(&wide, &options)
+        );
+    }
+}
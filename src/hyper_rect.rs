@@ -0,0 +1,181 @@
+use num_traits::{Num, NumAssignOps, NumOps};
+
+use crate::axis::{Axis3, SizeForAxis3};
+use crate::weight::normalize_weights;
+
+/// An axis aligned box in N dimensions: the origin (the smallest coordinate on each axis)
+/// and the size on each axis. axes are indexed from 0
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct HyperRect<T, const N: usize> {
+    origin: [T; N],
+    size: [T; N],
+}
+
+impl<T, const N: usize> HyperRect<T, N>
+where
+    T: Copy + Num + NumAssignOps + NumOps,
+{
+    pub fn new(origin: [T; N], size: [T; N]) -> Self {
+        Self { origin, size }
+    }
+
+    pub fn origin(&self) -> [T; N] {
+        self.origin
+    }
+
+    pub fn size(&self) -> [T; N] {
+        self.size
+    }
+
+    /// the product of the sizes
+    pub fn volume(&self) -> T {
+        self.size.iter().fold(T::one(), |volume, s| volume * *s)
+    }
+
+    /// dividing into two at `v` from the origin along the axis
+    pub fn divide(&self, v: T, axis: usize) -> (Self, Self) {
+        let mut first = *self;
+        let mut second = *self;
+        first.size[axis] = v;
+        second.origin[axis] = self.origin[axis] + v;
+        second.size[axis] = self.size[axis] - v;
+        (first, second)
+    }
+
+    /// dividing into specified weights of boxes along the axis
+    pub fn divide_by_weights_and_axis(&self, weights: &[T], axis: usize) -> Vec<Self>
+    where
+        T: for<'a> std::iter::Sum<&'a T>,
+    {
+        if weights.is_empty() {
+            return vec![];
+        }
+        let mut remaining = *self;
+        let mut divided = Vec::with_capacity(weights.len());
+        let normalized_weights = normalize_weights(weights);
+        for w in &normalized_weights[..normalized_weights.len() - 1] {
+            let (part, rest) = remaining.divide(*w * self.size[axis], axis);
+            divided.push(part);
+            remaining = rest;
+        }
+        divided.push(remaining);
+        divided
+    }
+}
+
+impl<T, const N: usize> HyperRect<T, N>
+where
+    T: Copy + Num + NumAssignOps + NumOps + PartialOrd + for<'a> std::iter::Sum<&'a T>,
+{
+    /// the axis with the largest size (the first of them on ties). `None` for zero dimensions
+    pub fn longest_axis(&self) -> Option<usize> {
+        (0..N).reduce(|longest, axis| match self.size[axis] > self.size[longest] {
+            true => axis,
+            false => longest,
+        })
+    }
+
+    /// dividing into specified weights of boxes by cutting the longest axis in two recursively,
+    /// so that the boxes stay close to cubes. the weights are split where their running sum is
+    /// the closest to half. the n-th box corresponds to the n-th weight
+    pub fn divide_by_weights(&self, weights: &[T]) -> Vec<Self> {
+        let mut divided = Vec::with_capacity(weights.len());
+        self.bisect(weights, &mut divided);
+        divided
+    }
+
+    fn bisect(&self, weights: &[T], divided: &mut Vec<Self>) {
+        let axis = match (weights.len(), self.longest_axis()) {
+            (0, _) => return,
+            (1, _) | (_, None) => {
+                divided.extend(self.divide_by_weights_and_axis(weights, 0));
+                return;
+            }
+            (_, Some(axis)) => axis,
+        };
+        let total: T = weights.iter().sum();
+        let two = T::one() + T::one();
+        let distance = |a: T, b: T| match a > b {
+            true => a - b,
+            false => b - a,
+        };
+        // the split after the first weight at least, and before the last one at most
+        let mut split = 1;
+        let mut sum = weights[0];
+        let mut best = distance(sum * two, total);
+        let mut running = sum;
+        for (i, w) in weights.iter().enumerate().take(weights.len() - 1).skip(1) {
+            running += *w;
+            let d = distance(running * two, total);
+            if d < best {
+                best = d;
+                split = i + 1;
+                sum = running;
+            }
+        }
+        let (first, second) = self.divide(sum / total * self.size[axis], axis);
+        first.bisect(&weights[..split], divided);
+        second.bisect(&weights[split..], divided);
+    }
+}
+
+impl<T> SizeForAxis3<T> for HyperRect<T, 3>
+where
+    T: Copy + Num + NumAssignOps + NumOps,
+{
+    fn size_for_axis3(&self, axis: Axis3) -> T {
+        self.size[axis.index()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_divide() {
+        let cube = HyperRect::new([0.0, 0.0, 0.0], [4.0, 2.0, 6.0]);
+        assert_eq!(cube.volume(), 48.0);
+        let (a, b) = cube.divide(1.0, 2);
+        assert_eq!(a, HyperRect::new([0.0, 0.0, 0.0], [4.0, 2.0, 1.0]));
+        assert_eq!(b, HyperRect::new([0.0, 0.0, 1.0], [4.0, 2.0, 5.0]));
+        assert_eq!(cube.longest_axis(), Some(2));
+        assert_eq!(cube.size_for_axis3(Axis3::Y), 2.0);
+        assert_eq!(cube.divide(1.0, Axis3::Z.index()), (a, b));
+        assert_eq!(HyperRect::<f64, 0>::new([], []).longest_axis(), None);
+    }
+
+    #[test]
+    fn test_divide_by_weights_and_axis() {
+        let cube = HyperRect::new([1.0, 0.0, 0.0, 0.0], [4.0, 2.0, 6.0, 1.0]);
+        let divided = cube.divide_by_weights_and_axis(&[1.0, 2.0, 1.0], 0);
+        assert_eq!(divided.len(), 3);
+        assert_eq!(divided[0].origin(), [1.0, 0.0, 0.0, 0.0]);
+        assert_eq!(divided[1].origin(), [2.0, 0.0, 0.0, 0.0]);
+        assert_eq!(divided[1].size(), [2.0, 2.0, 6.0, 1.0]);
+        assert_eq!(divided[2].origin(), [4.0, 0.0, 0.0, 0.0]);
+        assert!(cube.divide_by_weights_and_axis(&[], 0).is_empty());
+    }
+
+    #[test]
+    fn test_divide_by_weights() {
+        let cube = HyperRect::new([0.0, 0.0, 0.0], [8.0, 8.0, 8.0]);
+        let weights = [1.0; 8];
+        let divided = cube.divide_by_weights(&weights);
+        assert_eq!(divided.len(), 8);
+        // eight equal cubes
+        for part in &divided {
+            assert_eq!(part.size(), [4.0, 4.0, 4.0]);
+        }
+        assert_eq!(divided[7].origin(), [4.0, 4.0, 4.0]);
+
+        let weights = [5.0, 1.0, 3.0, 2.0, 1.0];
+        let divided = cube.divide_by_weights(&weights);
+        let total: f64 = weights.iter().sum();
+        for (part, w) in divided.iter().zip(weights.iter()) {
+            assert!((part.volume() / cube.volume() - w / total).abs() < 1e-9);
+        }
+        let volume: f64 = divided.iter().map(|p| p.volume()).sum();
+        assert!((volume - cube.volume()).abs() < 1e-9);
+    }
+}
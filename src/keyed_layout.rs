@@ -0,0 +1,157 @@
+use std::hash::Hash;
+
+use indexmap::IndexMap;
+use num_traits::{Num, NumAssignOps, NumOps};
+
+use crate::axis_aligned_rectangle::AxisAlignedRectangle;
+use crate::layout::Layout;
+
+/// A layout of keyed items: the cells are looked up by key in constant time, and iterated in the order of the items
+/// (not in the order the algorithm placed them, nor in the order of a hash)
+#[derive(Debug, PartialEq, Clone)]
+pub struct KeyedLayout<K, T>
+where
+    K: Hash + Eq,
+    T: Copy + Num + NumAssignOps + NumOps,
+{
+    cells: IndexMap<K, AxisAlignedRectangle<T>>,
+}
+
+/// A key given to more than one item
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct DuplicateKeyError<K> {
+    pub key: K,
+    /// the index of the second item with the key
+    pub index: usize,
+}
+
+impl<K> std::fmt::Display for DuplicateKeyError<K>
+where
+    K: std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "duplicate key {:?} at item {}", self.key, self.index)
+    }
+}
+
+impl<K> std::error::Error for DuplicateKeyError<K> where K: std::fmt::Debug {}
+
+impl<K, T> KeyedLayout<K, T>
+where
+    K: Hash + Eq + Clone,
+    T: Copy + Num + NumAssignOps + NumOps,
+{
+    /// the n-th key names the n-th cell of the layout. extra keys or cells are left out
+    pub fn new(keys: &[K], layout: &Layout<T>) -> Result<Self, DuplicateKeyError<K>> {
+        let mut cells = IndexMap::with_capacity(keys.len());
+        for (index, (key, cell)) in keys.iter().zip(layout.iter()).enumerate() {
+            if cells.insert(key.clone(), cell.clone()).is_some() {
+                return Err(DuplicateKeyError {
+                    key: key.clone(),
+                    index,
+                });
+            }
+        }
+        Ok(Self { cells })
+    }
+
+    pub fn get(&self, key: &K) -> Option<&AxisAlignedRectangle<T>> {
+        self.cells.get(key)
+    }
+
+    /// the position of the key among the items
+    pub fn index_of(&self, key: &K) -> Option<usize> {
+        self.cells.get_index_of(key)
+    }
+
+    /// the key and the cell of the n-th item
+    pub fn get_index(&self, index: usize) -> Option<(&K, &AxisAlignedRectangle<T>)> {
+        self.cells.get_index(index)
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// the keys and cells in the order of the items
+    pub fn iter(&self) -> indexmap::map::Iter<'_, K, AxisAlignedRectangle<T>> {
+        self.cells.iter()
+    }
+
+    pub fn keys(&self) -> indexmap::map::Keys<'_, K, AxisAlignedRectangle<T>> {
+        self.cells.keys()
+    }
+
+    /// the cells without their keys, in the order of the items
+    pub fn into_layout(self) -> Layout<T> {
+        Layout::new(self.cells.into_values().collect())
+    }
+}
+
+impl<T> AxisAlignedRectangle<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps,
+{
+    /// dividing a rectangle for keyed items: `layout` divides it by the weights (e.g. one of the `Dividing` methods),
+    /// and each cell is looked up by the key of its item
+    pub fn divide_keyed<K, L>(
+        &self,
+        items: &[(K, T)],
+        layout: L,
+    ) -> Result<KeyedLayout<K, T>, DuplicateKeyError<K>>
+    where
+        K: Hash + Eq + Clone,
+        L: FnOnce(&Self, &[T]) -> Vec<Self>,
+    {
+        let keys: Vec<K> = items.iter().map(|(key, _)| key.clone()).collect();
+        let weights: Vec<T> = items.iter().map(|(_, weight)| *weight).collect();
+        KeyedLayout::new(&keys, &Layout::new(layout(self, &weights)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::axis::Axis;
+    use crate::dividing::Dividing;
+    use crate::point::Point;
+    use crate::rectangle::Rectangle;
+
+    #[test]
+    fn test_divide_keyed() {
+        let rect = AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(60.0, 40.0));
+        let items = [("zeta", 1.0), ("alpha", 3.0), ("mu", 2.0)];
+        let weights = [1.0, 3.0, 2.0];
+        let divide = |r: &AxisAlignedRectangle<f64>, w: &[f64]| {
+            r.divide_vertical_then_horizontal_with_weights(w, 1.0, true)
+        };
+        let keyed = rect.divide_keyed(&items, divide).unwrap();
+        let cells = divide(&rect, &weights);
+        // iterated in the order of the items, not of the keys or their hashes
+        let keys: Vec<&str> = keyed.keys().copied().collect();
+        assert_eq!(keys, vec!["zeta", "alpha", "mu"]);
+        assert_eq!(keyed.get(&"alpha"), Some(&cells[1]));
+        assert_eq!(keyed.index_of(&"mu"), Some(2));
+        assert_eq!(keyed.get_index(0), Some((&"zeta", &cells[0])));
+        assert_eq!(keyed.get(&"nu"), None);
+        assert_eq!(keyed.len(), 3);
+        assert_eq!(keyed.into_layout().cells(), &cells[..]);
+    }
+
+    #[test]
+    fn test_duplicate_key() {
+        let rect = AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(60.0, 40.0));
+        let items = [("a", 1.0), ("b", 1.0), ("a", 1.0)];
+        let error = rect
+            .divide_keyed(&items, |r, w| {
+                r.divide_by_weights_and_axis(w, Axis::Vertical)
+            })
+            .unwrap_err();
+        assert_eq!(error, DuplicateKeyError { key: "a", index: 2 });
+        assert_eq!(error.to_string(), "duplicate key \"a\" at item 2");
+    }
+}
@@ -0,0 +1,605 @@
+use num_traits::{Num, NumAssignOps, NumOps};
+
+use crate::area::Area;
+use crate::axis::Axis;
+use crate::axis_aligned_rectangle::AxisAlignedRectangle;
+use crate::component::Component;
+use crate::direction::StartCorner;
+use crate::dividing::Dividing;
+use crate::emission_order::EmissionOrder;
+use crate::metrics::AspectErrorHistogram;
+use crate::objective::LayoutObjective;
+use crate::point::Point;
+use crate::rectangle::{Rectangle, RectangleSize};
+use crate::rotate::Transpose;
+use crate::split_tree::{CutLine, SplitTree, StripSummary};
+use crate::weight::normalize_weights;
+
+/// A divided layout: the positioned cells produced by dividing a container.
+/// the n-th cell corresponds to the n-th weight given to the dividing method
+#[derive(Debug, PartialEq, Clone)]
+pub struct Layout<T, M = ()>
+where
+    T: Copy + Num + NumAssignOps + NumOps,
+{
+    cells: Vec<AxisAlignedRectangle<T>>,
+    strips: Option<Vec<usize>>,
+    stats: Option<Vec<CellStats<T>>>,
+    tree: Option<SplitTree<T, M>>,
+}
+
+/// Statistics of a cell, computed once so that consumers do not have to repeat the math
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct CellStats<T> {
+    /// the weight of the cell divided by the sum of the weights
+    pub normalized_weight: T,
+    /// width / height of the cell
+    pub aspect_ratio: T,
+    /// the share of the container's area the cell covers, minus its normalized weight
+    pub area_error: T,
+    /// the strip (in emission order) the cell belongs to, for strip layouts
+    pub strip: Option<usize>,
+}
+
+impl<T> Layout<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps,
+{
+    pub fn new(cells: Vec<AxisAlignedRectangle<T>>) -> Self {
+        Self {
+            cells,
+            strips: None,
+            stats: None,
+            tree: None,
+        }
+    }
+}
+
+impl<T, M> Layout<T, M>
+where
+    T: Copy + Num + NumAssignOps + NumOps,
+{
+    /// record the strip (in emission order) of each cell
+    pub fn with_strips(mut self, strips: Vec<usize>) -> Self {
+        self.strips = Some(strips);
+        self
+    }
+
+    /// the strip of each cell, for strip layouts
+    pub fn strips(&self) -> Option<&[usize]> {
+        self.strips.as_deref()
+    }
+
+    /// record the split tree the cells were made by, with its metadata
+    pub fn with_tree<N>(self, tree: SplitTree<T, N>) -> Layout<T, N> {
+        Layout {
+            cells: self.cells,
+            strips: self.strips,
+            stats: self.stats,
+            tree: Some(tree),
+        }
+    }
+
+    /// the split tree the cells were made by, if recorded
+    pub fn tree(&self) -> Option<&SplitTree<T, M>> {
+        self.tree.as_ref()
+    }
+
+    /// the statistics of each cell, if computed by `with_stats`
+    pub fn stats(&self) -> Option<&[CellStats<T>]> {
+        self.stats.as_deref()
+    }
+
+    pub fn cells(&self) -> &[AxisAlignedRectangle<T>] {
+        &self.cells
+    }
+
+    pub fn into_cells(self) -> Vec<AxisAlignedRectangle<T>> {
+        self.cells
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, AxisAlignedRectangle<T>> {
+        self.cells.iter()
+    }
+
+    /// the score of the cells for the objective and the weights the layout was divided by
+    pub fn score<O>(&self, objective: &O, weights: &[T]) -> T
+    where
+        O: LayoutObjective<T, AxisAlignedRectangle<T>>,
+    {
+        objective.score(&self.cells, weights)
+    }
+
+    /// swap x and y of every cell relative to the container's origin.
+    /// a column-major layout of `within` becomes a row-major layout of `within` transposed, without recomputation
+    pub fn transpose(&self, within: &AxisAlignedRectangle<T>) -> Layout<T> {
+        Layout::new(
+            self.cells
+                .iter()
+                .map(|cell| cell.transpose_within(within))
+                .collect(),
+        )
+    }
+}
+
+impl<T, M> Layout<T, M>
+where
+    T: Copy + Num + NumAssignOps + NumOps + PartialOrd,
+{
+    /// the smallest rectangle containing every cell. for a layout produced by dividing, this is the container
+    pub fn bounds(&self) -> Option<AxisAlignedRectangle<T>> {
+        let first = self.cells.first()?;
+        let mut left = first.x();
+        let mut top = first.y();
+        let mut right = first.x() + first.width();
+        let mut bottom = first.y() + first.height();
+        for cell in &self.cells {
+            if cell.x() < left {
+                left = cell.x();
+            }
+            if cell.y() < top {
+                top = cell.y();
+            }
+            if cell.x() + cell.width() > right {
+                right = cell.x() + cell.width();
+            }
+            if cell.y() + cell.height() > bottom {
+                bottom = cell.y() + cell.height();
+            }
+        }
+        Some(AxisAlignedRectangle::new(
+            &Point::new(left, top),
+            &Rectangle::new(right - left, bottom - top),
+        ))
+    }
+
+    /// how far the cells deviate from the target aspect ratio, counted into bins by the ascending `edges`
+    pub fn aspect_error_histogram(&self, target: T, edges: &[T]) -> AspectErrorHistogram<T> {
+        AspectErrorHistogram::new(&self.cells, target, edges)
+    }
+
+    /// scale every cell proportionally from the `container` the layout was divided in to a container of the new size,
+    /// keeping the anchor corner of the container in place (e.g. the top left when a window is resized from the bottom right).
+    /// cells not covering the whole container keep their share of it. a container without area can not be scaled,
+    /// so the layout is returned as is
+    pub fn resize(
+        &self,
+        container: &AxisAlignedRectangle<T>,
+        new_size: &Rectangle<T>,
+        anchor: StartCorner,
+    ) -> Self
+    where
+        M: Clone,
+    {
+        if container.width() == T::zero() || container.height() == T::zero() {
+            return self.clone();
+        }
+        let anchor_x = match anchor.horizontal_direction().is_forward() {
+            true => container.x(),
+            false => container.x() + container.width(),
+        };
+        let anchor_y = match anchor.vertical_direction().is_forward() {
+            true => container.y(),
+            false => container.y() + container.height(),
+        };
+        // multiplying before dividing, so that integer sizes scale by fractions too
+        let scale_x = |x: T| anchor_x + (x - anchor_x) * new_size.width() / container.width();
+        let scale_y = |y: T| anchor_y + (y - anchor_y) * new_size.height() / container.height();
+        let cells = self
+            .cells
+            .iter()
+            .map(|cell| {
+                let (left, top) = (scale_x(cell.x()), scale_y(cell.y()));
+                let right = scale_x(cell.x() + cell.width());
+                let bottom = scale_y(cell.y() + cell.height());
+                AxisAlignedRectangle::new(
+                    &Point::new(left, top),
+                    &Rectangle::new(right - left, bottom - top),
+                )
+            })
+            .collect();
+        // the structure stays the same
+        Self {
+            cells,
+            strips: None,
+            stats: None,
+            tree: self.tree.clone(),
+        }
+    }
+}
+
+impl<T> Layout<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps + PartialOrd + for<'a> std::iter::Sum<&'a T>,
+{
+    /// dividing the container into strips (see `Dividing::divide_strips`), recording the strip of each cell
+    pub fn divide_strips(
+        container: &AxisAlignedRectangle<T>,
+        weights: &[T],
+        primary_axis: Axis,
+        aspect_ratio: T,
+        order: EmissionOrder,
+    ) -> Self {
+        let cells = container.divide_strips(weights, primary_axis, aspect_ratio, order);
+        let groups = container.strip_groups(weights, primary_axis, aspect_ratio);
+        let mut strips = vec![0; cells.len()];
+        for (n, group) in groups.iter().enumerate() {
+            for i in group {
+                strips[*i] = n;
+            }
+        }
+        let tree = SplitTree::strips(weights, &groups, primary_axis, order);
+        Self::new(cells).with_strips(strips).with_tree(tree)
+    }
+}
+
+impl<T, M> Layout<T, M>
+where
+    T: Copy + Num + NumAssignOps + NumOps + PartialOrd + for<'a> std::iter::Sum<&'a T>,
+{
+    /// the cut lines of the split tree the cells were made by, in the bounds of the cells.
+    /// empty when no tree was recorded
+    pub fn cut_lines(&self) -> Vec<CutLine<T>> {
+        match (&self.tree, self.bounds()) {
+            (Some(tree), Some(bounds)) => tree.cut_lines(&bounds),
+            _ => Vec::new(),
+        }
+    }
+
+    /// the strips of the split tree the cells were made by, in the bounds of the cells
+    /// (see `SplitTree::strip_summaries`). empty when no tree was recorded
+    pub fn strip_summaries(&self, weights: &[T]) -> Vec<StripSummary<T>> {
+        match (&self.tree, self.bounds()) {
+            (Some(tree), Some(bounds)) => tree.strip_summaries(&bounds, weights),
+            _ => Vec::new(),
+        }
+    }
+
+    /// compute the statistics of each cell for the weights the layout was divided by and the container it was
+    /// divided in (which the cells need not cover). the ratios need a float `T`: integers round them down.
+    /// a cell without height has the aspect ratio 0, and a container without area gives every cell a share of 0
+    pub fn with_stats(mut self, container: &AxisAlignedRectangle<T>, weights: &[T]) -> Self {
+        let total_area = container.area();
+        let normalized_weights = normalize_weights(weights);
+        let stats = self
+            .cells
+            .iter()
+            .zip(normalized_weights.iter())
+            .enumerate()
+            .map(|(i, (cell, normalized_weight))| {
+                let aspect_ratio = match cell.height() == T::zero() {
+                    true => T::zero(),
+                    false => cell.width() / cell.height(),
+                };
+                let share = match total_area == T::zero() {
+                    true => T::zero(),
+                    false => cell.area() / total_area,
+                };
+                CellStats {
+                    normalized_weight: *normalized_weight,
+                    aspect_ratio,
+                    area_error: share - *normalized_weight,
+                    strip: self.strips.as_ref().and_then(|s| s.get(i).copied()),
+                }
+            })
+            .collect();
+        self.stats = Some(stats);
+        self
+    }
+}
+
+/// one cell per line after a header with the number of cells, e.g. `  0: 0,0 4×3`.
+/// the precision (`{:.1}`) applies to every cell
+impl<T, M> std::fmt::Display for Layout<T, M>
+where
+    T: Copy + Num + NumAssignOps + NumOps + std::fmt::Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Layout ({} cells)", self.cells.len())?;
+        for (i, cell) in self.cells.iter().enumerate() {
+            write!(f, "\n  {}: ", i)?;
+            std::fmt::Display::fmt(cell, f)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T> From<Vec<AxisAlignedRectangle<T>>> for Layout<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps,
+{
+    fn from(cells: Vec<AxisAlignedRectangle<T>>) -> Self {
+        Self::new(cells)
+    }
+}
+
+impl<T, M> IntoIterator for Layout<T, M>
+where
+    T: Copy + Num + NumAssignOps + NumOps,
+{
+    type Item = AxisAlignedRectangle<T>;
+    type IntoIter = std::vec::IntoIter<AxisAlignedRectangle<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.cells.into_iter()
+    }
+}
+
+impl<'a, T, M> IntoIterator for &'a Layout<T, M>
+where
+    T: Copy + Num + NumAssignOps + NumOps,
+{
+    type Item = &'a AxisAlignedRectangle<T>;
+    type IntoIter = std::slice::Iter<'a, AxisAlignedRectangle<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.cells.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new() {
+        let cells = vec![
+            AxisAlignedRectangle::new(&Point::new(0, 0), &Rectangle::new(1, 2)),
+            AxisAlignedRectangle::new(&Point::new(1, 0), &Rectangle::new(1, 2)),
+        ];
+        let layout = Layout::from(cells.clone());
+        assert_eq!(layout.len(), 2);
+        assert!(!layout.is_empty());
+        assert_eq!(layout.cells(), &cells[..]);
+        assert_eq!(layout.iter().count(), 2);
+        assert_eq!(layout.into_cells(), cells);
+        assert!(Layout::<i32>::new(vec![]).is_empty());
+    }
+
+    #[test]
+    fn test_display() {
+        let layout = Layout::new(vec![
+            AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(1.5, 2.0)),
+            AxisAlignedRectangle::new(&Point::new(1.5, 0.0), &Rectangle::new(0.5, 2.0)),
+        ]);
+        assert_eq!(
+            format!("{:.1}", layout),
+            "Layout (2 cells)\n  0: 0.0,0.0 1.5×2.0\n  1: 1.5,0.0 0.5×2.0"
+        );
+        assert_eq!(Layout::<i32>::new(vec![]).to_string(), "Layout (0 cells)");
+    }
+
+    #[test]
+    fn test_transpose() {
+        let within = AxisAlignedRectangle::new(&Point::new(10.0, 20.0), &Rectangle::new(9.0, 8.0));
+        let weights = vec![4.0, 4.0, 1.0, 1.0, 1.0, 1.0];
+        let layout =
+            Layout::from(within.divide_vertical_then_horizontal_with_weights(&weights, 1.5, false));
+        let transposed_within = within.transpose_within(&within);
+        let transposed = layout.transpose(&within);
+        // a row-major layout of the transposed container
+        assert_eq!(
+            transposed.cells(),
+            &transposed_within.divide_horizontal_then_vertical_with_weights(
+                &weights,
+                1.0 / 1.5,
+                false
+            )[..]
+        );
+        for cell in &transposed {
+            assert!(transposed_within.enclodes(&cell.round()));
+        }
+        assert_eq!(transposed.transpose(&transposed_within), layout);
+    }
+
+    #[test]
+    fn test_bounds() {
+        let within = AxisAlignedRectangle::new(&Point::new(10.0, 20.0), &Rectangle::new(9.0, 8.0));
+        let layout = Layout::from(within.divide_vertical_then_horizontal_with_weights(
+            &[4.0, 4.0, 1.0, 1.0, 1.0, 1.0],
+            1.5,
+            false,
+        ));
+        assert_eq!(layout.bounds(), Some(within));
+        assert_eq!(Layout::<f64>::new(vec![]).bounds(), None);
+    }
+
+    #[test]
+    fn test_resize() {
+        let within = AxisAlignedRectangle::new(&Point::new(10.0, 20.0), &Rectangle::new(4.0, 2.0));
+        let layout = Layout::from(within.divide_by_weights_and_axis(&[1.0, 3.0], Axis::Vertical));
+
+        let resized = layout.resize(&within, &Rectangle::new(8.0, 4.0), StartCorner::TopLeft);
+        assert_eq!(
+            resized.bounds(),
+            Some(AxisAlignedRectangle::new(
+                &Point::new(10.0, 20.0),
+                &Rectangle::new(8.0, 4.0)
+            ))
+        );
+        assert_eq!(
+            resized.cells()[0],
+            AxisAlignedRectangle::new(&Point::new(10.0, 20.0), &Rectangle::new(2.0, 4.0))
+        );
+        assert_eq!(
+            resized.cells()[1],
+            AxisAlignedRectangle::new(&Point::new(12.0, 20.0), &Rectangle::new(6.0, 4.0))
+        );
+
+        // resized from the top left edge: the bottom right corner stays in place
+        let resized = layout.resize(&within, &Rectangle::new(8.0, 4.0), StartCorner::BottomRight);
+        assert_eq!(
+            resized.bounds(),
+            Some(AxisAlignedRectangle::new(
+                &Point::new(6.0, 18.0),
+                &Rectangle::new(8.0, 4.0)
+            ))
+        );
+        assert_eq!(
+            resized.cells()[0],
+            AxisAlignedRectangle::new(&Point::new(6.0, 18.0), &Rectangle::new(2.0, 4.0))
+        );
+
+        let resized = layout.resize(&within, &Rectangle::new(2.0, 1.0), StartCorner::TopRight);
+        assert_eq!(
+            resized.bounds(),
+            Some(AxisAlignedRectangle::new(
+                &Point::new(12.0, 20.0),
+                &Rectangle::new(2.0, 1.0)
+            ))
+        );
+    }
+
+    #[test]
+    fn test_resize_sparse() {
+        // the cells cover the left half of the container only, and keep covering the left half
+        let container = AxisAlignedRectangle::new(&Point::new(0, 0), &Rectangle::new(4, 2));
+        let layout = Layout::new(vec![AxisAlignedRectangle::new(
+            &Point::new(0, 0),
+            &Rectangle::new(2, 2),
+        )]);
+        let resized = layout.resize(&container, &Rectangle::new(8, 4), StartCorner::TopLeft);
+        assert_eq!(
+            resized.cells(),
+            &[AxisAlignedRectangle::new(
+                &Point::new(0, 0),
+                &Rectangle::new(4, 4)
+            )][..]
+        );
+        // integer sizes that are not whole multiples: 4x2 to 6x3 and to 2x1
+        let layout = Layout::new(vec![
+            AxisAlignedRectangle::new(&Point::new(0, 0), &Rectangle::new(1, 2)),
+            AxisAlignedRectangle::new(&Point::new(1, 0), &Rectangle::new(3, 2)),
+        ]);
+        let sides = |layout: &Layout<i32>| -> Vec<(i32, i32, i32, i32)> {
+            layout
+                .iter()
+                .map(|c| (c.x(), c.y(), c.width(), c.height()))
+                .collect()
+        };
+        let resized = layout.resize(&container, &Rectangle::new(6, 3), StartCorner::TopLeft);
+        assert_eq!(sides(&resized), vec![(0, 0, 1, 3), (1, 0, 5, 3)]);
+        let resized = layout.resize(&container, &Rectangle::new(2, 1), StartCorner::TopLeft);
+        assert_eq!(sides(&resized), vec![(0, 0, 0, 1), (0, 0, 2, 1)]);
+        // an empty container can not be scaled
+        let empty = AxisAlignedRectangle::new(&Point::new(0, 0), &Rectangle::new(0, 2));
+        assert_eq!(
+            layout.resize(&empty, &Rectangle::new(8, 4), StartCorner::TopLeft),
+            layout
+        );
+    }
+
+    #[test]
+    fn test_divide_strips() {
+        let within = AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(9.0, 8.0));
+        let weights = vec![4.0, 4.0, 1.0, 1.0, 1.0, 1.0];
+        let layout = Layout::divide_strips(
+            &within,
+            &weights,
+            Axis::Vertical,
+            1.5,
+            EmissionOrder::Forward,
+        );
+        assert_eq!(
+            layout.cells(),
+            &within.divide_vertical_then_horizontal_with_weights(&weights, 1.5, false)[..]
+        );
+        assert_eq!(layout.strips(), Some(&[0, 0, 1, 1, 1, 1][..]));
+        assert_eq!(layout.stats(), None);
+        assert_eq!(layout.tree().map(|t| t.leaf_count()), Some(6));
+        // one cut between the columns, one in the first column and three in the second
+        let lines = layout.cut_lines();
+        assert_eq!(lines.len(), 5);
+        assert_eq!(lines[0].axis, Axis::Vertical);
+        assert_eq!(lines[0].position, layout.cells()[2].x());
+        assert!(Layout::from(layout.cells().to_vec()).cut_lines().is_empty());
+
+        let summaries = layout.strip_summaries(&weights);
+        assert_eq!(summaries.len(), 2);
+        assert_eq!(summaries[0].members, vec![0, 1]);
+        assert_eq!(summaries[0].weight, 8.0);
+        assert_eq!(summaries[0].rect, within.divide_vertical(6.0).0);
+        assert_eq!(summaries[1].members, vec![2, 3, 4, 5]);
+        assert_eq!(summaries[1].weight, 4.0);
+        assert_eq!(summaries[1].rect.x(), layout.cells()[2].x());
+        assert!(Layout::from(layout.cells().to_vec())
+            .strip_summaries(&weights)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_with_stats() {
+        let within = AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(9.0, 8.0));
+        let weights: Vec<f64> = vec![4.0, 4.0, 1.0, 1.0, 1.0, 1.0];
+        let layout = Layout::divide_strips(
+            &within,
+            &weights,
+            Axis::Vertical,
+            1.5,
+            EmissionOrder::Forward,
+        )
+        .with_stats(&within, &weights);
+        let stats = layout.stats().unwrap();
+        assert_eq!(stats.len(), 6);
+        assert_eq!(stats[0].normalized_weight, 4.0 / 12.0);
+        assert!((stats[0].aspect_ratio - 1.5).abs() < 1e-9);
+        assert!(stats[0].area_error.abs() < 1e-9);
+        assert_eq!(stats[0].strip, Some(0));
+        assert_eq!(stats[5].strip, Some(1));
+
+        // without strips
+        let cells = vec![
+            AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(1.0, 2.0)),
+            AxisAlignedRectangle::new(&Point::new(1.0, 0.0), &Rectangle::new(3.0, 2.0)),
+        ];
+        let container = AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(4.0, 2.0));
+        let layout = Layout::new(cells.clone()).with_stats(&container, &[1.0, 1.0]);
+        let stats = layout.stats().unwrap();
+        assert_eq!(stats[0].aspect_ratio, 0.5);
+        assert_eq!(stats[0].area_error, -0.25);
+        assert_eq!(stats[1].area_error, 0.25);
+        assert_eq!(stats[1].strip, None);
+
+        // the cells cover the left half of the container only
+        let container = AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(8.0, 2.0));
+        let layout = Layout::new(cells).with_stats(&container, &[1.0, 3.0]);
+        let stats = layout.stats().unwrap();
+        assert_eq!(stats[0].area_error, 0.125 - 0.25);
+        assert_eq!(stats[1].area_error, 0.375 - 0.75);
+
+        // zero divisors for integers
+        let flat = AxisAlignedRectangle::new(&Point::new(0, 0), &Rectangle::new(4, 0));
+        let layout = Layout::new(vec![flat.clone()]).with_stats(&flat, &[1]);
+        let stats = layout.stats().unwrap();
+        assert_eq!((stats[0].aspect_ratio, stats[0].area_error), (0, -1));
+    }
+
+    #[test]
+    fn test_aspect_error_histogram() {
+        let within = AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(9.0, 8.0));
+        let weights = vec![4.0, 4.0, 1.0, 1.0, 1.0, 1.0];
+        let layout =
+            Layout::from(within.divide_vertical_then_horizontal_with_weights(&weights, 1.5, false));
+        let histogram = layout.aspect_error_histogram(1.5, &[0.01, 0.5]);
+        assert_eq!(histogram.counts, vec![6, 0, 0]);
+        let histogram = layout.aspect_error_histogram(1.0, &[0.01, 0.6]);
+        assert_eq!(histogram.counts, vec![0, 6, 0]);
+    }
+
+    #[test]
+    fn test_score() {
+        let within = AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(4.0, 1.0));
+        let layout =
+            Layout::from(within.divide_by_weights_and_axis(&[1.0, 1.0, 2.0], Axis::Vertical));
+        let objective = crate::objective::MaxAspectError { target: 1.0 };
+        assert_eq!(layout.score(&objective, &[1.0, 1.0, 2.0]), 1.0);
+    }
+}
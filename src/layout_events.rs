@@ -0,0 +1,189 @@
+use std::hash::Hash;
+
+use num_traits::{Num, NumAssignOps, NumOps};
+
+use crate::axis_aligned_rectangle::AxisAlignedRectangle;
+use crate::keyed_layout::KeyedLayout;
+use crate::point::Point;
+use crate::rectangle::Rectangle;
+
+/// A change of a keyed cell between two layouts
+#[derive(Debug, PartialEq, Clone)]
+pub enum LayoutEvent<K, T>
+where
+    T: Copy + Num + NumAssignOps + NumOps,
+{
+    Added {
+        key: K,
+        rect: AxisAlignedRectangle<T>,
+    },
+    Removed {
+        key: K,
+        rect: AxisAlignedRectangle<T>,
+    },
+    /// the origin of the cell changed
+    Moved {
+        key: K,
+        from: Point<T>,
+        to: Point<T>,
+    },
+    /// the size of the cell changed. a cell both moved and resized gets both events
+    Resized {
+        key: K,
+        from: Rectangle<T>,
+        to: Rectangle<T>,
+    },
+}
+
+impl<K, T> KeyedLayout<K, T>
+where
+    K: Hash + Eq + Clone,
+    T: Copy + Num + NumAssignOps + NumOps,
+{
+    /// the events that turn the previous layout into this one: the removals in the order of the previous items,
+    /// then the other events in the order of the current items. unchanged cells have no events
+    pub fn events_since(&self, previous: &Self) -> Vec<LayoutEvent<K, T>> {
+        let mut events: Vec<LayoutEvent<K, T>> = previous
+            .iter()
+            .filter(|(key, _)| self.get(key).is_none())
+            .map(|(key, rect)| LayoutEvent::Removed {
+                key: key.clone(),
+                rect: rect.clone(),
+            })
+            .collect();
+        for (key, rect) in self.iter() {
+            let Some(before) = previous.get(key) else {
+                events.push(LayoutEvent::Added {
+                    key: key.clone(),
+                    rect: rect.clone(),
+                });
+                continue;
+            };
+            if before.origin() != rect.origin() {
+                events.push(LayoutEvent::Moved {
+                    key: key.clone(),
+                    from: before.origin(),
+                    to: rect.origin(),
+                });
+            }
+            if before.rect() != rect.rect() {
+                events.push(LayoutEvent::Resized {
+                    key: key.clone(),
+                    from: before.rect(),
+                    to: rect.rect(),
+                });
+            }
+        }
+        events
+    }
+}
+
+/// Keeps the latest keyed layout, and tells what changed with each new one, e.g. to update the DOM nodes
+/// or sprites bound to the cells instead of redrawing all of them
+#[derive(Debug, Clone)]
+pub struct LayoutWatcher<K, T>
+where
+    K: Hash + Eq,
+    T: Copy + Num + NumAssignOps + NumOps,
+{
+    current: Option<KeyedLayout<K, T>>,
+}
+
+impl<K, T> Default for LayoutWatcher<K, T>
+where
+    K: Hash + Eq + Clone,
+    T: Copy + Num + NumAssignOps + NumOps,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, T> LayoutWatcher<K, T>
+where
+    K: Hash + Eq + Clone,
+    T: Copy + Num + NumAssignOps + NumOps,
+{
+    pub fn new() -> Self {
+        Self { current: None }
+    }
+
+    pub fn current(&self) -> Option<&KeyedLayout<K, T>> {
+        self.current.as_ref()
+    }
+
+    /// replace the layout, returning the events since the previous one. every cell of the first layout is added
+    pub fn update(&mut self, layout: KeyedLayout<K, T>) -> Vec<LayoutEvent<K, T>> {
+        let events = match &self.current {
+            Some(previous) => layout.events_since(previous),
+            None => layout
+                .iter()
+                .map(|(key, rect)| LayoutEvent::Added {
+                    key: key.clone(),
+                    rect: rect.clone(),
+                })
+                .collect(),
+        };
+        self.current = Some(layout);
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout::Layout;
+
+    fn rect(x: i32, y: i32, width: i32, height: i32) -> AxisAlignedRectangle<i32> {
+        AxisAlignedRectangle::new(&Point::new(x, y), &Rectangle::new(width, height))
+    }
+
+    fn keyed(keys: &[u64], cells: Vec<AxisAlignedRectangle<i32>>) -> KeyedLayout<u64, i32> {
+        KeyedLayout::new(keys, &Layout::new(cells)).unwrap()
+    }
+
+    #[test]
+    fn test_watcher() {
+        let mut watcher = LayoutWatcher::new();
+        let events = watcher.update(keyed(&[1, 2], vec![rect(0, 0, 5, 10), rect(5, 0, 5, 10)]));
+        assert_eq!(
+            events,
+            vec![
+                LayoutEvent::Added {
+                    key: 1,
+                    rect: rect(0, 0, 5, 10)
+                },
+                LayoutEvent::Added {
+                    key: 2,
+                    rect: rect(5, 0, 5, 10)
+                },
+            ]
+        );
+        let events = watcher.update(keyed(&[3, 2], vec![rect(0, 0, 2, 10), rect(2, 0, 8, 10)]));
+        assert_eq!(
+            events,
+            vec![
+                LayoutEvent::Removed {
+                    key: 1,
+                    rect: rect(0, 0, 5, 10)
+                },
+                LayoutEvent::Added {
+                    key: 3,
+                    rect: rect(0, 0, 2, 10)
+                },
+                LayoutEvent::Moved {
+                    key: 2,
+                    from: Point::new(5, 0),
+                    to: Point::new(2, 0)
+                },
+                LayoutEvent::Resized {
+                    key: 2,
+                    from: Rectangle::new(5, 10),
+                    to: Rectangle::new(8, 10)
+                },
+            ]
+        );
+        let unchanged = watcher.current().unwrap().clone();
+        assert!(watcher.update(unchanged).is_empty());
+    }
+}
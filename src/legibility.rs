@@ -0,0 +1,102 @@
+use num_traits::{Num, NumAssignOps, NumOps};
+
+use crate::axis_aligned_rectangle::AxisAlignedRectangle;
+use crate::layout::Layout;
+use crate::rectangle::{Rectangle, RectangleSize};
+
+/// How much content a cell is large enough to show
+#[derive(Debug, PartialEq, Eq, Clone, Copy, PartialOrd, Ord)]
+pub enum Legibility {
+    /// too small to show anything
+    Hidden,
+    /// room for an icon but not for text
+    IconOnly,
+    /// room for a short label
+    Compact,
+    /// room for the full text
+    Full,
+}
+
+/// The smallest cells for each `Legibility`
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct LegibilityThresholds<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps,
+{
+    /// cells with less area are hidden
+    pub min_area: T,
+    /// the side of an icon: cells narrower or lower than this are hidden
+    pub icon: T,
+    /// the smallest size for a short label
+    pub compact: Rectangle<T>,
+    /// the smallest size for the full text
+    pub full: Rectangle<T>,
+}
+
+impl<T> LegibilityThresholds<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps + PartialOrd,
+{
+    pub fn classify(&self, rect: &AxisAlignedRectangle<T>) -> Legibility {
+        let (width, height) = (rect.width(), rect.height());
+        let fits = |size: &Rectangle<T>| width >= size.width() && height >= size.height();
+        if width * height < self.min_area || width < self.icon || height < self.icon {
+            Legibility::Hidden
+        } else if fits(&self.full) {
+            Legibility::Full
+        } else if fits(&self.compact) {
+            Legibility::Compact
+        } else {
+            Legibility::IconOnly
+        }
+    }
+}
+
+impl<T> Layout<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps + PartialOrd,
+{
+    /// the legibility of each cell, in the order of the cells
+    pub fn legibility(&self, thresholds: &LegibilityThresholds<T>) -> Vec<Legibility> {
+        self.iter().map(|cell| thresholds.classify(cell)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::point::Point;
+
+    #[test]
+    fn test_legibility() {
+        let thresholds = LegibilityThresholds {
+            min_area: 100,
+            icon: 8,
+            compact: Rectangle::new(30, 12),
+            full: Rectangle::new(80, 30),
+        };
+        let rect = |width, height| {
+            AxisAlignedRectangle::new(&Point::new(0, 0), &Rectangle::new(width, height))
+        };
+        let layout = Layout::new(vec![
+            rect(100, 40),
+            rect(100, 20),
+            rect(20, 20),
+            rect(9, 9),
+            rect(200, 6),
+            rect(80, 29),
+        ]);
+        assert_eq!(
+            layout.legibility(&thresholds),
+            vec![
+                Legibility::Full,
+                Legibility::Compact,
+                Legibility::IconOnly,
+                Legibility::Hidden,
+                Legibility::Hidden,
+                Legibility::Compact,
+            ]
+        );
+        assert!(Legibility::Full > Legibility::Compact);
+    }
+}
@@ -1,12 +1,71 @@
-pub(crate) mod area;
-pub(crate) mod aspect_ratio;
+pub mod accuracy;
+#[cfg(feature = "approx")]
+pub mod approx;
+pub mod area;
+pub mod aspect_ratio;
+pub mod atlas;
 pub mod axis;
 pub mod axis_aligned_rectangle;
-pub(crate) mod component;
+#[cfg(feature = "rand")]
+pub mod bsp;
+pub mod cell;
+pub mod color;
+pub mod component;
+pub mod content_weights;
+pub mod coordinate_system;
+#[cfg(any(test, feature = "testing"))]
+pub mod corpus;
+#[cfg(any(test, feature = "testing"))]
+pub mod debug_divider;
+#[cfg(feature = "decimal")]
+pub mod decimal;
+pub mod deterministic;
+pub mod direction;
+pub mod divider;
 pub mod dividing;
+pub mod emission_order;
+pub mod exhaustive;
+pub mod fill_order;
+pub mod group;
+pub mod hierarchy;
+pub mod hyper_rect;
+pub mod keyed_layout;
+pub mod layout;
+pub mod layout_events;
+pub mod legibility;
+pub mod merge;
+pub mod metrics;
+#[cfg(feature = "rand")]
+pub mod mondrian;
+pub mod objective;
+pub mod optimizer;
+pub mod overlap;
+pub mod packing;
+pub mod pagination;
+pub mod pivot;
+pub mod pixels;
 pub mod point;
+pub mod prelude;
+pub mod quantum;
+pub mod radial;
 pub mod rectangle;
+pub mod region;
+pub mod relaxation;
 pub(crate) mod rotate;
+pub mod segment;
+pub mod selection;
+pub mod split_tree;
+pub mod square;
+pub mod subdivision;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+pub mod tile_grid;
+pub mod tolerance;
+pub mod trace;
+pub mod transform;
+#[cfg(feature = "rand")]
+pub mod variation;
 pub(crate) mod vector;
+#[cfg(feature = "wasm")]
 pub mod wasm_binding;
-pub(crate) mod weight;
+pub mod weight;
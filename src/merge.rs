@@ -0,0 +1,176 @@
+use num_traits::{Num, NumAssignOps, NumOps};
+
+use crate::area::Area;
+use crate::axis_aligned_rectangle::AxisAlignedRectangle;
+use crate::component::Component;
+use crate::layout::Layout;
+use crate::rectangle::{Rectangle, RectangleSize};
+
+/// A layout where some cells were merged, and where each original cell went
+#[derive(Debug, PartialEq, Clone)]
+pub struct MergedLayout<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps,
+{
+    /// the merged cells, in the order of their first original cell
+    pub layout: Layout<T>,
+    /// `mapping[n]` is the merged cell the n-th original cell (and its weight) went into
+    pub mapping: Vec<usize>,
+}
+
+/// the union of two rectangles when it is a rectangle, i.e. when they share a whole side, with the length of that side
+fn union_along_side<T>(
+    a: &AxisAlignedRectangle<T>,
+    b: &AxisAlignedRectangle<T>,
+) -> Option<(AxisAlignedRectangle<T>, T)>
+where
+    T: Copy + Num + NumAssignOps + NumOps + PartialOrd,
+{
+    let (first, second) = match a.x() < b.x() || a.y() < b.y() {
+        true => (a, b),
+        false => (b, a),
+    };
+    let side_by_side = first.y() == second.y()
+        && first.height() == second.height()
+        && first.x() + first.width() == second.x();
+    let stacked = first.x() == second.x()
+        && first.width() == second.width()
+        && first.y() + first.height() == second.y();
+    match (side_by_side, stacked) {
+        (true, _) => Some((
+            AxisAlignedRectangle::new(
+                &first.origin(),
+                &Rectangle::new(first.width() + second.width(), first.height()),
+            ),
+            first.height(),
+        )),
+        (_, true) => Some((
+            AxisAlignedRectangle::new(
+                &first.origin(),
+                &Rectangle::new(first.width(), first.height() + second.height()),
+            ),
+            first.width(),
+        )),
+        _ => None,
+    }
+}
+
+impl<T> Layout<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps + PartialOrd,
+{
+    /// merge the cells smaller than `min_area` into a neighbor, so that the small items stay where they were
+    /// rather than being moved to an "other" cell. the smallest cell is merged first, into the neighbor sharing
+    /// the longest side with it (the earlier cell on ties), and merged cells can be merged again.
+    /// cells are merged only when their union is a rectangle, i.e. when they share a whole side (compared exactly),
+    /// so a small cell without such a neighbor stays as is. the split tree is not kept
+    pub fn merge_small_cells(&self, min_area: T) -> MergedLayout<T> {
+        // merged cells by their first original cell, with the original cells in each
+        let mut merged: Vec<Option<(AxisAlignedRectangle<T>, Vec<usize>)>> = self
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| Some((cell.clone(), vec![i])))
+            .collect();
+        loop {
+            let mut smallest: Option<(usize, T)> = None;
+            let mut merge: Option<(usize, usize, AxisAlignedRectangle<T>)> = None;
+            for (i, (cell, _)) in merged
+                .iter()
+                .enumerate()
+                .filter_map(|(i, m)| m.as_ref().map(|m| (i, m)))
+            {
+                let area = cell.area();
+                if area >= min_area || smallest.is_some_and(|(_, s)| s <= area) {
+                    continue;
+                }
+                let mut best: Option<(usize, AxisAlignedRectangle<T>, T)> = None;
+                for (j, (other, _)) in merged
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(j, m)| m.as_ref().map(|m| (j, m)))
+                {
+                    if i == j {
+                        continue;
+                    }
+                    if let Some((union, side)) = union_along_side(cell, other) {
+                        if best.as_ref().is_none_or(|(_, _, s)| side > *s) {
+                            best = Some((j, union, side));
+                        }
+                    }
+                }
+                if let Some((j, union, _)) = best {
+                    smallest = Some((i, area));
+                    merge = Some((i, j, union));
+                }
+            }
+            let Some((i, j, union)) = merge else {
+                break;
+            };
+            let (keep, drop) = (i.min(j), i.max(j));
+            let dropped = merged[drop].take().map(|(_, members)| members);
+            if let Some((cell, members)) = merged[keep].as_mut() {
+                *cell = union;
+                members.extend(dropped.into_iter().flatten());
+            }
+        }
+        let mut mapping = vec![0; self.len()];
+        let mut cells = Vec::new();
+        for (cell, members) in merged.into_iter().flatten() {
+            for i in members {
+                mapping[i] = cells.len();
+            }
+            cells.push(cell);
+        }
+        MergedLayout {
+            layout: Layout::new(cells),
+            mapping,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::point::Point;
+
+    fn rect(x: i32, y: i32, width: i32, height: i32) -> AxisAlignedRectangle<i32> {
+        AxisAlignedRectangle::new(&Point::new(x, y), &Rectangle::new(width, height))
+    }
+
+    #[test]
+    fn test_merge_small_cells() {
+        let layout = Layout::new(vec![
+            rect(0, 0, 50, 10),
+            rect(50, 0, 2, 10),
+            rect(52, 0, 40, 10),
+            rect(92, 0, 3, 10),
+            rect(95, 0, 5, 10),
+        ]);
+        let merged = layout.merge_small_cells(60);
+        // 2 goes into 50 and 3 into 40 (the earlier ones of the same side), then 5 into 40 + 3
+        assert_eq!(
+            merged.layout.cells(),
+            &[rect(0, 0, 52, 10), rect(52, 0, 48, 10)][..]
+        );
+        assert_eq!(merged.mapping, vec![0, 0, 1, 1, 1]);
+        // nothing is small
+        let merged = layout.merge_small_cells(0);
+        assert_eq!(merged.layout, layout);
+        assert_eq!(merged.mapping, vec![0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_merge_needs_a_whole_side() {
+        // the small cell touches two cells, but shares a whole side with neither
+        let layout = Layout::new(vec![rect(0, 0, 1, 1), rect(1, 0, 3, 2), rect(0, 1, 1, 1)]);
+        let merged = layout.merge_small_cells(2);
+        // the two small cells on the left share a whole side with each other
+        assert_eq!(
+            merged.layout.cells(),
+            &[rect(0, 0, 1, 2), rect(1, 0, 3, 2)][..]
+        );
+        assert_eq!(merged.mapping, vec![0, 1, 0]);
+        let layout = Layout::new(vec![rect(0, 0, 1, 1), rect(1, 0, 3, 2)]);
+        assert_eq!(layout.merge_small_cells(2).layout, layout);
+    }
+}
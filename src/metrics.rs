@@ -0,0 +1,329 @@
+use num_traits::{Bounded, Num, NumAssignOps, NumCast, NumOps};
+
+use crate::accuracy::area_errors;
+use crate::area::Area;
+use crate::axis_aligned_rectangle::AxisAlignedRectangle;
+use crate::component::Component;
+use crate::layout::Layout;
+use crate::objective::{LayoutObjective, OrderDisplacement};
+use crate::rectangle::RectangleSize;
+use crate::tolerance::Tolerance;
+
+/// how far the aspect ratio (width / height) of a cell is from the target.
+/// 0 means exactly the target, 1 means twice (or half) as wide as the target, and so on
+pub fn aspect_error<T>(width: T, height: T, target: T) -> T
+where
+    T: Copy + Num + NumAssignOps + NumOps + PartialOrd,
+{
+    let ratio = width / height;
+    let error = match ratio > target {
+        true => ratio / target,
+        false => target / ratio,
+    };
+    error - T::one()
+}
+
+/// the aspect error of the worst cell. see `aspect_error`
+pub fn worst_aspect_error<T, C>(cells: &[C], target: T) -> T
+where
+    T: Copy + Num + NumAssignOps + NumOps + PartialOrd,
+    C: RectangleSize<T>,
+{
+    cells
+        .iter()
+        .map(|c| aspect_error(c.width(), c.height(), target))
+        .fold(T::zero(), |worst, e| match e > worst {
+            true => e,
+            false => worst,
+        })
+}
+
+/// the mean aspect error of the cells. see `aspect_error`
+pub fn mean_aspect_error<T, C>(cells: &[C], target: T) -> T
+where
+    T: Copy + Num + NumAssignOps + NumOps + PartialOrd,
+    C: RectangleSize<T>,
+{
+    let mut sum = T::zero();
+    let mut count = T::zero();
+    for c in cells {
+        sum += aspect_error(c.width(), c.height(), target);
+        count += T::one();
+    }
+    match count == T::zero() {
+        true => T::zero(),
+        false => sum / count,
+    }
+}
+
+/// the largest difference between the share of the container's area a cell covers and its normalized weight.
+/// see `accuracy::area_errors`
+pub fn worst_weight_error<T, C>(cells: &[C], container_area: T, weights: &[T]) -> T
+where
+    T: Copy + Num + NumAssignOps + NumOps + PartialOrd + for<'a> std::iter::Sum<&'a T>,
+    C: Area<T>,
+{
+    area_errors(cells, container_area, weights)
+        .into_iter()
+        .map(|e| match e < T::zero() {
+            true => T::zero() - e,
+            false => e,
+        })
+        .fold(T::zero(), |worst, e| match e > worst {
+            true => e,
+            false => worst,
+        })
+}
+
+/// Counts of cells by aspect error (see `aspect_error`).
+/// `counts[i]` is the number of cells with an error below `edges[i]` (and not below the previous edge);
+/// the last count is for the cells at or above the last edge
+#[derive(Debug, PartialEq, Clone)]
+pub struct AspectErrorHistogram<T> {
+    pub edges: Vec<T>,
+    pub counts: Vec<usize>,
+}
+
+impl<T> AspectErrorHistogram<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps + PartialOrd,
+{
+    /// `edges` must be in ascending order
+    pub fn new<C>(cells: &[C], target: T, edges: &[T]) -> Self
+    where
+        C: RectangleSize<T>,
+    {
+        let mut counts = vec![0; edges.len() + 1];
+        for c in cells {
+            let error = aspect_error(c.width(), c.height(), target);
+            let bin = edges
+                .iter()
+                .position(|edge| error < *edge)
+                .unwrap_or(edges.len());
+            counts[bin] += 1;
+        }
+        Self {
+            edges: edges.to_vec(),
+            counts,
+        }
+    }
+
+    pub fn total(&self) -> usize {
+        self.counts.iter().sum()
+    }
+}
+
+impl<T> std::fmt::Display for AspectErrorHistogram<T>
+where
+    T: std::fmt::Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (edge, count) in self.edges.iter().zip(self.counts.iter()) {
+            writeln!(f, "< {}: {}", edge, count)?;
+        }
+        match (self.edges.last(), self.counts.last()) {
+            (Some(edge), Some(count)) => write!(f, ">= {}: {}", edge, count),
+            (None, Some(count)) => write!(f, "all: {}", count),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Which of two layouts is better for a measure
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Preference {
+    A,
+    B,
+    Equal,
+}
+
+impl Preference {
+    /// lower is better; values within the tolerance are equal
+    fn lower<T>(a: T, b: T, tolerance: &Tolerance<T>) -> Self
+    where
+        T: Copy + Num + NumAssignOps + NumOps + PartialOrd,
+    {
+        match (tolerance.approx_eq(a, b), a < b) {
+            (true, _) => Preference::Equal,
+            (false, true) => Preference::A,
+            (false, false) => Preference::B,
+        }
+    }
+}
+
+/// Comparison of two layouts of the same weights. see `compare`
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct LayoutComparison<T> {
+    /// the worst aspect errors of a and b
+    pub worst_aspect_error: (T, T),
+    /// the order displacements of a and b (see `objective::OrderDisplacement`)
+    pub order_displacement: (T, T),
+    /// the sum of the distances (dx + dy) between the centers of corresponding cells of a and b
+    pub displacement: T,
+}
+
+impl<T> LayoutComparison<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps + PartialOrd,
+{
+    /// which worst aspect error is lower. errors within the tolerance (e.g. `Tolerance::default()`) are equal
+    pub fn better_worst_aspect(&self, tolerance: &Tolerance<T>) -> Preference {
+        Preference::lower(
+            self.worst_aspect_error.0,
+            self.worst_aspect_error.1,
+            tolerance,
+        )
+    }
+
+    /// which order displacement is lower. displacements within the tolerance are equal
+    pub fn better_order_preservation(&self, tolerance: &Tolerance<T>) -> Preference {
+        Preference::lower(
+            self.order_displacement.0,
+            self.order_displacement.1,
+            tolerance,
+        )
+    }
+}
+
+/// compare two layouts of the same weights, e.g. when the user toggles algorithms.
+/// aspect errors are measured against the target aspect ratio
+pub fn compare<T>(
+    layout_a: &Layout<T>,
+    layout_b: &Layout<T>,
+    weights: &[T],
+    aspect_ratio: T,
+) -> LayoutComparison<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps + PartialOrd + NumCast + Bounded,
+{
+    let two = T::one() + T::one();
+    let center = |cell: &AxisAlignedRectangle<T>| {
+        (
+            cell.x() + cell.width() / two,
+            cell.y() + cell.height() / two,
+        )
+    };
+    let distance = |a: T, b: T| match a > b {
+        true => a - b,
+        false => b - a,
+    };
+    let displacement = layout_a
+        .iter()
+        .zip(layout_b.iter())
+        .fold(T::zero(), |sum, (a, b)| {
+            let (ax, ay) = center(a);
+            let (bx, by) = center(b);
+            sum + distance(ax, bx) + distance(ay, by)
+        });
+    LayoutComparison {
+        worst_aspect_error: (
+            worst_aspect_error(layout_a.cells(), aspect_ratio),
+            worst_aspect_error(layout_b.cells(), aspect_ratio),
+        ),
+        order_displacement: (
+            OrderDisplacement.score(layout_a.cells(), weights),
+            OrderDisplacement.score(layout_b.cells(), weights),
+        ),
+        displacement,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::axis::Axis;
+    use crate::dividing::Dividing;
+    use crate::emission_order::EmissionOrder;
+    use crate::point::Point;
+    use crate::rectangle::Rectangle;
+
+    #[test]
+    fn test_aspect_error() {
+        assert_eq!(aspect_error(1.0, 1.0, 1.0), 0.0);
+        assert_eq!(aspect_error(2.0, 1.0, 1.0), 1.0);
+        assert_eq!(aspect_error(1.0, 2.0, 1.0), 1.0);
+        assert_eq!(aspect_error(3.0, 2.0, 1.5), 0.0);
+        assert_eq!(aspect_error(3.0, 1.0, 1.5), 1.0);
+    }
+
+    #[test]
+    fn test_worst_and_mean_aspect_error() {
+        let cells = vec![
+            Rectangle::new(1.0, 1.0),
+            Rectangle::new(2.0, 1.0),
+            Rectangle::new(1.0, 4.0),
+        ];
+        assert_eq!(worst_aspect_error(&cells, 1.0), 3.0);
+        assert_eq!(mean_aspect_error(&cells, 1.0), 4.0 / 3.0);
+        let empty: Vec<Rectangle<f64>> = vec![];
+        assert_eq!(worst_aspect_error(&empty, 1.0), 0.0);
+        assert_eq!(mean_aspect_error(&empty, 1.0), 0.0);
+    }
+
+    #[test]
+    fn test_worst_weight_error() {
+        let rect = AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(10.0, 1.0));
+        let cells = rect.divide_by_weights_and_axis(&[1.0, 1.0], Axis::Vertical);
+        assert_eq!(worst_weight_error(&cells, 10.0, &[1.0, 1.0]), 0.0);
+        assert_eq!(worst_weight_error(&cells, 10.0, &[3.0, 1.0]), 0.25);
+    }
+
+    #[test]
+    fn test_aspect_error_histogram() {
+        let cells = vec![
+            Rectangle::new(1.0, 1.0),
+            Rectangle::new(1.1, 1.0),
+            Rectangle::new(2.0, 1.0),
+            Rectangle::new(1.0, 4.0),
+        ];
+        let histogram = AspectErrorHistogram::new(&cells, 1.0, &[0.05, 0.5, 2.0]);
+        assert_eq!(histogram.counts, vec![1, 1, 1, 1]);
+        assert_eq!(histogram.total(), 4);
+        assert_eq!(
+            histogram.to_string(),
+            "< 0.05: 1\n< 0.5: 1\n< 2: 1\n>= 2: 1"
+        );
+
+        let histogram = AspectErrorHistogram::new(&cells, 1.0, &[]);
+        assert_eq!(histogram.counts, vec![4]);
+        assert_eq!(histogram.to_string(), "all: 4");
+    }
+
+    #[test]
+    fn test_compare() {
+        let rect = AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(4.0, 4.0));
+        let weights = vec![1.0, 1.0, 1.0, 1.0];
+        let slices = Layout::from(rect.divide_by_weights_and_axis(&weights, Axis::Vertical));
+        let strips = Layout::divide_strips(
+            &rect,
+            &weights,
+            Axis::Horizontal,
+            1.0,
+            EmissionOrder::Forward,
+        );
+        let comparison = compare(&slices, &strips, &weights, 1.0);
+        assert_eq!(comparison.worst_aspect_error, (3.0, 0.0));
+        assert_eq!(
+            comparison.better_worst_aspect(&Tolerance::default()),
+            Preference::B
+        );
+        assert_eq!(
+            comparison.better_worst_aspect(&Tolerance::absolute(3.0)),
+            Preference::Equal
+        );
+        assert_eq!(comparison.order_displacement, (0.0, 0.0));
+        assert_eq!(
+            comparison.better_order_preservation(&Tolerance::default()),
+            Preference::Equal
+        );
+        // centers: (0.5, 2), (1.5, 2), (2.5, 2), (3.5, 2) against (1, 1), (3, 1), (1, 3), (3, 3)
+        assert_eq!(comparison.displacement, 1.5 + 2.5 + 2.5 + 1.5);
+
+        let same = compare(&slices, &slices, &weights, 1.0);
+        assert_eq!(same.displacement, 0.0);
+        assert_eq!(
+            same.better_worst_aspect(&Tolerance::default()),
+            Preference::Equal
+        );
+    }
+}
@@ -0,0 +1,195 @@
+//! Random partitions for generative art, in the style of Mondrian's compositions.
+use num_traits::{Float, Num, NumAssignOps, NumOps};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::axis::{Axis, SizeForAxis};
+use crate::axis_aligned_rectangle::AxisAlignedRectangle;
+use crate::color::Rgba;
+use crate::dividing::Dividing;
+use crate::layout::Layout;
+use crate::rectangle::RectangleSize;
+
+/// The constraints of a composition
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct MondrianConfig<T> {
+    /// the number of cells to aim at. fewer come out when no cell can be cut within the other constraints
+    pub cells: usize,
+    /// no cell is more elongated than this (the longer side over the shorter one, at least 1)
+    pub max_aspect_ratio: T,
+    /// how unequal cuts are: the smaller part is between this fraction and half of the cut cell.
+    /// e.g. 0.25 gives cuts between 1:3 and 1:1.5, never in halves
+    pub unequal: T,
+}
+
+impl<T> MondrianConfig<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps + Float,
+{
+    /// a composition from the seed. the same seed gives the same composition
+    pub fn generate(&self, container: &AxisAlignedRectangle<T>, seed: u64) -> Layout<T> {
+        self.generate_with_rng(container, &mut StdRng::seed_from_u64(seed))
+    }
+
+    /// a composition with cuts from the random number generator: the largest cell that can be cut is cut
+    /// across its longer side, until there are enough cells
+    pub fn generate_with_rng<R: Rng>(
+        &self,
+        container: &AxisAlignedRectangle<T>,
+        rng: &mut R,
+    ) -> Layout<T> {
+        let mut cells = vec![container.clone()];
+        let mut uncuttable = vec![false];
+        while cells.len() < self.cells {
+            let Some(i) = (0..cells.len()).filter(|i| !uncuttable[*i]).max_by(|a, b| {
+                let area = |cell: &AxisAlignedRectangle<T>| cell.width() * cell.height();
+                area(&cells[*a])
+                    .partial_cmp(&area(&cells[*b]))
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            }) else {
+                break;
+            };
+            match self.cut(&cells[i], rng) {
+                Some((first, second)) => {
+                    cells[i] = first;
+                    cells.insert(i + 1, second);
+                    uncuttable.insert(i + 1, false);
+                }
+                None => uncuttable[i] = true,
+            }
+        }
+        Layout::new(cells)
+    }
+
+    fn cut<R: Rng>(
+        &self,
+        cell: &AxisAlignedRectangle<T>,
+        rng: &mut R,
+    ) -> Option<(AxisAlignedRectangle<T>, AxisAlignedRectangle<T>)> {
+        let axis = match cell.width() >= cell.height() {
+            true => Axis::Vertical,
+            false => Axis::Horizontal,
+        };
+        let half = T::one() / (T::one() + T::one());
+        let unequal = self.unequal.max(T::zero()).min(half);
+        let elongation = |rect: &AxisAlignedRectangle<T>| {
+            let (width, height) = (rect.width(), rect.height());
+            width.max(height) / width.min(height)
+        };
+        // a few tries at a cut whose parts are not too elongated
+        for _ in 0..8 {
+            let smaller =
+                unequal + T::from(rng.gen::<f64>()).unwrap_or(T::zero()) * (half - unequal);
+            let fraction = match rng.gen_bool(0.5) {
+                true => smaller,
+                false => T::one() - smaller,
+            };
+            let (first, second) = cell.divide(fraction * cell.size_for_axis(axis), axis);
+            if elongation(&first) <= self.max_aspect_ratio
+                && elongation(&second) <= self.max_aspect_ratio
+            {
+                return Some((first, second));
+            }
+        }
+        None
+    }
+}
+
+/// each cell with a color of the palette: the first color is the background, and about `accent_share`
+/// of the cells get one of the other colors, at random from the seed
+pub fn paint<T>(
+    layout: &Layout<T>,
+    palette: &[Rgba],
+    accent_share: f64,
+    seed: u64,
+) -> Vec<(AxisAlignedRectangle<T>, Rgba)>
+where
+    T: Copy + Num + NumAssignOps + NumOps,
+{
+    let mut rng = StdRng::seed_from_u64(seed);
+    let Some((background, accents)) = palette.split_first() else {
+        return vec![];
+    };
+    layout
+        .iter()
+        .map(|cell| {
+            let color = match !accents.is_empty() && rng.gen_bool(accent_share.clamp(0.0, 1.0)) {
+                true => accents[rng.gen_range(0..accents.len())],
+                false => *background,
+            };
+            (cell.clone(), color)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::point::Point;
+    use crate::rectangle::Rectangle;
+    use crate::testing::verify_tiling;
+    use crate::tolerance::Tolerance;
+
+    fn canvas() -> AxisAlignedRectangle<f64> {
+        AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(600.0, 400.0))
+    }
+
+    #[test]
+    fn test_generate() {
+        let config = MondrianConfig {
+            cells: 12,
+            max_aspect_ratio: 4.0,
+            unequal: 0.25,
+        };
+        let layout = config.generate(&canvas(), 7);
+        assert_eq!(layout.len(), 12);
+        assert_eq!(
+            verify_tiling(&canvas(), layout.cells(), &Tolerance::default()),
+            Ok(())
+        );
+        for cell in layout.iter() {
+            let (long, short) = match cell.width() > cell.height() {
+                true => (cell.width(), cell.height()),
+                false => (cell.height(), cell.width()),
+            };
+            assert!(long / short <= 4.0);
+        }
+        assert_eq!(config.generate(&canvas(), 7), layout);
+    }
+
+    #[test]
+    fn test_generate_bounded() {
+        // a cut of a square leaves parts of aspect ratio 2 at least, so none is allowed
+        let config = MondrianConfig {
+            cells: 10,
+            max_aspect_ratio: 1.5,
+            unequal: 0.25,
+        };
+        let square = AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(1.0, 1.0));
+        assert_eq!(config.generate(&square, 0).len(), 1);
+    }
+
+    #[test]
+    fn test_paint() {
+        let white = Rgba::rgb(255, 255, 255);
+        let red = Rgba::rgb(200, 20, 20);
+        let blue = Rgba::rgb(20, 40, 160);
+        let config = MondrianConfig {
+            cells: 20,
+            max_aspect_ratio: 5.0,
+            unequal: 0.2,
+        };
+        let layout = config.generate(&canvas(), 1);
+        let painted = paint(&layout, &[white, red, blue], 0.3, 1);
+        assert_eq!(painted.len(), layout.len());
+        assert!(painted.iter().any(|(_, color)| *color == white));
+        assert!(painted
+            .iter()
+            .all(|(_, color)| [white, red, blue].contains(color)));
+        assert_eq!(paint(&layout, &[white, red, blue], 0.3, 1), painted);
+        assert!(paint(&layout, &[white], 1.0, 1)
+            .iter()
+            .all(|(_, color)| *color == white));
+        assert!(paint(&layout, &[], 0.3, 1).is_empty());
+    }
+}
@@ -0,0 +1,189 @@
+use num_traits::{Bounded, Num, NumAssignOps, NumCast, NumOps};
+
+use crate::axis_aligned_rectangle::AxisAlignedRectangle;
+use crate::component::Component;
+use crate::metrics::{mean_aspect_error, worst_aspect_error};
+use crate::rectangle::RectangleSize;
+
+/// Scores divided rectangles for the weights they were divided by. lower is better.
+/// closures `Fn(&[C], &[T]) -> T` are objectives too
+pub trait LayoutObjective<T, C> {
+    fn score(&self, cells: &[C], weights: &[T]) -> T;
+}
+
+impl<T, C, F> LayoutObjective<T, C> for F
+where
+    F: Fn(&[C], &[T]) -> T,
+{
+    fn score(&self, cells: &[C], weights: &[T]) -> T {
+        self(cells, weights)
+    }
+}
+
+/// The aspect error of the worst cell (see `metrics::aspect_error`)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MaxAspectError<T> {
+    pub target: T,
+}
+
+impl<T, C> LayoutObjective<T, C> for MaxAspectError<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps + PartialOrd,
+    C: RectangleSize<T>,
+{
+    fn score(&self, cells: &[C], _weights: &[T]) -> T {
+        worst_aspect_error(cells, self.target)
+    }
+}
+
+/// The mean aspect error of the cells (see `metrics::aspect_error`)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MeanAspectError<T> {
+    pub target: T,
+}
+
+impl<T, C> LayoutObjective<T, C> for MeanAspectError<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps + PartialOrd,
+    C: RectangleSize<T>,
+{
+    fn score(&self, cells: &[C], _weights: &[T]) -> T {
+        mean_aspect_error(cells, self.target)
+    }
+}
+
+/// How far the cells are from the input order when read row by row (top to bottom, then left to right):
+/// the mean distance between each cell's position in reading order and its index.
+/// the largest value of `T` when the distances do not fit in it, so that such a layout never wins
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct OrderDisplacement;
+
+impl<T> LayoutObjective<T, AxisAlignedRectangle<T>> for OrderDisplacement
+where
+    T: Copy + Num + NumAssignOps + NumOps + PartialOrd + NumCast + Bounded,
+{
+    fn score(&self, cells: &[AxisAlignedRectangle<T>], _weights: &[T]) -> T {
+        if cells.is_empty() {
+            return T::zero();
+        }
+        let mut reading_order: Vec<usize> = (0..cells.len()).collect();
+        reading_order.sort_by(|a, b| {
+            let (a, b) = (&cells[*a], &cells[*b]);
+            (a.y(), a.x())
+                .partial_cmp(&(b.y(), b.x()))
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        let total: usize = reading_order
+            .iter()
+            .enumerate()
+            .map(|(position, index)| position.abs_diff(*index))
+            .sum();
+        match (T::from(total), T::from(cells.len())) {
+            (Some(total), Some(count)) => total / count,
+            _ => T::max_value(),
+        }
+    }
+}
+
+/// A weighted sum of objectives
+pub struct Composite<'a, T, C> {
+    parts: Vec<(T, Box<dyn LayoutObjective<T, C> + 'a>)>,
+}
+
+impl<'a, T, C> Composite<'a, T, C> {
+    pub fn new() -> Self {
+        Self { parts: Vec::new() }
+    }
+
+    /// add an objective, multiplied by the factor
+    pub fn with<O>(mut self, factor: T, objective: O) -> Self
+    where
+        O: LayoutObjective<T, C> + 'a,
+    {
+        self.parts.push((factor, Box::new(objective)));
+        self
+    }
+}
+
+impl<T, C> Default for Composite<'_, T, C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, C> LayoutObjective<T, C> for Composite<'_, T, C>
+where
+    T: Copy + Num + NumAssignOps + NumOps,
+{
+    fn score(&self, cells: &[C], weights: &[T]) -> T {
+        self.parts
+            .iter()
+            .fold(T::zero(), |sum, (factor, objective)| {
+                sum + *factor * objective.score(cells, weights)
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::axis::Axis;
+    use crate::dividing::Dividing;
+    use crate::point::Point;
+    use crate::rectangle::Rectangle;
+
+    #[test]
+    fn test_closure() {
+        let objective =
+            |cells: &[Rectangle<f64>], weights: &[f64]| cells.len() as f64 + weights.len() as f64;
+        assert_eq!(objective.score(&[Rectangle::new(1.0, 1.0)], &[1.0]), 2.0);
+    }
+
+    #[test]
+    fn test_aspect_errors() {
+        let cells = vec![Rectangle::new(1.0, 1.0), Rectangle::new(2.0, 1.0)];
+        assert_eq!(
+            MaxAspectError { target: 1.0 }.score(&cells, &[1.0, 2.0]),
+            1.0
+        );
+        assert_eq!(
+            MeanAspectError { target: 1.0 }.score(&cells, &[1.0, 2.0]),
+            0.5
+        );
+    }
+
+    #[test]
+    fn test_order_displacement() {
+        let rect = AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(4.0, 1.0));
+        let weights = vec![1.0, 1.0, 1.0, 1.0];
+        let divided = rect.divide_by_weights_and_axis(&weights, Axis::Vertical);
+        assert_eq!(OrderDisplacement.score(&divided, &weights), 0.0);
+        let mut reversed = divided.clone();
+        reversed.reverse();
+        assert_eq!(OrderDisplacement.score(&reversed, &weights), 2.0);
+        assert_eq!(OrderDisplacement.score(&[], &weights), 0.0);
+
+        // 30 cells in reverse are 450 positions off in total, more than a u8 holds
+        let reversed: Vec<AxisAlignedRectangle<u8>> = (0..30)
+            .rev()
+            .map(|x| AxisAlignedRectangle::new(&Point::new(x, 0), &Rectangle::new(1, 1)))
+            .collect();
+        assert_eq!(OrderDisplacement.score(&reversed, &[]), u8::MAX);
+    }
+
+    #[test]
+    fn test_composite() {
+        let rect = AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(8.0, 1.0));
+        let weights = vec![1.0, 1.0, 1.0, 1.0];
+        let mut divided = rect.divide_by_weights_and_axis(&weights, Axis::Vertical);
+        divided.reverse();
+        let objective = Composite::new()
+            .with(1.0, MaxAspectError { target: 1.0 })
+            .with(10.0, OrderDisplacement);
+        assert_eq!(objective.score(&divided, &weights), 1.0 + 20.0);
+        assert_eq!(
+            Composite::<f64, AxisAlignedRectangle<f64>>::default().score(&divided, &weights),
+            0.0
+        );
+    }
+}
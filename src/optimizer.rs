@@ -0,0 +1,244 @@
+//! Local search refinement of strip layouts.
+//!
+//! Starting from strip groups (see `Dividing::strip_groups`), the optimizer repeatedly tries
+//! every local move and takes the one that improves the objective the most:
+//! - moving the item at a strip boundary into the neighbouring strip (nudging the boundary)
+//! - swapping the items on both sides of a strip boundary
+//!
+//! It stops when no move improves the score or the budget is used up.
+use std::time::{Duration, Instant};
+
+use num_traits::{Num, NumAssignOps, NumOps};
+
+use crate::area::Area;
+use crate::axis::{Axis, SizeForAxis};
+use crate::dividing::Dividing;
+use crate::objective::LayoutObjective;
+use crate::rectangle::RectangleSize;
+
+/// How long the optimizer may search
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Budget {
+    pub max_iterations: usize,
+    /// wall clock limit. leave it `None` on targets without a clock (wasm32-unknown-unknown)
+    pub max_duration: Option<Duration>,
+}
+
+impl Default for Budget {
+    fn default() -> Self {
+        Self {
+            max_iterations: 100,
+            max_duration: None,
+        }
+    }
+}
+
+/// The result of the local search
+#[derive(Debug, Clone, PartialEq)]
+pub struct Optimized<T, D> {
+    /// the indices of the weights in each strip
+    pub groups: Vec<Vec<usize>>,
+    /// the n-th rectangle corresponds to the n-th weight
+    pub cells: Vec<D>,
+    pub score: T,
+    /// the number of improving moves taken
+    pub iterations: usize,
+}
+
+/// improve the strip groups of a layout by local moves, lowering the score of the objective
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        level = "debug",
+        skip_all,
+        fields(weights = weights.len(), strips = initial_groups.len())
+    )
+)]
+pub fn optimize_strips<T, D, O>(
+    rect: &D,
+    weights: &[T],
+    primary_axis: Axis,
+    initial_groups: Vec<Vec<usize>>,
+    objective: O,
+    budget: Budget,
+) -> Optimized<T, D>
+where
+    D: Dividing<T> + Sized + RectangleSize<T> + Clone + SizeForAxis<T> + Area<T>,
+    T: Copy + for<'a> std::iter::Sum<&'a T> + Num + NumAssignOps + NumOps + PartialOrd,
+    O: LayoutObjective<T, D>,
+{
+    let started = budget.max_duration.map(|_| Instant::now());
+    let mut groups = initial_groups;
+    let mut cells = rect.divide_by_strip_groups(weights, &groups, primary_axis);
+    let mut score = objective.score(&cells, weights);
+    let mut iterations = 0;
+
+    while iterations < budget.max_iterations {
+        if let (Some(started), Some(max_duration)) = (started, budget.max_duration) {
+            if started.elapsed() >= max_duration {
+                break;
+            }
+        }
+        let mut best: Option<(Vec<Vec<usize>>, Vec<D>, T)> = None;
+        for candidate in neighbours(&groups) {
+            let candidate_cells = rect.divide_by_strip_groups(weights, &candidate, primary_axis);
+            let candidate_score = objective.score(&candidate_cells, weights);
+            let best_score = match &best {
+                Some((_, _, s)) => *s,
+                None => score,
+            };
+            if candidate_score < best_score {
+                best = Some((candidate, candidate_cells, candidate_score));
+            }
+        }
+        let Some((best_groups, best_cells, best_score)) = best else {
+            break;
+        };
+        groups = best_groups;
+        cells = best_cells;
+        score = best_score;
+        iterations += 1;
+    }
+    #[cfg(feature = "tracing")]
+    tracing::debug!(iterations, strips = groups.len(), "optimized strips");
+
+    Optimized {
+        groups,
+        cells,
+        score,
+        iterations,
+    }
+}
+
+/// every strip grouping one local move away
+fn neighbours(groups: &[Vec<usize>]) -> Vec<Vec<Vec<usize>>> {
+    let mut result = Vec::new();
+    for i in 0..groups.len().saturating_sub(1) {
+        let (left, right) = (&groups[i], &groups[i + 1]);
+        // nudge the boundary forward: the last item of the left strip moves to the right strip
+        if let Some((last, rest)) = left.split_last() {
+            let mut candidate = groups.to_vec();
+            candidate[i] = rest.to_vec();
+            candidate[i + 1] = std::iter::once(*last)
+                .chain(right.iter().copied())
+                .collect();
+            result.push(without_empty(candidate));
+        }
+        // nudge the boundary backward: the first item of the right strip moves to the left strip
+        if let Some((first, rest)) = right.split_first() {
+            let mut candidate = groups.to_vec();
+            candidate[i] = left
+                .iter()
+                .copied()
+                .chain(std::iter::once(*first))
+                .collect();
+            candidate[i + 1] = rest.to_vec();
+            result.push(without_empty(candidate));
+        }
+        // swap the items on both sides of the boundary
+        if let (Some(last), Some(first)) = (left.last(), right.first()) {
+            let mut candidate = groups.to_vec();
+            let left_len = candidate[i].len();
+            candidate[i][left_len - 1] = *first;
+            candidate[i + 1][0] = *last;
+            result.push(candidate);
+        }
+    }
+    result
+}
+
+fn without_empty(groups: Vec<Vec<usize>>) -> Vec<Vec<usize>> {
+    groups.into_iter().filter(|g| !g.is_empty()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::axis_aligned_rectangle::AxisAlignedRectangle;
+    use crate::metrics::worst_aspect_error;
+    use crate::objective::MaxAspectError;
+    use crate::point::Point;
+    use crate::rectangle::Rectangle;
+
+    #[test]
+    fn test_neighbours() {
+        let groups = vec![vec![0, 1], vec![2]];
+        let result = neighbours(&groups);
+        assert_eq!(
+            result,
+            vec![
+                vec![vec![0], vec![1, 2]],
+                vec![vec![0, 1, 2]],
+                vec![vec![0, 2], vec![1]],
+            ]
+        );
+        assert!(neighbours(&[vec![0, 1]]).is_empty());
+    }
+
+    #[test]
+    fn test_optimize_strips() {
+        let rect = AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(300.0, 200.0));
+        let weights = vec![9.0, 1.0, 1.0, 1.0, 5.0, 5.0, 1.0, 1.0];
+        let initial = rect.strip_groups(&weights, Axis::Vertical, 1.0);
+        let initial_cells = rect.divide_by_strip_groups(&weights, &initial, Axis::Vertical);
+        let objective =
+            |cells: &[AxisAlignedRectangle<f64>], _: &[f64]| worst_aspect_error(cells, 1.0);
+        let optimized = optimize_strips(
+            &rect,
+            &weights,
+            Axis::Vertical,
+            initial,
+            objective,
+            Budget::default(),
+        );
+        assert!(optimized.score <= objective.score(&initial_cells, &weights));
+        assert_eq!(optimized.cells.len(), weights.len());
+        assert_eq!(
+            optimized.cells,
+            rect.divide_by_strip_groups(&weights, &optimized.groups, Axis::Vertical)
+        );
+        // every weight is still in exactly one strip
+        let mut indices: Vec<usize> = optimized.groups.iter().flatten().copied().collect();
+        indices.sort();
+        assert_eq!(indices, (0..weights.len()).collect::<Vec<_>>());
+        // each cell still has the area of its weight
+        let total: f64 = weights.iter().sum();
+        for (cell, weight) in optimized.cells.iter().zip(weights.iter()) {
+            assert!((cell.area() / rect.area() - weight / total).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_budget() {
+        let rect = Rectangle::new(100.0, 100.0);
+        let weights = vec![1.0, 2.0, 3.0, 4.0, 5.0];
+        let initial = vec![vec![0], vec![1], vec![2], vec![3], vec![4]];
+        let objective = MaxAspectError { target: 1.0 };
+        let optimized = optimize_strips(
+            &rect,
+            &weights,
+            Axis::Vertical,
+            initial.clone(),
+            objective,
+            Budget {
+                max_iterations: 0,
+                max_duration: None,
+            },
+        );
+        assert_eq!(optimized.iterations, 0);
+        assert_eq!(optimized.groups, initial);
+
+        let optimized = optimize_strips(
+            &rect,
+            &weights,
+            Axis::Vertical,
+            initial,
+            objective,
+            Budget {
+                max_iterations: 1000,
+                max_duration: Some(Duration::from_secs(10)),
+            },
+        );
+        assert!(optimized.iterations > 0);
+    }
+}
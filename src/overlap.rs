@@ -0,0 +1,147 @@
+use num_traits::{Num, NumAssignOps, NumOps};
+
+use crate::axis::{Axis, SizeForAxis, ValueForAxis};
+use crate::axis_aligned_rectangle::AxisAlignedRectangle;
+use crate::component::Component;
+use crate::point::Point;
+use crate::rectangle::{Rectangle, RectangleSize};
+
+/// How overlapping rectangles are separated
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum OverlapStrategy {
+    /// move both rectangles apart by half the overlap each, keeping their sizes
+    #[default]
+    Push,
+    /// cut half the overlap off each rectangle, keeping their outer edges
+    Shrink,
+}
+
+/// separate overlapping rectangles (e.g. after the user dragged one) along the axis they overlap less on.
+/// pushing one pair apart can make another overlap, so this repeats up to `rects.len()` passes.
+/// returns whether no overlaps are left
+pub fn resolve_overlaps<T>(rects: &mut [AxisAlignedRectangle<T>], strategy: OverlapStrategy) -> bool
+where
+    T: Copy + Num + NumAssignOps + NumOps + PartialOrd,
+{
+    for _ in 0..=rects.len() {
+        let mut resolved = true;
+        for i in 0..rects.len() {
+            for j in i + 1..rects.len() {
+                let Some(overlap) = rects[i].intersection(&rects[j]) else {
+                    continue;
+                };
+                resolved = false;
+                let (a, b) = separate(&rects[i], &rects[j], &overlap, strategy);
+                rects[i] = a;
+                rects[j] = b;
+            }
+        }
+        if resolved {
+            return true;
+        }
+    }
+    false
+}
+
+fn separate<T>(
+    a: &AxisAlignedRectangle<T>,
+    b: &AxisAlignedRectangle<T>,
+    overlap: &AxisAlignedRectangle<T>,
+    strategy: OverlapStrategy,
+) -> (AxisAlignedRectangle<T>, AxisAlignedRectangle<T>)
+where
+    T: Copy + Num + NumAssignOps + NumOps + PartialOrd,
+{
+    // the axis of the cut line between them: vertical when they overlap less in width
+    let axis = match overlap.width() < overlap.height() {
+        true => Axis::Vertical,
+        false => Axis::Horizontal,
+    };
+    let penetration = overlap.size_for_axis(axis);
+    let half = penetration / (T::one() + T::one());
+    let rest = penetration - half;
+    // the rectangle with the smaller center goes backward
+    let doubled_center = |r: &AxisAlignedRectangle<T>| {
+        r.origin().value_for_axis(axis) + r.origin().value_for_axis(axis) + r.size_for_axis(axis)
+    };
+    let a_first = doubled_center(a) <= doubled_center(b);
+    let (first, second) = match a_first {
+        true => (a, b),
+        false => (b, a),
+    };
+    let (first, second) = match strategy {
+        OverlapStrategy::Push => (
+            moved(first, axis, T::zero() - half, T::zero()),
+            moved(second, axis, rest, T::zero()),
+        ),
+        OverlapStrategy::Shrink => (
+            moved(first, axis, T::zero(), T::zero() - half),
+            moved(second, axis, rest, T::zero() - rest),
+        ),
+    };
+    match a_first {
+        true => (first, second),
+        false => (second, first),
+    }
+}
+
+/// move the rectangle along the axis and grow its size along the axis
+fn moved<T>(
+    rect: &AxisAlignedRectangle<T>,
+    axis: Axis,
+    offset: T,
+    grow: T,
+) -> AxisAlignedRectangle<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps,
+{
+    match axis {
+        Axis::Vertical => AxisAlignedRectangle::new(
+            &Point::new(rect.x() + offset, rect.y()),
+            &Rectangle::new(rect.width() + grow, rect.height()),
+        ),
+        Axis::Horizontal => AxisAlignedRectangle::new(
+            &Point::new(rect.x(), rect.y() + offset),
+            &Rectangle::new(rect.width(), rect.height() + grow),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(x: i32, y: i32, width: i32, height: i32) -> AxisAlignedRectangle<i32> {
+        AxisAlignedRectangle::new(&Point::new(x, y), &Rectangle::new(width, height))
+    }
+
+    #[test]
+    fn test_push() {
+        let mut rects = vec![rect(0, 0, 4, 4), rect(3, 1, 4, 4)];
+        assert!(resolve_overlaps(&mut rects, OverlapStrategy::Push));
+        assert_eq!(rects, vec![rect(0, 0, 4, 4), rect(4, 1, 4, 4)]);
+
+        let mut rects = vec![rect(0, 4, 4, 4), rect(1, 0, 4, 6)];
+        assert!(resolve_overlaps(&mut rects, OverlapStrategy::Push));
+        assert_eq!(rects, vec![rect(0, 5, 4, 4), rect(1, -1, 4, 6)]);
+    }
+
+    #[test]
+    fn test_shrink() {
+        let mut rects = vec![rect(0, 0, 4, 4), rect(2, 1, 4, 4)];
+        assert!(resolve_overlaps(&mut rects, OverlapStrategy::Shrink));
+        assert_eq!(rects, vec![rect(0, 0, 3, 4), rect(3, 1, 3, 4)]);
+    }
+
+    #[test]
+    fn test_cascade() {
+        let mut rects = vec![rect(0, 0, 4, 4), rect(2, 0, 4, 4), rect(6, 0, 4, 4)];
+        assert!(resolve_overlaps(&mut rects, OverlapStrategy::Push));
+        for (i, a) in rects.iter().enumerate() {
+            for b in rects.iter().skip(i + 1) {
+                assert_eq!(a.intersection(b), None);
+            }
+        }
+        assert!(resolve_overlaps::<i32>(&mut [], OverlapStrategy::Push));
+    }
+}
@@ -0,0 +1,669 @@
+//! Packing: the inverse of dividing. Instead of dividing a container by weights,
+//! place rectangles of given sizes inside it.
+use num_traits::{Num, NumAssignOps, NumOps};
+
+use crate::axis_aligned_rectangle::AxisAlignedRectangle;
+use crate::component::Component;
+use crate::point::Point;
+use crate::rectangle::{Rectangle, RectangleSize};
+use crate::region::RectilinearRegion;
+
+/// The result of packing. the n-th placement corresponds to the n-th size;
+/// `None` for the sizes that did not fit, whose indices are also listed in `unplaced`
+#[derive(Debug, PartialEq, Clone)]
+pub struct Packed<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps,
+{
+    pub placements: Vec<Option<AxisAlignedRectangle<T>>>,
+    pub unplaced: Vec<usize>,
+}
+
+impl<T> Packed<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps,
+{
+    pub(crate) fn from_placements(placements: Vec<Option<AxisAlignedRectangle<T>>>) -> Self {
+        let unplaced = placements
+            .iter()
+            .enumerate()
+            .filter(|(_, p)| p.is_none())
+            .map(|(i, _)| i)
+            .collect();
+        Self {
+            placements,
+            unplaced,
+        }
+    }
+
+    /// whether every size was placed
+    pub fn is_complete(&self) -> bool {
+        self.unplaced.is_empty()
+    }
+}
+
+/// A packing heuristic. packers are interchangeable: each places the sizes inside the container
+/// without overlaps, in the order given
+pub trait Packer<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps,
+{
+    fn pack(&self, container: &AxisAlignedRectangle<T>, sizes: &[Rectangle<T>]) -> Packed<T>;
+}
+
+/// Packing by guillotine cuts: each placement cuts its free rectangle into the placed item and two free rectangles.
+/// items are placed in the order given, each into the free rectangle it fits best (the least area left over)
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct GuillotinePacker;
+
+impl<T> Packer<T> for GuillotinePacker
+where
+    T: Copy + Num + NumAssignOps + NumOps + PartialOrd,
+{
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "debug", skip_all, fields(packer = "GuillotinePacker", sizes = sizes.len()))
+    )]
+    fn pack(&self, container: &AxisAlignedRectangle<T>, sizes: &[Rectangle<T>]) -> Packed<T> {
+        let mut free = RectilinearRegion::from(container.clone());
+        let placements = sizes
+            .iter()
+            .map(|size| {
+                let index = best_area_fit(free.rects(), size)?;
+                let target = free.rects()[index].clone();
+                free.subtract(&target);
+                let (right, bottom) = guillotine_split(&target, size);
+                for rect in right.iter().chain(bottom.iter()) {
+                    free.insert(rect);
+                }
+                Some(AxisAlignedRectangle::new(&target.origin(), size))
+            })
+            .collect();
+        Packed::from_placements(placements)
+    }
+}
+
+/// Which shelf an item goes to
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum ShelfFit {
+    /// only the last shelf; a new shelf is opened as soon as an item does not fit
+    #[default]
+    NextFit,
+    /// the first shelf the item fits in
+    FirstFit,
+}
+
+/// Packing into shelves: rows from top to bottom, as tall as the item that opened them,
+/// filled from left to right. good for many items of similar heights (e.g. text labels)
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct ShelfPacker {
+    pub fit: ShelfFit,
+}
+
+struct Shelf<T> {
+    y: T,
+    height: T,
+    used_width: T,
+}
+
+impl<T> Packer<T> for ShelfPacker
+where
+    T: Copy + Num + NumAssignOps + NumOps + PartialOrd,
+{
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "debug", skip_all, fields(packer = "ShelfPacker", sizes = sizes.len()))
+    )]
+    fn pack(&self, container: &AxisAlignedRectangle<T>, sizes: &[Rectangle<T>]) -> Packed<T> {
+        let mut shelves: Vec<Shelf<T>> = Vec::new();
+        let fits_shelf = |shelf: &Shelf<T>, size: &Rectangle<T>| {
+            size.height() <= shelf.height && shelf.used_width + size.width() <= container.width()
+        };
+        let placements = sizes
+            .iter()
+            .map(|size| {
+                let found = match self.fit {
+                    ShelfFit::NextFit => shelves
+                        .len()
+                        .checked_sub(1)
+                        .filter(|i| fits_shelf(&shelves[*i], size)),
+                    ShelfFit::FirstFit => shelves.iter().position(|s| fits_shelf(s, size)),
+                };
+                let index = match found {
+                    Some(index) => index,
+                    None => {
+                        let y = shelves.last().map_or(container.y(), |s| s.y + s.height);
+                        if size.width() > container.width()
+                            || y + size.height() > container.y() + container.height()
+                        {
+                            return None;
+                        }
+                        shelves.push(Shelf {
+                            y,
+                            height: size.height(),
+                            used_width: T::zero(),
+                        });
+                        shelves.len() - 1
+                    }
+                };
+                let shelf = &mut shelves[index];
+                let placed = AxisAlignedRectangle::new(
+                    &Point::new(container.x() + shelf.used_width, shelf.y),
+                    size,
+                );
+                shelf.used_width += size.width();
+                Some(placed)
+            })
+            .collect();
+        Packed::from_placements(placements)
+    }
+}
+
+/// Packing on a skyline: the top outline of the placed items. each item goes where it rests highest
+/// (then leftmost) on the skyline, so gaps under items are never reused, but few are made for items of similar heights
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct SkylinePacker;
+
+/// a horizontal part of the skyline: everything from `x` to `x + width` is occupied down from `y`
+#[derive(Debug, Clone, Copy)]
+struct Segment<T> {
+    x: T,
+    y: T,
+    width: T,
+}
+
+impl<T> Packer<T> for SkylinePacker
+where
+    T: Copy + Num + NumAssignOps + NumOps + PartialOrd,
+{
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "debug", skip_all, fields(packer = "SkylinePacker", sizes = sizes.len()))
+    )]
+    fn pack(&self, container: &AxisAlignedRectangle<T>, sizes: &[Rectangle<T>]) -> Packed<T> {
+        let mut skyline = vec![Segment {
+            x: container.x(),
+            y: container.y(),
+            width: container.width(),
+        }];
+        let right = container.x() + container.width();
+        let bottom = container.y() + container.height();
+        let placements = sizes
+            .iter()
+            .map(|size| {
+                let mut best: Option<Point<T>> = None;
+                for start in 0..skyline.len() {
+                    let x = skyline[start].x;
+                    if x + size.width() > right {
+                        break;
+                    }
+                    // the item rests on the highest segment below it
+                    let mut y = skyline[start].y;
+                    for segment in skyline[start..]
+                        .iter()
+                        .take_while(|s| s.x < x + size.width())
+                    {
+                        if segment.y > y {
+                            y = segment.y;
+                        }
+                    }
+                    if y + size.height() > bottom {
+                        continue;
+                    }
+                    if best.is_none_or(|b| y < b.y()) {
+                        best = Some(Point::new(x, y));
+                    }
+                }
+                let origin = best?;
+                raise_skyline(
+                    &mut skyline,
+                    origin.x(),
+                    size.width(),
+                    origin.y() + size.height(),
+                );
+                Some(AxisAlignedRectangle::new(&origin, size))
+            })
+            .collect();
+        Packed::from_placements(placements)
+    }
+}
+
+/// raise the skyline from `x` to `x + width` to `y`
+fn raise_skyline<T>(skyline: &mut Vec<Segment<T>>, x: T, width: T, y: T)
+where
+    T: Copy + Num + NumAssignOps + NumOps + PartialOrd,
+{
+    let end = x + width;
+    let mut raised: Vec<Segment<T>> = Vec::new();
+    let mut inserted = false;
+    for segment in skyline.iter() {
+        let segment_end = segment.x + segment.width;
+        if segment_end <= x || segment.x >= end {
+            if segment.x >= end && !inserted {
+                raised.push(Segment { x, y, width });
+                inserted = true;
+            }
+            raised.push(*segment);
+            continue;
+        }
+        // the part of the segment left of the item
+        if segment.x < x {
+            raised.push(Segment {
+                x: segment.x,
+                y: segment.y,
+                width: x - segment.x,
+            });
+        }
+        if !inserted {
+            raised.push(Segment { x, y, width });
+            inserted = true;
+        }
+        // the part of the segment right of the item
+        if segment_end > end {
+            raised.push(Segment {
+                x: end,
+                y: segment.y,
+                width: segment_end - end,
+            });
+        }
+    }
+    if !inserted {
+        raised.push(Segment { x, y, width });
+    }
+    // merge neighbours of the same height
+    let mut merged: Vec<Segment<T>> = Vec::new();
+    for segment in raised {
+        match merged.last_mut() {
+            Some(last) if last.y == segment.y => last.width += segment.width,
+            _ => merged.push(segment),
+        }
+    }
+    *skyline = merged;
+}
+
+/// Where an item went when packing into several bins
+#[derive(Debug, PartialEq, Clone)]
+pub struct BinPlacement<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps,
+{
+    /// the index of the bin in `MultiBinPacked::bins`
+    pub bin: usize,
+    pub rect: AxisAlignedRectangle<T>,
+}
+
+/// The result of packing into several bins. the n-th placement corresponds to the n-th size
+#[derive(Debug, PartialEq, Clone)]
+pub struct MultiBinPacked<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps,
+{
+    /// the bins opened, in order
+    pub bins: Vec<AxisAlignedRectangle<T>>,
+    pub placements: Vec<Option<BinPlacement<T>>>,
+    /// the sizes that do not fit even an empty bin
+    pub unplaced: Vec<usize>,
+}
+
+/// The area left unused by packing into several bins
+#[derive(Debug, PartialEq, Clone)]
+pub struct Waste<T> {
+    /// the unused area of each bin
+    pub per_bin: Vec<T>,
+    pub total: T,
+    /// the unused area divided by the area of all the bins
+    pub ratio: T,
+}
+
+impl<T> MultiBinPacked<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps,
+{
+    /// the indices of the sizes placed in the bin and where
+    pub fn bin_placements(&self, bin: usize) -> Vec<(usize, &AxisAlignedRectangle<T>)> {
+        self.placements
+            .iter()
+            .enumerate()
+            .filter_map(|(i, p)| p.as_ref().filter(|p| p.bin == bin).map(|p| (i, &p.rect)))
+            .collect()
+    }
+
+    pub fn waste(&self) -> Waste<T> {
+        let mut per_bin: Vec<T> = self.bins.iter().map(|b| b.width() * b.height()).collect();
+        let bins_area = per_bin.iter().fold(T::zero(), |sum, a| sum + *a);
+        for placement in self.placements.iter().flatten() {
+            per_bin[placement.bin] -= placement.rect.width() * placement.rect.height();
+        }
+        let total = per_bin.iter().fold(T::zero(), |sum, a| sum + *a);
+        let ratio = match bins_area == T::zero() {
+            true => T::zero(),
+            false => total / bins_area,
+        };
+        Waste {
+            per_bin,
+            total,
+            ratio,
+        }
+    }
+}
+
+/// pack the sizes into as many bins as needed, opening a new bin when the items left do not fit the open ones.
+/// the containers are opened in order, skipping those that fit none of the items left;
+/// the last container is repeated once they run out
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip_all, fields(sizes = sizes.len()))
+)]
+pub fn pack_bins<T, P>(
+    packer: &P,
+    containers: &[AxisAlignedRectangle<T>],
+    sizes: &[Rectangle<T>],
+) -> MultiBinPacked<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps,
+    P: Packer<T> + ?Sized,
+{
+    let mut bins: Vec<AxisAlignedRectangle<T>> = Vec::new();
+    let mut placements: Vec<Option<BinPlacement<T>>> = vec![None; sizes.len()];
+    let mut remaining: Vec<usize> = (0..sizes.len()).collect();
+    let mut next = 0;
+    while !remaining.is_empty() {
+        let Some(container) = containers.get(next).or(containers.last()) else {
+            break;
+        };
+        let is_last = next + 1 >= containers.len();
+        next += 1;
+        let remaining_sizes: Vec<Rectangle<T>> = remaining.iter().map(|i| sizes[*i]).collect();
+        let packed = packer.pack(container, &remaining_sizes);
+        if packed.unplaced.len() == remaining.len() {
+            // nothing left fits an empty bin of this container, but a later one may be larger
+            match is_last {
+                true => break,
+                false => continue,
+            }
+        }
+        let bin = bins.len();
+        bins.push(container.clone());
+        for (i, placement) in remaining.iter().zip(packed.placements) {
+            placements[*i] = placement.map(|rect| BinPlacement { bin, rect });
+        }
+        remaining = packed.unplaced.iter().map(|n| remaining[*n]).collect();
+    }
+    #[cfg(feature = "tracing")]
+    tracing::debug!(
+        bins = bins.len(),
+        unplaced = remaining.len(),
+        "packed into bins"
+    );
+    MultiBinPacked {
+        bins,
+        placements,
+        unplaced: remaining,
+    }
+}
+
+fn fits<T>(free: &AxisAlignedRectangle<T>, size: &Rectangle<T>) -> bool
+where
+    T: Copy + Num + NumAssignOps + NumOps + PartialOrd,
+{
+    size.width() <= free.width() && size.height() <= free.height()
+}
+
+/// the index of the free rectangle with the least area left over after placing the size
+fn best_area_fit<T>(free: &[AxisAlignedRectangle<T>], size: &Rectangle<T>) -> Option<usize>
+where
+    T: Copy + Num + NumAssignOps + NumOps + PartialOrd,
+{
+    let mut best: Option<(usize, T)> = None;
+    for (i, f) in free.iter().enumerate() {
+        if !fits(f, size) {
+            continue;
+        }
+        let left_over = f.width() * f.height() - size.width() * size.height();
+        if best.is_none_or(|(_, b)| left_over < b) {
+            best = Some((i, left_over));
+        }
+    }
+    best.map(|(i, _)| i)
+}
+
+/// cut the free rectangle after placing the size at its origin, into the space right of and below the item.
+/// the cut runs along the shorter leftover axis, so that the larger free rectangle stays as large as possible
+fn guillotine_split<T>(
+    free: &AxisAlignedRectangle<T>,
+    size: &Rectangle<T>,
+) -> (
+    Option<AxisAlignedRectangle<T>>,
+    Option<AxisAlignedRectangle<T>>,
+)
+where
+    T: Copy + Num + NumAssignOps + NumOps + PartialOrd,
+{
+    let left_over_width = free.width() - size.width();
+    let left_over_height = free.height() - size.height();
+    let (right_height, bottom_width) = match left_over_width < left_over_height {
+        // cut horizontally: the space below spans the whole width
+        true => (size.height(), free.width()),
+        // cut vertically: the space right spans the whole height
+        false => (free.height(), size.width()),
+    };
+    let right = AxisAlignedRectangle::new(
+        &Point::new(free.x() + size.width(), free.y()),
+        &Rectangle::new(left_over_width, right_height),
+    );
+    let bottom = AxisAlignedRectangle::new(
+        &Point::new(free.x(), free.y() + size.height()),
+        &Rectangle::new(bottom_width, left_over_height),
+    );
+    (non_empty(right), non_empty(bottom))
+}
+
+fn non_empty<T>(rect: AxisAlignedRectangle<T>) -> Option<AxisAlignedRectangle<T>>
+where
+    T: Copy + Num + NumAssignOps + NumOps + PartialOrd,
+{
+    match rect.width() > T::zero() && rect.height() > T::zero() {
+        true => Some(rect),
+        false => None,
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// every placement is inside the container and no two placements overlap
+    pub(crate) fn assert_valid_packing(
+        container: &AxisAlignedRectangle<i32>,
+        packed: &Packed<i32>,
+    ) {
+        let placed: Vec<&AxisAlignedRectangle<i32>> = packed.placements.iter().flatten().collect();
+        for (i, a) in placed.iter().enumerate() {
+            assert!(a.x() >= container.x() && a.y() >= container.y());
+            assert!(a.x() + a.width() <= container.x() + container.width());
+            assert!(a.y() + a.height() <= container.y() + container.height());
+            for b in placed.iter().skip(i + 1) {
+                let overlaps = a.x() < b.x() + b.width()
+                    && b.x() < a.x() + a.width()
+                    && a.y() < b.y() + b.height()
+                    && b.y() < a.y() + a.height();
+                assert!(!overlaps, "{:?} overlaps {:?}", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn test_guillotine_pack() {
+        let container = AxisAlignedRectangle::new(&Point::new(0, 0), &Rectangle::new(4, 4));
+        let sizes = vec![Rectangle::new(2, 2); 4];
+        let packed = GuillotinePacker.pack(&container, &sizes);
+        assert!(packed.is_complete());
+        assert_valid_packing(&container, &packed);
+        assert_eq!(
+            packed.placements[0],
+            Some(AxisAlignedRectangle::new(
+                &Point::new(0, 0),
+                &Rectangle::new(2, 2)
+            ))
+        );
+    }
+
+    #[test]
+    fn test_guillotine_pack_unplaced() {
+        let container = AxisAlignedRectangle::new(&Point::new(10, 10), &Rectangle::new(5, 3));
+        let sizes = vec![
+            Rectangle::new(3, 3),
+            Rectangle::new(6, 1),
+            Rectangle::new(2, 2),
+            Rectangle::new(2, 2),
+            Rectangle::new(2, 1),
+        ];
+        let packed = GuillotinePacker.pack(&container, &sizes);
+        assert_eq!(packed.unplaced, vec![1, 3]);
+        assert_eq!(packed.placements[1], None);
+        assert!(!packed.is_complete());
+        assert_valid_packing(&container, &packed);
+    }
+
+    #[test]
+    fn test_shelf_pack() {
+        let container = AxisAlignedRectangle::new(&Point::new(0, 0), &Rectangle::new(10, 6));
+        let sizes = vec![
+            Rectangle::new(6, 3),
+            Rectangle::new(6, 2),
+            Rectangle::new(4, 2),
+            Rectangle::new(4, 4),
+        ];
+        let next_fit = ShelfPacker {
+            fit: ShelfFit::NextFit,
+        }
+        .pack(&container, &sizes);
+        assert_valid_packing(&container, &next_fit);
+        // the third item does not go back to the first shelf
+        assert_eq!(
+            next_fit.placements[2],
+            Some(AxisAlignedRectangle::new(
+                &Point::new(6, 3),
+                &Rectangle::new(4, 2)
+            ))
+        );
+        assert_eq!(next_fit.unplaced, vec![3]);
+
+        let first_fit = ShelfPacker {
+            fit: ShelfFit::FirstFit,
+        }
+        .pack(&container, &sizes);
+        assert_valid_packing(&container, &first_fit);
+        assert_eq!(
+            first_fit.placements[2],
+            Some(AxisAlignedRectangle::new(
+                &Point::new(6, 0),
+                &Rectangle::new(4, 2)
+            ))
+        );
+        assert_eq!(first_fit.unplaced, vec![3]);
+    }
+
+    #[test]
+    fn test_skyline_pack() {
+        let container = AxisAlignedRectangle::new(&Point::new(0, 0), &Rectangle::new(10, 6));
+        let sizes = vec![
+            Rectangle::new(6, 3),
+            Rectangle::new(4, 2),
+            Rectangle::new(4, 2),
+            Rectangle::new(6, 3),
+            Rectangle::new(1, 1),
+        ];
+        let packed = SkylinePacker.pack(&container, &sizes);
+        assert_valid_packing(&container, &packed);
+        assert_eq!(
+            packed.placements[2],
+            Some(AxisAlignedRectangle::new(
+                &Point::new(6, 2),
+                &Rectangle::new(4, 2)
+            ))
+        );
+        assert_eq!(
+            packed.placements[3],
+            Some(AxisAlignedRectangle::new(
+                &Point::new(0, 3),
+                &Rectangle::new(6, 3)
+            ))
+        );
+        assert!(packed.is_complete());
+    }
+
+    #[test]
+    fn test_packers_are_interchangeable() {
+        let container = AxisAlignedRectangle::new(&Point::new(0, 0), &Rectangle::new(8, 8));
+        let sizes = [
+            Rectangle::new(4, 2),
+            Rectangle::new(2, 2),
+            Rectangle::new(2, 2),
+        ]
+        .repeat(3);
+        let packers: Vec<Box<dyn Packer<i32>>> = vec![
+            Box::new(GuillotinePacker),
+            Box::new(ShelfPacker::default()),
+            Box::new(ShelfPacker {
+                fit: ShelfFit::FirstFit,
+            }),
+            Box::new(SkylinePacker),
+        ];
+        for packer in packers {
+            let packed = packer.pack(&container, &sizes);
+            assert!(packed.is_complete());
+            assert_valid_packing(&container, &packed);
+        }
+    }
+
+    #[test]
+    fn test_pack_bins() {
+        let page = AxisAlignedRectangle::new(&Point::new(0, 0), &Rectangle::new(4, 4));
+        let sizes = vec![
+            Rectangle::new(4, 2),
+            Rectangle::new(4, 4),
+            Rectangle::new(4, 2),
+            Rectangle::new(5, 1),
+            Rectangle::new(2, 2),
+        ];
+        let packed = pack_bins(&ShelfPacker::default(), std::slice::from_ref(&page), &sizes);
+        assert_eq!(packed.bins, vec![page.clone(), page.clone(), page]);
+        assert_eq!(packed.unplaced, vec![3]);
+        assert_eq!(packed.placements[1].as_ref().map(|p| p.bin), Some(1));
+        let first: Vec<usize> = packed.bin_placements(0).iter().map(|(i, _)| *i).collect();
+        assert_eq!(first, vec![0, 2]);
+        let second: Vec<usize> = packed.bin_placements(1).iter().map(|(i, _)| *i).collect();
+        assert_eq!(second, vec![1]);
+        // the 2x2 item fits neither full bin and opens a third
+        assert_eq!(packed.placements[4].as_ref().map(|p| p.bin), Some(2));
+
+        let waste = packed.waste();
+        assert_eq!(waste.per_bin, vec![0, 0, 12]);
+        assert_eq!(waste.total, 12);
+    }
+
+    #[test]
+    fn test_pack_bins_of_containers() {
+        let small = AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(2.0, 2.0));
+        let large = AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(4.0, 4.0));
+        let sizes = vec![Rectangle::new(2.0, 2.0); 6];
+        let packed = pack_bins(&GuillotinePacker, &[small.clone(), large.clone()], &sizes);
+        assert_eq!(packed.bins, vec![small, large.clone(), large.clone()]);
+        assert!(packed.unplaced.is_empty());
+        let waste = packed.waste();
+        assert_eq!(waste.per_bin, vec![0.0, 0.0, 12.0]);
+        assert_eq!(waste.ratio, 12.0 / 36.0);
+
+        // a container too small for every item is skipped for the larger one after it
+        let tiny = AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(1.0, 1.0));
+        let packed = pack_bins(&GuillotinePacker, &[tiny, large.clone()], &sizes[..3]);
+        assert_eq!(packed.bins, vec![large]);
+        assert!(packed.unplaced.is_empty());
+
+        let empty = pack_bins(&GuillotinePacker, &[], &sizes);
+        assert!(empty.bins.is_empty());
+        assert_eq!(empty.unplaced.len(), 6);
+        assert_eq!(empty.waste().ratio, 0.0);
+    }
+}
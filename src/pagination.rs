@@ -0,0 +1,106 @@
+use num_traits::{Num, NumAssignOps, NumOps};
+
+use crate::area::Area;
+use crate::axis_aligned_rectangle::AxisAlignedRectangle;
+use crate::layout::Layout;
+
+/// How full a page may get. a page takes items while both limits hold
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct PageLimits<T> {
+    /// the most cells on a page
+    pub max_cells: Option<usize>,
+    /// the smallest area a cell on a page may have
+    pub min_cell_area: Option<T>,
+}
+
+/// Weights spilled across pages of the same size
+#[derive(Debug, PartialEq, Clone)]
+pub struct Pages<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps,
+{
+    pub pages: Vec<Layout<T>>,
+    /// `assignment[n]` is the page of the n-th weight and the index of its cell on that page
+    pub assignment: Vec<(usize, usize)>,
+}
+
+impl<T> AxisAlignedRectangle<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps + PartialOrd,
+{
+    /// dividing a long list of weights across pages the size of this rectangle, e.g. for reports.
+    /// the weights are taken in order: a page takes the next weight as long as the page, divided by `layout`,
+    /// stays within the limits, and a new page is started otherwise. a page always takes at least one weight,
+    /// even if its cell is smaller than the limit
+    pub fn paginate<L>(&self, weights: &[T], limits: &PageLimits<T>, layout: L) -> Pages<T>
+    where
+        L: Fn(&Self, &[T]) -> Vec<Self>,
+    {
+        let fits = |cells: &[Self]| {
+            let count_fits = limits.max_cells.is_none_or(|max| cells.len() <= max);
+            let area_fits = limits
+                .min_cell_area
+                .is_none_or(|min| cells.iter().all(|cell| cell.area() >= min));
+            count_fits && area_fits
+        };
+        let mut pages = Vec::new();
+        let mut assignment = Vec::with_capacity(weights.len());
+        let mut start = 0;
+        while start < weights.len() {
+            let mut cells = layout(self, &weights[start..start + 1]);
+            let mut end = start + 1;
+            while end < weights.len() {
+                let candidate = layout(self, &weights[start..end + 1]);
+                if !fits(&candidate) {
+                    break;
+                }
+                cells = candidate;
+                end += 1;
+            }
+            assignment.extend((0..end - start).map(|i| (pages.len(), i)));
+            pages.push(Layout::new(cells));
+            start = end;
+        }
+        Pages { pages, assignment }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dividing::Dividing;
+    use crate::point::Point;
+    use crate::rectangle::Rectangle;
+
+    #[test]
+    fn test_paginate() {
+        let page = AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(100.0, 100.0));
+        let layout = |r: &AxisAlignedRectangle<f64>, w: &[f64]| {
+            r.divide_vertical_then_horizontal_with_weights(w, 1.0, false)
+        };
+        let weights = [1.0; 10];
+        let limits = PageLimits {
+            max_cells: Some(4),
+            min_cell_area: None,
+        };
+        let pages = page.paginate(&weights, &limits, layout);
+        let sizes: Vec<usize> = pages.pages.iter().map(|p| p.len()).collect();
+        assert_eq!(sizes, vec![4, 4, 2]);
+        assert_eq!(pages.assignment[5], (1, 1));
+        assert_eq!(pages.assignment[9], (2, 1));
+
+        // taking the 3 would leave the 1s 1/6 of the page, and the 1 after it 1/4, both under 3000
+        let weights = [1.0, 1.0, 1.0, 3.0, 1.0];
+        let limits = PageLimits {
+            max_cells: None,
+            min_cell_area: Some(3000.0),
+        };
+        let pages = page.paginate(&weights, &limits, layout);
+        let sizes: Vec<usize> = pages.pages.iter().map(|p| p.len()).collect();
+        assert_eq!(sizes, vec![3, 1, 1]);
+        assert_eq!(
+            pages.assignment,
+            vec![(0, 0), (0, 1), (0, 2), (1, 0), (2, 0)]
+        );
+    }
+}
@@ -0,0 +1,44 @@
+/// How `Dividing::divide_pivot` picks the pivot of a list of weights
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PivotStrategy {
+    /// the middle item, which keeps the recursion balanced in the number of items
+    #[default]
+    Middle,
+    /// the largest item (the first of them on ties), which tends to give it the squarest cell
+    Size,
+}
+
+impl PivotStrategy {
+    /// the index of the pivot of the weights. `None` if there are no weights
+    pub fn pivot<T>(&self, weights: &[T]) -> Option<usize>
+    where
+        T: PartialOrd,
+    {
+        if weights.is_empty() {
+            return None;
+        }
+        match self {
+            PivotStrategy::Middle => Some(weights.len() / 2),
+            PivotStrategy::Size => {
+                (0..weights.len()).reduce(|largest, i| match weights[i] > weights[largest] {
+                    true => i,
+                    false => largest,
+                })
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pivot() {
+        let weights = [1, 4, 2, 4, 3];
+        assert_eq!(PivotStrategy::Middle.pivot(&weights), Some(2));
+        assert_eq!(PivotStrategy::Size.pivot(&weights), Some(1));
+        assert_eq!(PivotStrategy::Middle.pivot(&[7]), Some(0));
+        assert_eq!(PivotStrategy::Size.pivot::<i32>(&[]), None);
+    }
+}
@@ -0,0 +1,141 @@
+use num_traits::{NumAssignOps, PrimInt};
+
+use crate::axis_aligned_rectangle::AxisAlignedRectangle;
+use crate::component::Component;
+use crate::point::Point;
+use crate::rectangle::RectangleSize;
+
+/// Iterator over the integer points of a range of x and y, row by row
+#[derive(Debug, Clone)]
+pub struct Pixels<T> {
+    x_start: T,
+    x_end: T,
+    y_end: T,
+    x: T,
+    y: T,
+}
+
+impl<T> Pixels<T>
+where
+    T: PrimInt + NumAssignOps,
+{
+    /// the points with x in [x_start, x_end) and y in [y_start, y_end)
+    fn new(x_start: T, x_end: T, y_start: T, y_end: T) -> Self {
+        // an empty row range ends the iteration at once
+        let y = match x_start < x_end {
+            true => y_start,
+            false => y_end,
+        };
+        Self {
+            x_start,
+            x_end,
+            y_end,
+            x: x_start,
+            y,
+        }
+    }
+}
+
+impl<T> Iterator for Pixels<T>
+where
+    T: PrimInt + NumAssignOps,
+{
+    type Item = Point<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.y >= self.y_end {
+            return None;
+        }
+        let point = Point::new(self.x, self.y);
+        self.x += T::one();
+        if self.x >= self.x_end {
+            self.x = self.x_start;
+            self.y += T::one();
+        }
+        Some(point)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let count = |from: T, to: T| match from < to {
+            true => (to - from).to_usize(),
+            false => Some(0),
+        };
+        let remaining = match (
+            count(self.y, self.y_end),
+            count(self.x_start, self.x_end),
+            count(self.x, self.x_end),
+        ) {
+            (Some(0), _, _) => Some(0),
+            (Some(rows), Some(width), Some(rest)) => (rows - 1)
+                .checked_mul(width)
+                .and_then(|n| n.checked_add(rest)),
+            _ => None,
+        };
+        (remaining.unwrap_or(usize::MAX), remaining)
+    }
+}
+
+impl<T> AxisAlignedRectangle<T>
+where
+    T: PrimInt + NumAssignOps,
+{
+    /// the pixels the rectangle covers, by their left top corners, row-major:
+    /// x in [x, x + width) and y in [y, y + height), so there are width * height of them
+    pub fn pixels(&self) -> Pixels<T> {
+        Pixels::new(
+            self.x(),
+            self.x() + self.width(),
+            self.y(),
+            self.y() + self.height(),
+        )
+    }
+
+    /// the integer points in the rectangle, row-major. with the boundary, x is in [x, x + width]
+    /// and y in [y, y + height]; without it, strictly inside
+    pub fn lattice_points(&self, include_boundary: bool) -> Pixels<T> {
+        let (right, bottom) = (self.x() + self.width(), self.y() + self.height());
+        match include_boundary {
+            true => Pixels::new(self.x(), right + T::one(), self.y(), bottom + T::one()),
+            false => Pixels::new(self.x() + T::one(), right, self.y() + T::one(), bottom),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rectangle::Rectangle;
+
+    fn points(pixels: Pixels<i32>) -> Vec<(i32, i32)> {
+        pixels.map(|p| (p.x(), p.y())).collect()
+    }
+
+    #[test]
+    fn test_pixels() {
+        let rect = AxisAlignedRectangle::new(&Point::new(-1, 2), &Rectangle::new(3, 2));
+        assert_eq!(
+            points(rect.pixels()),
+            vec![(-1, 2), (0, 2), (1, 2), (-1, 3), (0, 3), (1, 3)]
+        );
+        assert_eq!(rect.pixels().size_hint(), (6, Some(6)));
+        let mut pixels = rect.pixels();
+        pixels.next();
+        assert_eq!(pixels.size_hint(), (5, Some(5)));
+        let empty = AxisAlignedRectangle::new(&Point::new(0, 0), &Rectangle::new(0, 5));
+        assert_eq!(empty.pixels().count(), 0);
+        assert_eq!(empty.pixels().size_hint(), (0, Some(0)));
+    }
+
+    #[test]
+    fn test_lattice_points() {
+        let rect = AxisAlignedRectangle::new(&Point::new(0u8, 0u8), &Rectangle::new(2u8, 1u8));
+        assert_eq!(rect.lattice_points(true).count(), 6);
+        assert_eq!(rect.lattice_points(false).count(), 0);
+        let rect = AxisAlignedRectangle::new(&Point::new(0, 0), &Rectangle::new(3, 3));
+        assert_eq!(
+            points(rect.lattice_points(false)),
+            vec![(1, 1), (2, 1), (1, 2), (2, 2)]
+        );
+        assert_eq!(rect.lattice_points(true).count(), 16);
+    }
+}
@@ -2,10 +2,11 @@ use num_traits::{Float, Num, NumAssignOps, NumOps};
 
 use crate::axis::{Axis, ValueForAxis};
 use crate::component::Component;
+use crate::coordinate_system::CoordinateSystem;
 use crate::rotate::QuarterRotation;
 use crate::vector::Vector;
 /// A point in 2D space
-#[derive(Debug, PartialEq, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
 pub struct Point<T>
 where
     T: Copy + Num + NumAssignOps + NumOps,
@@ -14,7 +15,7 @@ where
     y: T,
 }
 
-#[derive(Debug, PartialEq, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
 pub enum Edge {
     LeftTop,
     RightTop,
@@ -22,10 +23,35 @@ pub enum Edge {
     RightBottom,
 }
 
+impl Edge {
+    /// the edge on the other side vertically (left top <-> left bottom)
+    pub fn flip_vertical(&self) -> Self {
+        match self {
+            Edge::LeftTop => Edge::LeftBottom,
+            Edge::RightTop => Edge::RightBottom,
+            Edge::LeftBottom => Edge::LeftTop,
+            Edge::RightBottom => Edge::RightTop,
+        }
+    }
+
+    /// the edge expressed in screen coordinates (y grows downward)
+    pub fn to_screen(&self, coordinate_system: CoordinateSystem) -> Self {
+        match coordinate_system {
+            CoordinateSystem::Screen => *self,
+            CoordinateSystem::Math => self.flip_vertical(),
+        }
+    }
+}
+
 impl<T> Point<T>
 where
     T: Copy + Num + NumAssignOps + NumOps + Float,
 {
+    /// round toward the edge, where the edge is named in the coordinate system
+    pub fn round_in(&self, edge: Edge, coordinate_system: CoordinateSystem) -> Self {
+        self.round(edge.to_screen(coordinate_system))
+    }
+
     pub fn round(&self, edge: Edge) -> Self {
         match edge {
             Edge::LeftTop => Self {
@@ -83,6 +109,17 @@ where
     }
 }
 
+impl<T> Point<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps + Ord,
+{
+    /// compare by y, then by x: top to bottom, then left to right (in screen coordinates),
+    /// e.g. for `sort_by(Point::cmp_reading_order)`
+    pub fn cmp_reading_order(a: &Self, b: &Self) -> std::cmp::Ordering {
+        a.y.cmp(&b.y).then(a.x.cmp(&b.x))
+    }
+}
+
 /// A point in 2D space with default values. in many cases, this is (0, 0)
 impl<T> std::default::Default for Point<T>
 where
@@ -93,6 +130,19 @@ where
     }
 }
 
+/// `x,y`, e.g. `1.5,2`. the precision (`{:.1}`) applies to both components
+impl<T> std::fmt::Display for Point<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps + std::fmt::Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match f.precision() {
+            Some(precision) => write!(f, "{:.*},{:.*}", precision, self.x, precision, self.y),
+            None => write!(f, "{},{}", self.x, self.y),
+        }
+    }
+}
+
 /// Vector from point A to point B
 impl<T> std::ops::Sub<Point<T>> for Point<T>
 where
@@ -135,6 +185,38 @@ mod tests {
         assert_point_eq(&result, &Point::new(0, 0));
     }
 
+    #[test]
+    fn test_round_in() {
+        let p = Point::new(1.5, 2.5);
+        assert_eq!(p.round(Edge::LeftTop), Point::new(1.0, 2.0));
+        assert_eq!(
+            p.round_in(Edge::LeftTop, CoordinateSystem::Screen),
+            Point::new(1.0, 2.0)
+        );
+        assert_eq!(
+            p.round_in(Edge::LeftTop, CoordinateSystem::Math),
+            Point::new(1.0, 3.0)
+        );
+        assert_eq!(
+            p.round_in(Edge::RightBottom, CoordinateSystem::Math),
+            Point::new(2.0, 2.0)
+        );
+    }
+
+    #[test]
+    fn test_edge_flip_vertical() {
+        for edge in [
+            Edge::LeftTop,
+            Edge::RightTop,
+            Edge::LeftBottom,
+            Edge::RightBottom,
+        ] {
+            assert_ne!(edge.flip_vertical(), edge);
+            assert_eq!(edge.flip_vertical().flip_vertical(), edge);
+            assert_eq!(edge.to_screen(CoordinateSystem::Screen), edge);
+        }
+    }
+
     #[test]
     fn test_value_for_axis() {
         let result = Point::new(2, 3);
@@ -151,6 +233,24 @@ mod tests {
         assert_eq!(result, Vector::new(1, 1));
     }
 
+    #[test]
+    fn test_cmp_reading_order() {
+        let mut points = vec![Point::new(1, 1), Point::new(2, 0), Point::new(0, 1)];
+        points.sort_by(Point::cmp_reading_order);
+        assert_eq!(
+            points,
+            vec![Point::new(2, 0), Point::new(0, 1), Point::new(1, 1)]
+        );
+        let distinct: std::collections::HashSet<Point<i32>> = points.into_iter().collect();
+        assert_eq!(distinct.len(), 3);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Point::new(2, -3).to_string(), "2,-3");
+        assert_eq!(format!("{:.1}", Point::new(1.0, 2.25)), "1.0,2.2");
+    }
+
     #[test]
     fn test_rotate() {
         let result = Point::new(2, 3).rotate_clockwise();
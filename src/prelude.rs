@@ -0,0 +1,21 @@
+//! The traits needed to call the methods of the divided rectangles, and the commonly used types.
+//!
+//! ```
+//! use rust_rectangle_dividing::prelude::*;
+//!
+//! let rect = AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(4.0, 2.0));
+//! let divided = rect.divide_by_weights_and_axis(&[1.0, 1.0], Axis::Vertical);
+//! assert_eq!(divided[1].x(), 2.0);
+//! assert_eq!(divided[1].aspect_ratio(), 1.0);
+//! assert_eq!(divided[1].area(), 4.0);
+//! ```
+pub use crate::area::Area;
+pub use crate::aspect_ratio::AspectRatio;
+pub use crate::axis::{Axis, SizeForAxis, ValueForAxis};
+pub use crate::axis_aligned_rectangle::AxisAlignedRectangle;
+pub use crate::component::Component;
+pub use crate::dividing::Dividing;
+pub use crate::emission_order::EmissionOrder;
+pub use crate::layout::Layout;
+pub use crate::point::Point;
+pub use crate::rectangle::{Rectangle, RectangleSize};
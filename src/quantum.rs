@@ -0,0 +1,126 @@
+use num_traits::{Num, NumAssignOps, NumCast, NumOps};
+
+use crate::axis_aligned_rectangle::AxisAlignedRectangle;
+use crate::component::Component;
+use crate::packing::Packed;
+use crate::point::Point;
+use crate::rectangle::{Rectangle, RectangleSize};
+
+impl<T> AxisAlignedRectangle<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps + PartialOrd + NumCast,
+{
+    /// dividing a rectangle into cells of whole quanta (e.g. thumbnails of a fixed size), a quantum treemap:
+    /// the n-th cell holds at least `counts[n]` quanta (one for a count of zero) in a block of whole columns and rows
+    /// as square as it gets, and the blocks are placed in order into shelves on the grid of quanta from the origin.
+    /// every side is a multiple of the quantum, and the cells never overlap nor leave the rectangle: the n-th placement
+    /// is `None` (and n is in `unplaced`) when its block does not fit in what is left below the current shelf
+    pub fn divide_quantum(&self, counts: &[usize], quantum: Rectangle<T>) -> Packed<T> {
+        let (quantum_width, quantum_height) = (quantum.width(), quantum.height());
+        let fitting = |size: T, quantum: T| match quantum > T::zero() {
+            true => (size / quantum).to_usize().unwrap_or(0),
+            false => 0,
+        };
+        let (cols, rows) = (
+            fitting(self.width(), quantum_width),
+            fitting(self.height(), quantum_height),
+        );
+        // columns per row of a square block, for quanta that are not square
+        let shape = match (quantum_height.to_f64(), quantum_width.to_f64()) {
+            (Some(height), Some(width)) => height / width,
+            _ => 1.0,
+        };
+        let block =
+            |col: usize, row: usize, block_cols: usize, block_rows: usize| -> Option<Self> {
+                Some(Self::new(
+                    &Point::new(
+                        self.x() + T::from(col)? * quantum_width,
+                        self.y() + T::from(row)? * quantum_height,
+                    ),
+                    &Rectangle::new(
+                        T::from(block_cols)? * quantum_width,
+                        T::from(block_rows)? * quantum_height,
+                    ),
+                ))
+            };
+        let mut placements = Vec::with_capacity(counts.len());
+        let (mut col, mut row, mut shelf_rows) = (0, 0, 0);
+        for count in counts {
+            let count = (*count).max(1);
+            let block_cols = ((count as f64 * shape).sqrt().ceil() as usize).clamp(1, cols.max(1));
+            let block_rows = count.div_ceil(block_cols);
+            let (at_col, at_row, at_shelf_rows) = match col + block_cols > cols {
+                true => (0, row + shelf_rows, 0),
+                false => (col, row, shelf_rows),
+            };
+            let placed = match block_cols <= cols && at_row + block_rows <= rows {
+                true => block(at_col, at_row, block_cols, block_rows),
+                false => None,
+            };
+            if placed.is_some() {
+                (col, row, shelf_rows) =
+                    (at_col + block_cols, at_row, at_shelf_rows.max(block_rows));
+            }
+            placements.push(placed);
+        }
+        Packed::from_placements(placements)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::assert_no_overlaps;
+
+    fn rect(x: i32, y: i32, width: i32, height: i32) -> AxisAlignedRectangle<i32> {
+        AxisAlignedRectangle::new(&Point::new(x, y), &Rectangle::new(width, height))
+    }
+
+    #[test]
+    fn test_divide_quantum() {
+        let container = rect(0, 0, 100, 60);
+        let divided = container.divide_quantum(&[4, 3, 1, 9, 0, 16, 2], Rectangle::new(20, 10));
+        assert_eq!(
+            divided.placements,
+            vec![
+                // 4 quanta of 20x10 in 2 columns of 2 rows, 3 in 2 columns of 2 rows
+                Some(rect(0, 0, 40, 20)),
+                Some(rect(40, 0, 40, 20)),
+                Some(rect(80, 0, 20, 10)),
+                // 9 in 3 columns of 3 rows, on the next shelf
+                Some(rect(0, 20, 60, 30)),
+                Some(rect(60, 20, 20, 10)),
+                // 16 in 3 columns of 6 rows would go below the container
+                None,
+                // 2 still fit on the shelf
+                Some(rect(80, 20, 20, 20)),
+            ]
+        );
+        assert_eq!(divided.unplaced, vec![5]);
+        for (cell, count) in divided.placements.iter().flatten().zip([4, 3, 1, 9, 1, 2]) {
+            assert_eq!(cell.width() % 20, 0);
+            assert_eq!(cell.height() % 10, 0);
+            assert!((cell.width() / 20 * cell.height() / 10) as usize >= count);
+        }
+        let no_quantum = container.divide_quantum(&[1], Rectangle::new(0, 10));
+        assert_eq!(no_quantum.unplaced, vec![0]);
+        let too_narrow = rect(0, 0, 10, 60).divide_quantum(&[1, 2], Rectangle::new(20, 10));
+        assert_eq!(too_narrow.unplaced, vec![0, 1]);
+    }
+
+    #[test]
+    fn test_divide_quantum_no_overlaps() {
+        let container =
+            AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(640.0, 480.0));
+        let counts: Vec<usize> = (0..40).map(|i| (i * 7) % 23).collect();
+        let divided = container.divide_quantum(&counts, Rectangle::new(32.0, 24.0));
+        assert_eq!(divided.placements.len(), counts.len());
+        assert!(!divided.is_complete());
+        let cells: Vec<AxisAlignedRectangle<f64>> =
+            divided.placements.into_iter().flatten().collect();
+        for cell in &cells {
+            assert!(cell.x() + cell.width() <= 640.0 && cell.y() + cell.height() <= 480.0);
+        }
+        assert_no_overlaps(&container, &cells);
+    }
+}
@@ -0,0 +1,277 @@
+//! Dividing annuli into sectors, for sunburst-style output.
+//!
+//! A `Sector` is the part of an annulus between two radii and two angles (radians, increasing
+//! from the start angle). dividing follows the rectangle API: the n-th sector corresponds to the
+//! n-th weight and its area is proportional to it, along either the angle or the radius.
+use num_traits::{Float, FloatConst, Num, NumAssignOps, NumOps};
+
+use crate::point::Point;
+use crate::weight::normalize_weights;
+
+/// Along which a sector is divided
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RadialAxis {
+    /// cuts along rays from the center, so the parts share the radii and split the angle
+    Angular,
+    /// cuts along circles, so the parts share the angle and split the radii
+    Radial,
+}
+
+/// The part of an annulus between two radii and two angles
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Sector<T> {
+    inner_radius: T,
+    outer_radius: T,
+    start_angle: T,
+    end_angle: T,
+}
+
+impl<T> Sector<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps + Float + for<'a> std::iter::Sum<&'a T>,
+{
+    pub fn new(inner_radius: T, outer_radius: T, start_angle: T, end_angle: T) -> Self {
+        Self {
+            inner_radius,
+            outer_radius,
+            start_angle,
+            end_angle,
+        }
+    }
+
+    /// a whole annulus, from the angle 0 to 2π
+    pub fn annulus(inner_radius: T, outer_radius: T) -> Self
+    where
+        T: FloatConst,
+    {
+        let two = T::one() + T::one();
+        Self::new(inner_radius, outer_radius, T::zero(), two * T::PI())
+    }
+
+    pub fn inner_radius(&self) -> T {
+        self.inner_radius
+    }
+
+    pub fn outer_radius(&self) -> T {
+        self.outer_radius
+    }
+
+    pub fn start_angle(&self) -> T {
+        self.start_angle
+    }
+
+    pub fn end_angle(&self) -> T {
+        self.end_angle
+    }
+
+    pub fn angle(&self) -> T {
+        self.end_angle - self.start_angle
+    }
+
+    pub fn area(&self) -> T {
+        let two = T::one() + T::one();
+        self.angle() / two
+            * (self.outer_radius * self.outer_radius - self.inner_radius * self.inner_radius)
+    }
+
+    /// the point at the middle angle and the middle radius, e.g. to anchor a label.
+    /// the center is the origin and angles grow from the x axis toward the y axis
+    pub fn mid_point(&self) -> Point<T> {
+        let two = T::one() + T::one();
+        let angle = (self.start_angle + self.end_angle) / two;
+        let radius = (self.inner_radius + self.outer_radius) / two;
+        Point::new(radius * angle.cos(), radius * angle.sin())
+    }
+
+    /// dividing a sector into two: at the angle `v` from the start angle, or at the radius `v` from the inner radius
+    pub fn divide(&self, v: T, axis: RadialAxis) -> (Self, Self) {
+        match axis {
+            RadialAxis::Angular => {
+                let angle = self.start_angle + v;
+                (
+                    Self::new(
+                        self.inner_radius,
+                        self.outer_radius,
+                        self.start_angle,
+                        angle,
+                    ),
+                    Self::new(self.inner_radius, self.outer_radius, angle, self.end_angle),
+                )
+            }
+            RadialAxis::Radial => {
+                let radius = self.inner_radius + v;
+                (
+                    Self::new(self.inner_radius, radius, self.start_angle, self.end_angle),
+                    Self::new(radius, self.outer_radius, self.start_angle, self.end_angle),
+                )
+            }
+        }
+    }
+
+    /// dividing a sector into specified weights of sectors, each with the share of the area of its weight.
+    /// radially, the rings get thinner outward, since the same width covers more area there
+    pub fn divide_by_weights_and_axis(&self, weights: &[T], axis: RadialAxis) -> Vec<Self> {
+        if weights.is_empty() {
+            return vec![];
+        }
+        let inner_squared = self.inner_radius * self.inner_radius;
+        let ring_squared = self.outer_radius * self.outer_radius - inner_squared;
+        let mut divided = Vec::with_capacity(weights.len());
+        let mut remaining = *self;
+        let mut cumulative = T::zero();
+        let normalized_weights = normalize_weights(weights);
+        for w in &normalized_weights[..normalized_weights.len() - 1] {
+            cumulative += *w;
+            let v = match axis {
+                RadialAxis::Angular => self.start_angle + cumulative * self.angle(),
+                RadialAxis::Radial => (inner_squared + cumulative * ring_squared).sqrt(),
+            };
+            let offset = match axis {
+                RadialAxis::Angular => v - remaining.start_angle,
+                RadialAxis::Radial => v - remaining.inner_radius,
+            };
+            let (part, rest) = remaining.divide(offset, axis);
+            divided.push(part);
+            remaining = rest;
+        }
+        divided.push(remaining);
+        divided
+    }
+
+    /// dividing a sector into rings by depth and sectors by weight, like a sunburst: the root's children split the
+    /// first ring (of `ring_width`) by their weights, and each node's children split the part of the next ring under it.
+    /// the cells are in depth-first order
+    pub fn divide_hierarchy(&self, root: &RadialNode<T>, ring_width: T) -> Vec<RadialCell<T>> {
+        let mut cells = Vec::new();
+        self.divide_children(root, ring_width, &mut Vec::new(), &mut cells);
+        cells
+    }
+
+    fn divide_children(
+        &self,
+        node: &RadialNode<T>,
+        ring_width: T,
+        path: &mut Vec<usize>,
+        cells: &mut Vec<RadialCell<T>>,
+    ) {
+        if node.children.is_empty() {
+            return;
+        }
+        let ring = Self::new(
+            self.inner_radius,
+            self.inner_radius + ring_width,
+            self.start_angle,
+            self.end_angle,
+        );
+        let weights: Vec<T> = node.children.iter().map(|c| c.weight).collect();
+        let sectors = ring.divide_by_weights_and_axis(&weights, RadialAxis::Angular);
+        for (i, (child, sector)) in node.children.iter().zip(sectors).enumerate() {
+            path.push(i);
+            cells.push(RadialCell {
+                path: path.clone(),
+                sector,
+            });
+            let next = Self::new(
+                sector.outer_radius,
+                self.outer_radius,
+                sector.start_angle,
+                sector.end_angle,
+            );
+            next.divide_children(child, ring_width, path, cells);
+            path.pop();
+        }
+    }
+}
+
+/// A weighted tree for `Sector::divide_hierarchy`. the weight of an inner node is its share among its siblings
+#[derive(Debug, PartialEq, Clone)]
+pub struct RadialNode<T> {
+    pub weight: T,
+    pub children: Vec<RadialNode<T>>,
+}
+
+impl<T> RadialNode<T> {
+    pub fn leaf(weight: T) -> Self {
+        Self {
+            weight,
+            children: Vec::new(),
+        }
+    }
+
+    pub fn new(weight: T, children: Vec<RadialNode<T>>) -> Self {
+        Self { weight, children }
+    }
+}
+
+/// A sector of a divided hierarchy, with the path of child indices from the root to its node.
+/// the depth (and the ring) is the length of the path minus one
+#[derive(Debug, PartialEq, Clone)]
+pub struct RadialCell<T> {
+    pub path: Vec<usize>,
+    pub sector: Sector<T>,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use super::*;
+    use crate::component::Component;
+
+    #[test]
+    fn test_divide_angular() {
+        let annulus = Sector::annulus(1.0, 2.0);
+        let divided = annulus.divide_by_weights_and_axis(&[1.0, 2.0, 1.0], RadialAxis::Angular);
+        assert_eq!(divided.len(), 3);
+        assert!((divided[0].end_angle() - PI / 2.0).abs() < 1e-12);
+        assert!((divided[1].angle() - PI).abs() < 1e-12);
+        assert_eq!(divided[2].end_angle(), 2.0 * PI);
+        let total: f64 = divided.iter().map(|s| s.area()).sum();
+        assert!((total - annulus.area()).abs() < 1e-12);
+        assert!((divided[1].area() / annulus.area() - 0.5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_divide_radial() {
+        let disc = Sector::annulus(0.0, 2.0);
+        let divided = disc.divide_by_weights_and_axis(&[1.0, 1.0, 2.0], RadialAxis::Radial);
+        for (sector, share) in divided.iter().zip([0.25, 0.25, 0.5]) {
+            assert!((sector.area() / disc.area() - share).abs() < 1e-12);
+            assert_eq!(sector.angle(), disc.angle());
+        }
+        assert!((divided[0].outer_radius() - 1.0).abs() < 1e-12);
+        assert_eq!(divided[2].outer_radius(), 2.0);
+    }
+
+    #[test]
+    fn test_mid_point() {
+        let sector = Sector::new(1.0, 3.0, 0.0, PI / 2.0);
+        let mid = sector.mid_point();
+        assert!((mid.x() - 2.0 * (PI / 4.0).cos()).abs() < 1e-12);
+        assert!((mid.y() - 2.0 * (PI / 4.0).sin()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_divide_hierarchy() {
+        let root = RadialNode::new(
+            1.0,
+            vec![
+                RadialNode::new(3.0, vec![RadialNode::leaf(1.0), RadialNode::leaf(2.0)]),
+                RadialNode::leaf(1.0),
+            ],
+        );
+        let cells = Sector::annulus(1.0, 10.0).divide_hierarchy(&root, 1.0);
+        let paths: Vec<Vec<usize>> = cells.iter().map(|c| c.path.clone()).collect();
+        assert_eq!(paths, vec![vec![0], vec![0, 0], vec![0, 1], vec![1]]);
+        // the first ring
+        assert_eq!(cells[0].sector.inner_radius(), 1.0);
+        assert_eq!(cells[0].sector.outer_radius(), 2.0);
+        assert!((cells[0].sector.angle() - 1.5 * PI).abs() < 1e-12);
+        // the second ring, under the first child
+        assert_eq!(cells[1].sector.inner_radius(), 2.0);
+        assert_eq!(cells[1].sector.outer_radius(), 3.0);
+        assert!((cells[1].sector.angle() - 0.5 * PI).abs() < 1e-12);
+        assert_eq!(cells[2].sector.end_angle(), cells[0].sector.end_angle());
+        assert_eq!(cells[3].sector.start_angle(), cells[0].sector.end_angle());
+    }
+}
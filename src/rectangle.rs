@@ -1,12 +1,15 @@
 use crate::area::Area;
 use crate::aspect_ratio::AspectRatio;
 use crate::axis::{Axis, SizeForAxis};
+use crate::axis_aligned_rectangle::AxisAlignedRectangle;
+use crate::component::Component;
 use crate::dividing::VerticalDividingHelper;
-use crate::rotate::QuarterRotation;
+use crate::point::Point;
+use crate::rotate::{QuarterRotation, Transpose};
 use num_traits::{Float, Num, NumAssignOps, NumOps};
 /// rectangle in 2D space with a width and height
 
-#[derive(Debug, PartialEq, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
 pub struct Rectangle<T>
 where
     T: Copy + Num + NumAssignOps + NumOps,
@@ -78,6 +81,155 @@ where
     pub fn new(width: T, height: T) -> Self {
         Self { width, height }
     }
+
+    /// 0×0, e.g. the size of nothing placed yet
+    pub fn zero() -> Self {
+        Self::new(T::zero(), T::zero())
+    }
+
+    /// 1×1
+    pub fn unit() -> Self {
+        Self::new(T::one(), T::one())
+    }
+
+    pub fn set_width(&mut self, width: T) {
+        self.width = width;
+    }
+
+    pub fn set_height(&mut self, height: T) {
+        self.height = height;
+    }
+
+    pub fn with_width(mut self, width: T) -> Self {
+        self.width = width;
+        self
+    }
+
+    pub fn with_height(mut self, height: T) -> Self {
+        self.height = height;
+        self
+    }
+
+    /// the rectangle of the width and the aspect ratio (width / height)
+    pub fn from_width_and_aspect(width: T, aspect_ratio: T) -> Self {
+        Self::new(width, width / aspect_ratio)
+    }
+
+    /// the rectangle of the height and the aspect ratio (width / height)
+    pub fn from_height_and_aspect(height: T, aspect_ratio: T) -> Self {
+        Self::new(height * aspect_ratio, height)
+    }
+}
+
+impl<T> Rectangle<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps + Float,
+{
+    /// the rectangle of the area and the aspect ratio (width / height), e.g. to pre-size a cell for a weight
+    pub fn from_area_and_aspect(area: T, aspect_ratio: T) -> Self {
+        Self::new((area * aspect_ratio).sqrt(), (area / aspect_ratio).sqrt())
+    }
+}
+
+/// A rectangle with default values. in many cases, this is 0×0
+impl<T> std::default::Default for Rectangle<T>
+where
+    T: Default + Copy + Num + NumAssignOps + NumOps,
+{
+    fn default() -> Self {
+        Self::new(T::default(), T::default())
+    }
+}
+
+impl<T> Rectangle<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps + PartialOrd,
+{
+    /// the size with each side clamped between the sides of `min` and `max`. `max` wins where they conflict
+    pub fn clamp(&self, min: &Self, max: &Self) -> Self {
+        let clamp = |v: T, lower: T, upper: T| {
+            let v = match v < lower {
+                true => lower,
+                false => v,
+            };
+            match v > upper {
+                true => upper,
+                false => v,
+            }
+        };
+        Self::new(
+            clamp(self.width, min.width, max.width),
+            clamp(self.height, min.height, max.height),
+        )
+    }
+
+    /// scale the content (this size) to fit entirely within the container, keeping its aspect ratio, and center it.
+    /// returns the content and the two letterbox bars around it (above and below, or left and right),
+    /// which are empty when the aspect ratios match
+    pub fn fit_within(
+        &self,
+        container: &AxisAlignedRectangle<T>,
+    ) -> (AxisAlignedRectangle<T>, [AxisAlignedRectangle<T>; 2]) {
+        let two = T::one() + T::one();
+        let scale_x = container.width() / self.width;
+        let scale_y = container.height() / self.height;
+        match scale_x < scale_y {
+            // as wide as the container, with bars above and below
+            true => {
+                let height = self.height * scale_x;
+                let bar = (container.height() - height) / two;
+                let content = AxisAlignedRectangle::new(
+                    &Point::new(container.x(), container.y() + bar),
+                    &Rectangle::new(container.width(), height),
+                );
+                let top = AxisAlignedRectangle::new(
+                    &container.origin(),
+                    &Rectangle::new(container.width(), bar),
+                );
+                let bottom = AxisAlignedRectangle::new(
+                    &Point::new(container.x(), container.y() + bar + height),
+                    &Rectangle::new(container.width(), container.height() - bar - height),
+                );
+                (content, [top, bottom])
+            }
+            // as tall as the container, with bars left and right
+            false => {
+                let width = self.width * scale_y;
+                let bar = (container.width() - width) / two;
+                let content = AxisAlignedRectangle::new(
+                    &Point::new(container.x() + bar, container.y()),
+                    &Rectangle::new(width, container.height()),
+                );
+                let left = AxisAlignedRectangle::new(
+                    &container.origin(),
+                    &Rectangle::new(bar, container.height()),
+                );
+                let right = AxisAlignedRectangle::new(
+                    &Point::new(container.x() + bar + width, container.y()),
+                    &Rectangle::new(container.width() - bar - width, container.height()),
+                );
+                (content, [left, right])
+            }
+        }
+    }
+
+    /// scale the content (this size) to cover the whole container, keeping its aspect ratio, and center it.
+    /// returns the part of the content that stays visible, in the content's own coordinates (e.g. the crop of an image)
+    pub fn cover(&self, container: &AxisAlignedRectangle<T>) -> AxisAlignedRectangle<T> {
+        let two = T::one() + T::one();
+        let scale_x = container.width() / self.width;
+        let scale_y = container.height() / self.height;
+        let scale = match scale_x > scale_y {
+            true => scale_x,
+            false => scale_y,
+        };
+        let width = container.width() / scale;
+        let height = container.height() / scale;
+        AxisAlignedRectangle::new(
+            &Point::new((self.width - width) / two, (self.height - height) / two),
+            &Rectangle::new(width, height),
+        )
+    }
 }
 
 /// Rotate a rectangle by 90 degrees
@@ -93,6 +245,16 @@ where
     }
 }
 
+/// A rectangle without a position is transposed by swapping its width and height
+impl<T> Transpose for Rectangle<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps,
+{
+    fn transpose_within(&self, _within: &Self) -> Self {
+        self.rotate_clockwise()
+    }
+}
+
 impl<T> VerticalDividingHelper<T> for Rectangle<T>
 where
     T: Copy + Num + NumAssignOps + NumOps,
@@ -106,6 +268,23 @@ where
     }
 }
 
+/// `w×h`, e.g. `4×3`. the precision (`{:.1}`) applies to both sides
+impl<T> std::fmt::Display for Rectangle<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps + std::fmt::Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match f.precision() {
+            Some(precision) => write!(
+                f,
+                "{:.*}×{:.*}",
+                precision, self.width, precision, self.height
+            ),
+            None => write!(f, "{}×{}", self.width, self.height),
+        }
+    }
+}
+
 impl<T> AspectRatio<T> for Rectangle<T>
 where
     T: Copy + Num + NumAssignOps + NumOps,
@@ -129,6 +308,50 @@ mod tests {
         assert_eq!(result.height, 3);
     }
 
+    #[test]
+    fn test_zero_unit_default() {
+        assert_eq!(Rectangle::<i32>::zero(), Rectangle::new(0, 0));
+        assert_eq!(Rectangle::<f64>::unit(), Rectangle::new(1.0, 1.0));
+        assert_eq!(Rectangle::<u8>::default(), Rectangle::zero());
+    }
+
+    #[test]
+    fn test_setters() {
+        let mut rect = Rectangle::new(2, 3);
+        rect.set_width(5);
+        rect.set_height(7);
+        assert_eq!(rect, Rectangle::new(5, 7));
+        assert_eq!(rect.with_width(1).with_height(2), Rectangle::new(1, 2));
+    }
+
+    #[test]
+    fn test_from_aspect() {
+        assert_eq!(
+            Rectangle::from_width_and_aspect(16, 2),
+            Rectangle::new(16, 8)
+        );
+        assert_eq!(
+            Rectangle::from_height_and_aspect(9.0, 16.0 / 9.0),
+            Rectangle::new(16.0, 9.0)
+        );
+        let rect = Rectangle::from_area_and_aspect(600.0, 1.5);
+        assert_eq!(rect, Rectangle::new(30.0, 20.0));
+        assert_eq!(rect.area(), 600.0);
+        assert_eq!(rect.aspect_ratio(), 1.5);
+    }
+
+    #[test]
+    fn test_clamp() {
+        let min = Rectangle::new(2, 2);
+        let max = Rectangle::new(10, 5);
+        assert_eq!(Rectangle::new(1, 8).clamp(&min, &max), Rectangle::new(2, 5));
+        assert_eq!(Rectangle::new(4, 3).clamp(&min, &max), Rectangle::new(4, 3));
+        assert_eq!(
+            Rectangle::new(4, 3).clamp(&Rectangle::new(6, 6), &max),
+            Rectangle::new(6, 5)
+        );
+    }
+
     #[test]
     fn test_identity() {
         // identity: a rectangle is equal to itself
@@ -141,6 +364,63 @@ mod tests {
         assert_rotate_twice_is_same_as_original(&Rectangle::new(2, 3));
     }
 
+    #[test]
+    fn test_transpose() {
+        let rect = Rectangle::new(2, 3);
+        assert_eq!(rect.transpose_within(&rect), Rectangle::new(3, 2));
+    }
+
+    #[test]
+    fn test_fit_within() {
+        let container =
+            AxisAlignedRectangle::new(&Point::new(10.0, 10.0), &Rectangle::new(16.0, 9.0));
+        let (content, bars) = Rectangle::new(4.0, 3.0).fit_within(&container);
+        assert_eq!(
+            content,
+            AxisAlignedRectangle::new(&Point::new(12.0, 10.0), &Rectangle::new(12.0, 9.0))
+        );
+        assert_eq!(
+            bars,
+            [
+                AxisAlignedRectangle::new(&Point::new(10.0, 10.0), &Rectangle::new(2.0, 9.0)),
+                AxisAlignedRectangle::new(&Point::new(24.0, 10.0), &Rectangle::new(2.0, 9.0)),
+            ]
+        );
+
+        let (content, bars) = Rectangle::new(32.0, 9.0).fit_within(&container);
+        assert_eq!(
+            content,
+            AxisAlignedRectangle::new(&Point::new(10.0, 12.25), &Rectangle::new(16.0, 4.5))
+        );
+        assert_eq!(bars[0].rect(), Rectangle::new(16.0, 2.25));
+        assert_eq!(bars[1].y(), 16.75);
+
+        let (content, bars) = Rectangle::new(32.0, 18.0).fit_within(&container);
+        assert_eq!(content, container);
+        assert_eq!(bars[0].width(), 0.0);
+        assert_eq!(bars[1].width(), 0.0);
+    }
+
+    #[test]
+    fn test_cover() {
+        let container =
+            AxisAlignedRectangle::new(&Point::new(10.0, 10.0), &Rectangle::new(16.0, 9.0));
+        assert_eq!(
+            Rectangle::new(400.0, 300.0).cover(&container),
+            AxisAlignedRectangle::new(&Point::new(0.0, 37.5), &Rectangle::new(400.0, 225.0))
+        );
+        assert_eq!(
+            Rectangle::new(32.0, 9.0).cover(&container),
+            AxisAlignedRectangle::new(&Point::new(8.0, 0.0), &Rectangle::new(16.0, 9.0))
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Rectangle::new(4, 3).to_string(), "4×3");
+        assert_eq!(format!("{:.2}", Rectangle::new(0.5, 1.0)), "0.50×1.00");
+    }
+
     #[test]
     fn test_area() {
         let result = Rectangle::new(2, 3).area();
@@ -202,7 +482,7 @@ mod tests {
         let divided1 = rect.divide_by_values_and_axis(&vec![1.0, 2.0], Axis::Vertical);
 
         let rect = Rectangle::new(6.0, 2.0);
-        let divided2 = rect.divide_by_weights_and_axis(&vec![2.0, 4.0, 6.0], Axis::Vertical);
+        let divided2 = rect.divide_by_weights_and_axis(&[2.0, 4.0, 6.0], Axis::Vertical);
         assert_eq!(divided1, divided2);
     }
 
@@ -0,0 +1,309 @@
+use num_traits::{Num, NumAssignOps, NumOps};
+
+use crate::area::Area;
+use crate::axis_aligned_rectangle::AxisAlignedRectangle;
+use crate::component::Component;
+use crate::dividing::Dividing;
+use crate::point::Point;
+use crate::rectangle::{Rectangle, RectangleSize};
+
+/// A region made of axis aligned rectangles (e.g. the free space of a container), kept as disjoint rectangles
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct RectilinearRegion<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps,
+{
+    rects: Vec<AxisAlignedRectangle<T>>,
+}
+
+impl<T> RectilinearRegion<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps + PartialOrd,
+{
+    pub fn new() -> Self {
+        Self { rects: Vec::new() }
+    }
+
+    /// the disjoint rectangles the region is made of
+    pub fn rects(&self) -> &[AxisAlignedRectangle<T>] {
+        &self.rects
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rects.is_empty()
+    }
+
+    pub fn area(&self) -> T {
+        self.rects.iter().fold(T::zero(), |sum, r| sum + r.area())
+    }
+
+    /// add the rectangle to the region. only the parts not in the region yet are added
+    pub fn insert(&mut self, rect: &AxisAlignedRectangle<T>) {
+        let mut pieces = vec![rect.clone()];
+        for existing in &self.rects {
+            pieces = pieces
+                .iter()
+                .flat_map(|piece| piece.subtract(existing))
+                .collect();
+        }
+        self.rects.extend(pieces);
+    }
+
+    /// remove the rectangle from the region
+    pub fn subtract(&mut self, rect: &AxisAlignedRectangle<T>) {
+        self.rects = self.rects.iter().flat_map(|r| r.subtract(rect)).collect();
+    }
+
+    /// the parts of the region inside the rectangle
+    pub fn query(&self, rect: &AxisAlignedRectangle<T>) -> Vec<AxisAlignedRectangle<T>> {
+        self.rects
+            .iter()
+            .filter_map(|r| r.intersection(rect))
+            .collect()
+    }
+
+    /// whether the rectangle is entirely in the region
+    pub fn contains(&self, rect: &AxisAlignedRectangle<T>) -> bool {
+        let covered = self
+            .query(rect)
+            .iter()
+            .fold(T::zero(), |sum, r| sum + r.area());
+        covered == rect.area()
+    }
+}
+
+impl<T> RectilinearRegion<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps + PartialOrd,
+{
+    /// the largest axis aligned rectangle entirely in the region (the topmost, then leftmost, of equal ones).
+    /// every edge of such a rectangle lies on an edge of the region, so only those are tried: O(n^5) for n rectangles
+    pub fn largest_rectangle(&self) -> Option<AxisAlignedRectangle<T>> {
+        let xs = sorted_edges(self.rects.iter().flat_map(|r| [r.x(), r.x() + r.width()]));
+        let ys = sorted_edges(self.rects.iter().flat_map(|r| [r.y(), r.y() + r.height()]));
+        let mut best: Option<AxisAlignedRectangle<T>> = None;
+        for (i, top) in ys.iter().enumerate() {
+            for bottom in &ys[i + 1..] {
+                for (j, left) in xs.iter().enumerate() {
+                    for right in &xs[j + 1..] {
+                        let candidate = AxisAlignedRectangle::new(
+                            &Point::new(*left, *top),
+                            &Rectangle::new(*right - *left, *bottom - *top),
+                        );
+                        if best.as_ref().is_some_and(|b| candidate.area() <= b.area()) {
+                            continue;
+                        }
+                        if !self.contains(&candidate) {
+                            // wider candidates from the same left edge are not inside either
+                            break;
+                        }
+                        best = Some(candidate);
+                    }
+                }
+            }
+        }
+        best
+    }
+}
+
+fn sorted_edges<T>(edges: impl Iterator<Item = T>) -> Vec<T>
+where
+    T: Copy + PartialOrd,
+{
+    let mut edges: Vec<T> = edges.collect();
+    edges.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    edges.dedup_by(|a, b| a == b);
+    edges
+}
+
+/// the largest axis aligned rectangle in the container not overlapping any of the placed rectangles,
+/// e.g. the biggest free spot for the next widget
+pub fn largest_empty_rectangle<T>(
+    container: &AxisAlignedRectangle<T>,
+    placed: &[AxisAlignedRectangle<T>],
+) -> Option<AxisAlignedRectangle<T>>
+where
+    T: Copy + Num + NumAssignOps + NumOps + PartialOrd,
+{
+    let mut free = RectilinearRegion::from(container.clone());
+    for rect in placed {
+        free.subtract(rect);
+    }
+    free.largest_rectangle()
+}
+
+impl<T> AxisAlignedRectangle<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps + PartialOrd + for<'a> std::iter::Sum<&'a T>,
+{
+    /// dividing the free space left by the obstacles (e.g. a notch or pinned chrome) by weights.
+    /// the free space is cut into disjoint rectangles, which take the weights in order, in proportion to their areas;
+    /// each is then divided like `divide_vertical_then_horizontal_with_weights`.
+    /// a cell's area matches its weight only as far as the weights can be shared out whole.
+    /// the n-th rectangle corresponds to the n-th weight
+    pub fn divide_by_weights_avoiding(
+        &self,
+        weights: &[T],
+        obstacles: &[AxisAlignedRectangle<T>],
+        aspect_ratio: T,
+    ) -> Vec<AxisAlignedRectangle<T>> {
+        let mut free = RectilinearRegion::from(self.clone());
+        for obstacle in obstacles {
+            free.subtract(obstacle);
+        }
+        let mut regions = free.rects().to_vec();
+        regions.sort_by(|a, b| {
+            (a.y(), a.x())
+                .partial_cmp(&(b.y(), b.x()))
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        let total_area = free.area();
+        let total_weight: T = weights.iter().sum();
+        if regions.is_empty() || total_weight == T::zero() {
+            return Vec::new();
+        }
+
+        // a weight goes to the region whose share of the area covers the middle of the weight's share
+        let two = T::one() + T::one();
+        let mut groups: Vec<Vec<T>> = vec![Vec::new(); regions.len()];
+        let mut region = 0;
+        let mut area_before = T::zero();
+        let mut weight_before = T::zero();
+        for weight in weights {
+            let middle = (weight_before + *weight / two) / total_weight;
+            while region + 1 < regions.len()
+                && (area_before + regions[region].area()) / total_area < middle
+            {
+                area_before += regions[region].area();
+                region += 1;
+            }
+            groups[region].push(*weight);
+            weight_before += *weight;
+        }
+        regions
+            .iter()
+            .zip(groups.iter())
+            .filter(|(_, group)| !group.is_empty())
+            .flat_map(|(region, group)| {
+                region.divide_vertical_then_horizontal_with_weights(group, aspect_ratio, false)
+            })
+            .collect()
+    }
+}
+
+impl<T> From<AxisAlignedRectangle<T>> for RectilinearRegion<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps,
+{
+    fn from(rect: AxisAlignedRectangle<T>) -> Self {
+        Self { rects: vec![rect] }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(x: i32, y: i32, width: i32, height: i32) -> AxisAlignedRectangle<i32> {
+        AxisAlignedRectangle::new(&Point::new(x, y), &Rectangle::new(width, height))
+    }
+
+    fn assert_disjoint(region: &RectilinearRegion<i32>) {
+        for (i, a) in region.rects().iter().enumerate() {
+            for b in region.rects().iter().skip(i + 1) {
+                assert_eq!(a.intersection(b), None);
+            }
+        }
+    }
+
+    #[test]
+    fn test_insert_and_subtract() {
+        let mut region = RectilinearRegion::from(rect(0, 0, 10, 10));
+        region.subtract(&rect(2, 2, 3, 3));
+        assert_disjoint(&region);
+        assert_eq!(region.area(), 91);
+        assert!(!region.contains(&rect(0, 0, 5, 5)));
+        assert!(region.contains(&rect(5, 0, 5, 10)));
+
+        region.insert(&rect(1, 1, 3, 3));
+        assert_disjoint(&region);
+        assert_eq!(region.area(), 95);
+
+        region.insert(&rect(8, 8, 4, 4));
+        assert_disjoint(&region);
+        assert_eq!(region.area(), 95 + 12);
+    }
+
+    #[test]
+    fn test_query() {
+        let mut region = RectilinearRegion::new();
+        assert!(region.is_empty());
+        region.insert(&rect(0, 0, 2, 2));
+        region.insert(&rect(4, 0, 2, 2));
+        assert_eq!(
+            region.query(&rect(1, 1, 4, 4)),
+            vec![rect(1, 1, 1, 1), rect(4, 1, 1, 1)]
+        );
+        assert!(region.query(&rect(2, 0, 2, 2)).is_empty());
+    }
+
+    #[test]
+    fn test_largest_empty_rectangle() {
+        let container = rect(0, 0, 10, 10);
+        assert_eq!(
+            largest_empty_rectangle(&container, &[]),
+            Some(container.clone())
+        );
+        assert_eq!(
+            largest_empty_rectangle(&container, &[rect(0, 0, 10, 4), rect(0, 4, 3, 6)]),
+            Some(rect(3, 4, 7, 6))
+        );
+        // four equal bands around the middle: the topmost wins
+        assert_eq!(
+            largest_empty_rectangle(&container, &[rect(2, 2, 6, 6)]),
+            Some(rect(0, 0, 10, 2))
+        );
+        assert_eq!(
+            largest_empty_rectangle(&container, &[rect(4, 0, 2, 10), rect(0, 4, 4, 2)]),
+            Some(rect(6, 0, 4, 10))
+        );
+        assert_eq!(
+            largest_empty_rectangle(&container, std::slice::from_ref(&container)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_divide_by_weights_avoiding() {
+        let container =
+            AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(10.0, 10.0));
+        let notch = AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(10.0, 2.0));
+        let weights = vec![1.0, 1.0, 1.0, 1.0];
+        let divided =
+            container.divide_by_weights_avoiding(&weights, std::slice::from_ref(&notch), 1.0);
+        assert_eq!(divided.len(), weights.len());
+        for cell in &divided {
+            assert_eq!(cell.intersection(&notch), None);
+            assert_eq!(cell.area(), 20.0);
+        }
+
+        // an obstacle in the middle leaves four regions; the weights are shared out by area
+        let obstacle = AxisAlignedRectangle::new(&Point::new(2.0, 2.0), &Rectangle::new(6.0, 6.0));
+        let weights = vec![5.0, 5.0, 3.0, 3.0, 5.0, 5.0];
+        let divided =
+            container.divide_by_weights_avoiding(&weights, std::slice::from_ref(&obstacle), 1.0);
+        assert_eq!(divided.len(), weights.len());
+        for (i, a) in divided.iter().enumerate() {
+            assert_eq!(a.intersection(&obstacle), None);
+            for b in divided.iter().skip(i + 1) {
+                assert_eq!(a.intersection(b), None);
+            }
+        }
+        let total: f64 = divided.iter().map(|c| c.area()).sum();
+        assert_eq!(total, 64.0);
+
+        assert!(container
+            .divide_by_weights_avoiding(&weights, std::slice::from_ref(&container), 1.0)
+            .is_empty());
+    }
+}
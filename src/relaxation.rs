@@ -0,0 +1,208 @@
+//! Refining a guillotine layout by moving its cuts, Lloyd-style: every iteration moves each cut toward where
+//! its parts would match their weights and, for cells, the target aspect ratio, and keeps the move only when
+//! the layout gets better.
+use num_traits::{Float, Num, NumAssignOps, NumOps};
+
+use crate::area::Area;
+use crate::axis::{Axis, SizeForAxis};
+use crate::axis_aligned_rectangle::AxisAlignedRectangle;
+use crate::dividing::Dividing;
+use crate::layout::Layout;
+use crate::metrics::{worst_aspect_error, worst_weight_error};
+use crate::split_tree::{SplitChild, SplitTree};
+
+/// The options of the relaxation
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Relaxation<T> {
+    pub iterations: usize,
+    /// the aspect ratio (width / height) the cells aim at
+    pub aspect_ratio: T,
+    /// how strongly cells are pulled toward the aspect ratio rather than toward their weights, from 0 to 1
+    pub aspect_pull: T,
+    /// the largest weight error (see `metrics::worst_weight_error`) a relaxed layout may have
+    pub max_weight_error: T,
+}
+
+impl<T> Relaxation<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps + Float + for<'a> std::iter::Sum<&'a T>,
+{
+    /// the layout with its cuts relaxed. the split tree of the layout is kept and only its fractions change,
+    /// so the cells stay a guillotine partition of the same container. an iteration is kept when the worst aspect
+    /// error does not grow and the weight error stays within the bound, or, while the weight error is out of the
+    /// bound, when it lowers the weight error; otherwise the next one moves half as far.
+    /// a layout without a tree is returned as is
+    pub fn relax(&self, layout: &Layout<T>, weights: &[T]) -> Layout<T> {
+        let (Some(tree), Some(container)) = (layout.tree(), layout.bounds()) else {
+            return layout.clone();
+        };
+        let score = |layout: &Layout<T>| {
+            (
+                worst_aspect_error(layout.cells(), self.aspect_ratio),
+                worst_weight_error(layout.cells(), container.area(), weights),
+            )
+        };
+        let mut best = layout.clone();
+        let mut best_tree = tree.clone();
+        let (mut aspect_error, mut weight_error) = score(&best);
+        let mut step = T::one();
+        for _ in 0..self.iterations {
+            let moved = self.step(&best_tree, &container, weights, step);
+            let candidate = moved.materialize(&container);
+            let (candidate_aspect, candidate_weight) = score(&candidate);
+            // out of the bound, the weights come first
+            let better = match weight_error > self.max_weight_error {
+                true => candidate_weight < weight_error,
+                false => {
+                    candidate_weight <= self.max_weight_error && candidate_aspect <= aspect_error
+                }
+            };
+            if better {
+                best = candidate;
+                best_tree = moved;
+                aspect_error = candidate_aspect;
+                weight_error = candidate_weight;
+            } else {
+                step /= T::one() + T::one();
+            }
+        }
+        match layout.strips() {
+            Some(strips) => best.with_strips(strips.to_vec()),
+            None => best,
+        }
+    }
+
+    /// every cut of the tree moved by the step toward its targets
+    fn step(
+        &self,
+        tree: &SplitTree<T>,
+        rect: &AxisAlignedRectangle<T>,
+        weights: &[T],
+        step: T,
+    ) -> SplitTree<T> {
+        let SplitTree::Cut {
+            axis,
+            children,
+            meta,
+        } = tree
+        else {
+            return tree.clone();
+        };
+        let weight_of = |node: &SplitTree<T>| -> T {
+            node.leaves()
+                .iter()
+                .filter_map(|i| weights.get(*i))
+                .sum::<T>()
+        };
+        let child_weights: Vec<T> = children.iter().map(|c| weight_of(&c.node)).collect();
+        let total: T = child_weights.iter().sum();
+        let size = rect.size_for_axis(*axis);
+        let cross = rect.size_for_axis(axis.opposite());
+        // the size along the axis of a cell of the target aspect ratio
+        let ideal = match axis {
+            Axis::Vertical => self.aspect_ratio * cross,
+            Axis::Horizontal => cross / self.aspect_ratio,
+        };
+        let mut fractions: Vec<T> = children
+            .iter()
+            .zip(child_weights.iter())
+            .map(|(child, weight)| {
+                let by_weight = *weight / total;
+                let target = match child.node {
+                    SplitTree::Leaf { .. } => {
+                        by_weight + self.aspect_pull * (ideal / size - by_weight)
+                    }
+                    SplitTree::Cut { .. } => by_weight,
+                };
+                let fraction = child.fraction + step * (target - child.fraction);
+                match fraction > T::zero() && fraction.is_finite() {
+                    true => fraction,
+                    false => child.fraction,
+                }
+            })
+            .collect();
+        let sum: T = fractions.iter().sum();
+        for fraction in fractions.iter_mut() {
+            *fraction /= sum;
+        }
+        let parts = rect.divide_by_weights_and_axis(&fractions, *axis);
+        let children = children
+            .iter()
+            .zip(fractions)
+            .zip(parts.iter())
+            .map(|((child, fraction), part)| SplitChild {
+                fraction,
+                node: self.step(&child.node, part, weights, step),
+            })
+            .collect();
+        SplitTree::Cut {
+            axis: *axis,
+            children,
+            meta: *meta,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::emission_order::EmissionOrder;
+    use crate::point::Point;
+    use crate::rectangle::{Rectangle, RectangleSize};
+    use crate::testing::verify_tiling;
+    use crate::tolerance::Tolerance;
+
+    fn container() -> AxisAlignedRectangle<f64> {
+        AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(100.0, 60.0))
+    }
+
+    #[test]
+    fn test_relax_aspect() {
+        // skewed weights leave some cells elongated
+        let weights = [40.0, 8.0, 4.0, 2.0, 1.0, 1.0];
+        let layout = Layout::divide_strips(
+            &container(),
+            &weights,
+            Axis::Vertical,
+            1.0,
+            EmissionOrder::Forward,
+        );
+        let relaxation = Relaxation {
+            iterations: 20,
+            aspect_ratio: 1.0,
+            aspect_pull: 0.5,
+            max_weight_error: 0.02,
+        };
+        let relaxed = relaxation.relax(&layout, &weights);
+        assert_eq!(relaxed.len(), layout.len());
+        assert_eq!(relaxed.strips(), layout.strips());
+        assert_eq!(
+            verify_tiling(&container(), relaxed.cells(), &Tolerance::default()),
+            Ok(())
+        );
+        let before = worst_aspect_error(layout.cells(), 1.0);
+        let after = worst_aspect_error(relaxed.cells(), 1.0);
+        assert!(after < before, "{} < {}", after, before);
+        assert!(worst_weight_error(relaxed.cells(), 6000.0, &weights) <= 0.02);
+    }
+
+    #[test]
+    fn test_relax_weights() {
+        // a tree whose fractions no longer match the weights, e.g. after editing
+        let weights = [1.0, 1.0, 2.0];
+        let tree = SplitTree::slices(&[1.0, 1.0, 1.0], Axis::Vertical);
+        let layout = tree.materialize(&container());
+        let relaxation = Relaxation {
+            iterations: 10,
+            aspect_ratio: 1.0,
+            aspect_pull: 0.0,
+            max_weight_error: 0.0,
+        };
+        let relaxed = relaxation.relax(&layout, &weights);
+        assert!(worst_weight_error(relaxed.cells(), 6000.0, &weights) < 1e-9);
+        assert!((relaxed.cells()[2].width() - 50.0).abs() < 1e-9);
+        // nothing to relax without a tree
+        let plain = Layout::new(layout.cells().to_vec());
+        assert_eq!(relaxation.relax(&plain, &weights), plain);
+    }
+}
@@ -11,3 +11,8 @@ where
             .rotate_clockwise()
     }
 }
+
+/// Swap x and y relative to a container, keeping the container's origin in place
+pub trait Transpose {
+    fn transpose_within(&self, within: &Self) -> Self;
+}
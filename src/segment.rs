@@ -0,0 +1,190 @@
+use num_traits::{Float, Num, NumAssignOps, NumOps};
+
+use crate::dividing::Dividing;
+use crate::weight::normalize_weights;
+
+/// A 1D interval: a start and a length, e.g. a span of a timeline or a scrollbar track
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct Segment<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps,
+{
+    start: T,
+    length: T,
+}
+
+impl<T> Segment<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps,
+{
+    pub fn new(start: T, length: T) -> Self {
+        Self { start, length }
+    }
+
+    pub fn start(&self) -> T {
+        self.start
+    }
+
+    pub fn length(&self) -> T {
+        self.length
+    }
+
+    pub fn end(&self) -> T {
+        self.start + self.length
+    }
+
+    /// whether the value is within [start, end)
+    pub fn contains(&self, value: T) -> bool
+    where
+        T: PartialOrd,
+    {
+        self.start <= value && value < self.end()
+    }
+
+    /// dividing a segment into specified weights of segments.
+    /// the n-th segment corresponds to the n-th weight
+    pub fn divide_by_weights(&self, weights: &[T]) -> Vec<Self>
+    where
+        T: for<'a> std::iter::Sum<&'a T>,
+    {
+        let zero = T::zero();
+        self.divide_by_weights_with_gap(weights, zero)
+    }
+
+    /// same as `divide_by_weights`, leaving a gap between adjacent segments (not at the ends).
+    /// the weights share the length left after the gaps
+    pub fn divide_by_weights_with_gap(&self, weights: &[T], gap: T) -> Vec<Self>
+    where
+        T: for<'a> std::iter::Sum<&'a T>,
+    {
+        if weights.is_empty() {
+            return vec![];
+        }
+        let mut gaps = T::zero();
+        for _ in 1..weights.len() {
+            gaps += gap;
+        }
+        let available = self.length - gaps;
+        let mut start = self.start;
+        let mut divided = Vec::with_capacity(weights.len());
+        let normalized_weights = normalize_weights(weights);
+        for w in &normalized_weights[..normalized_weights.len() - 1] {
+            let length = *w * available;
+            divided.push(Self::new(start, length));
+            start += length + gap;
+        }
+        // the last one ends exactly at the end, whatever the error of the others
+        divided.push(Self::new(start, self.end() - start));
+        divided
+    }
+}
+
+impl<T> Segment<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps + Float,
+{
+    /// round the start and the end independently, so that adjacent segments keep sharing their ends
+    pub fn round(&self) -> Self {
+        let start = self.start.round();
+        Self::new(start, self.end().round() - start)
+    }
+
+    /// same as `round`, to the nearest multiple of `step`. a non-positive step leaves the segment as is
+    pub fn snap(&self, step: T) -> Self {
+        if step <= T::zero() {
+            return *self;
+        }
+        let snap = |v: T| (v / step).round() * step;
+        let start = snap(self.start);
+        Self::new(start, snap(self.end()) - start)
+    }
+}
+
+/// a segment has a single axis, so dividing it vertically and horizontally are the same:
+/// both split it at the given length from the start
+impl<T> Dividing<T> for Segment<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps,
+{
+    fn divide_vertical(&self, x: T) -> (Self, Self) {
+        (
+            Self::new(self.start, x),
+            Self::new(self.start + x, self.length - x),
+        )
+    }
+
+    fn divide_horizontal(&self, y: T) -> (Self, Self) {
+        self.divide_vertical(y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_divide() {
+        let segment = Segment::new(2, 10);
+        assert_eq!(segment.end(), 12);
+        assert!(segment.contains(2));
+        assert!(!segment.contains(12));
+        assert_eq!(
+            segment.divide_vertical(3),
+            (Segment::new(2, 3), Segment::new(5, 7))
+        );
+        assert_eq!(segment.divide_horizontal(3), segment.divide_vertical(3));
+    }
+
+    #[test]
+    fn test_divide_by_weights() {
+        let segment = Segment::new(10.0, 100.0);
+        assert_eq!(
+            segment.divide_by_weights(&[1.0, 3.0, 1.0]),
+            vec![
+                Segment::new(10.0, 20.0),
+                Segment::new(30.0, 60.0),
+                Segment::new(90.0, 20.0),
+            ]
+        );
+        assert!(segment.divide_by_weights(&[]).is_empty());
+        assert_eq!(segment.divide_by_weights(&[2.0]), vec![segment]);
+    }
+
+    #[test]
+    fn test_divide_by_weights_with_gap() {
+        let segment = Segment::new(0.0, 100.0);
+        let divided = segment.divide_by_weights_with_gap(&[1.0, 1.0, 2.0], 10.0);
+        assert_eq!(
+            divided,
+            vec![
+                Segment::new(0.0, 20.0),
+                Segment::new(30.0, 20.0),
+                Segment::new(60.0, 40.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_round_and_snap() {
+        let divided = Segment::new(0.0, 10.0).divide_by_weights(&[1.0, 1.0, 1.0]);
+        let rounded: Vec<Segment<f64>> = divided.iter().map(|s| s.round()).collect();
+        assert_eq!(
+            rounded,
+            vec![
+                Segment::new(0.0, 3.0),
+                Segment::new(3.0, 4.0),
+                Segment::new(7.0, 3.0),
+            ]
+        );
+        let snapped: Vec<Segment<f64>> = divided.iter().map(|s| s.snap(5.0)).collect();
+        assert_eq!(
+            snapped,
+            vec![
+                Segment::new(0.0, 5.0),
+                Segment::new(5.0, 0.0),
+                Segment::new(5.0, 5.0),
+            ]
+        );
+        assert_eq!(divided[0].snap(0.0), divided[0]);
+    }
+}
@@ -0,0 +1,99 @@
+use num_traits::{Num, NumAssignOps, NumOps};
+
+use crate::area::Area;
+use crate::axis::{Axis, SizeForAxis};
+use crate::dividing::Dividing;
+use crate::emission_order::EmissionOrder;
+use crate::rectangle::RectangleSize;
+
+/// The dividing algorithms that `Dividing::divide_best` chooses from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Algorithm {
+    /// `divide_strips` with vertical strips (columns)
+    VerticalStrips,
+    /// `divide_strips` with horizontal strips (rows)
+    HorizontalStrips,
+    /// a single row of cells (`divide_by_weights_and_axis` with vertical cuts)
+    VerticalSlices,
+    /// a single column of cells (`divide_by_weights_and_axis` with horizontal cuts)
+    HorizontalSlices,
+    /// `divide_squarified`
+    Squarified,
+    /// `divide_binary`
+    Binary,
+}
+
+impl Algorithm {
+    /// the algorithms `Dividing::divide_best` and `Dividing::auto_divide` try. on a tie the earlier one wins
+    pub const ALL: [Algorithm; 6] = [
+        Algorithm::VerticalStrips,
+        Algorithm::HorizontalStrips,
+        Algorithm::VerticalSlices,
+        Algorithm::HorizontalSlices,
+        Algorithm::Squarified,
+        Algorithm::Binary,
+    ];
+
+    /// dividing the rectangle by weights with this algorithm. the aspect ratio is only used by the strip algorithms
+    pub fn divide<T, D>(&self, rect: &D, weights: &[T], aspect_ratio: T) -> Vec<D>
+    where
+        D: Dividing<T> + Sized + RectangleSize<T> + Clone + SizeForAxis<T> + Area<T>,
+        T: Copy
+            + for<'a> std::iter::Sum<&'a T>
+            + Num
+            + NumAssignOps
+            + NumOps
+            + std::cmp::PartialOrd,
+    {
+        match self {
+            Algorithm::VerticalStrips => rect.divide_strips(
+                weights,
+                Axis::Vertical,
+                aspect_ratio,
+                EmissionOrder::Forward,
+            ),
+            Algorithm::HorizontalStrips => rect.divide_strips(
+                weights,
+                Axis::Horizontal,
+                aspect_ratio,
+                EmissionOrder::Forward,
+            ),
+            Algorithm::VerticalSlices => rect.divide_by_weights_and_axis(weights, Axis::Vertical),
+            Algorithm::HorizontalSlices => {
+                rect.divide_by_weights_and_axis(weights, Axis::Horizontal)
+            }
+            Algorithm::Squarified => rect.divide_squarified(weights),
+            Algorithm::Binary => rect.divide_binary(weights),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rectangle::Rectangle;
+
+    #[test]
+    fn test_divide() {
+        let rect = Rectangle::new(6.0, 4.0);
+        let weights = vec![3.0, 2.0, 1.0];
+        for algorithm in Algorithm::ALL {
+            let divided = algorithm.divide(&rect, &weights, 1.0);
+            assert_eq!(divided.len(), weights.len());
+            let total: f64 = divided.iter().map(|d| d.area()).sum();
+            assert!((total - rect.area()).abs() < 1e-9);
+        }
+        assert_eq!(
+            Algorithm::VerticalSlices.divide(&rect, &weights, 1.0),
+            vec![
+                Rectangle::new(3.0, 4.0),
+                Rectangle::new(2.0, 4.0),
+                Rectangle::new(1.0, 4.0)
+            ]
+        );
+        assert_eq!(
+            Algorithm::HorizontalStrips.divide(&rect, &weights, 1.0),
+            rect.divide_horizontal_then_vertical_with_weights(&weights, 1.0, false)
+        );
+    }
+}
@@ -0,0 +1,1332 @@
+//! The structure of a divided layout: the cuts made, without the geometry.
+use num_traits::{Num, NumAssignOps, NumOps};
+use serde::{Deserialize, Serialize};
+
+use crate::axis::{Axis, SizeForAxis, ValueForAxis};
+use crate::axis_aligned_rectangle::AxisAlignedRectangle;
+use crate::component::Component;
+use crate::dividing::Dividing;
+use crate::emission_order::EmissionOrder;
+use crate::layout::Layout;
+use crate::point::Point;
+use crate::rectangle::{Rectangle, RectangleSize};
+use crate::tolerance::Tolerance;
+use crate::weight::normalize_weights;
+
+/// A tree of the cuts that divide a rectangle. leaves are the cells, by the index of their weight.
+/// every node can carry metadata of the type `M` (e.g. a struct of a name, a color and whether it is collapsed,
+/// or `serde_json::Value`), kept through serialization, edits and materialization. `()` when there is none
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub enum SplitTree<T, M = ()> {
+    /// a cell, for the weight of the index
+    Leaf {
+        index: usize,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        meta: Option<M>,
+    },
+    /// cut lines of the axis across the rectangle. the children are the parts from the start edge on (left or top),
+    /// each taking its fraction of the size along the axis
+    Cut {
+        axis: Axis,
+        children: Vec<SplitChild<T, M>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        meta: Option<M>,
+    },
+}
+
+/// A part of a cut rectangle
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct SplitChild<T, M = ()> {
+    pub fraction: T,
+    pub node: SplitTree<T, M>,
+}
+
+impl<T> SplitTree<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps + for<'a> std::iter::Sum<&'a T>,
+{
+    /// the tree of `Dividing::divide_by_weights_and_axis`: one cut into a slice for each weight
+    pub fn slices(weights: &[T], axis: Axis) -> Self {
+        SplitTree::cut(
+            axis,
+            normalize_weights(weights)
+                .into_iter()
+                .enumerate()
+                .map(|(index, fraction)| SplitChild {
+                    fraction,
+                    node: SplitTree::leaf(index),
+                })
+                .collect(),
+        )
+    }
+
+    /// the tree of `Dividing::divide_strips` for its strip groups (see `Dividing::strip_groups`):
+    /// a cut into strips along the primary axis, and a cut of each strip into its cells
+    pub fn strips(
+        weights: &[T],
+        groups: &[Vec<usize>],
+        primary_axis: Axis,
+        order: EmissionOrder,
+    ) -> Self {
+        let norm_weights = normalize_weights(weights);
+        let mut strips: Vec<Option<SplitChild<T>>> =
+            std::iter::repeat_with(|| None).take(groups.len()).collect();
+        for (n, (group, slot)) in groups
+            .iter()
+            .zip(order.strip_slots(groups.len()))
+            .enumerate()
+        {
+            let strip_weight: T = group.iter().map(|i| &norm_weights[*i]).sum();
+            let mut cells: Vec<SplitChild<T>> = group
+                .iter()
+                .map(|i| SplitChild {
+                    fraction: norm_weights[*i] / strip_weight,
+                    node: SplitTree::leaf(*i),
+                })
+                .collect();
+            if order.is_strip_reversed(n) {
+                cells.reverse();
+            }
+            strips[slot] = Some(SplitChild {
+                fraction: strip_weight,
+                node: SplitTree::cut(primary_axis.opposite(), cells),
+            });
+        }
+        SplitTree::cut(primary_axis, strips.into_iter().flatten().collect())
+    }
+}
+
+impl<T, M> SplitTree<T, M>
+where
+    T: Copy + Num + NumAssignOps + NumOps + for<'a> std::iter::Sum<&'a T>,
+{
+    /// the cells of the tree in the container. the n-th cell is for the n-th smallest leaf index,
+    /// so for a tree of weights, the n-th cell corresponds to the n-th weight
+    pub fn materialize(&self, container: &AxisAlignedRectangle<T>) -> Layout<T, M>
+    where
+        M: Clone,
+    {
+        self.materialize_with(container, |_, cell| cell)
+    }
+
+    /// same as `materialize`, applying the transform to each cell with the index of its leaf
+    /// (e.g. `|_, cell| cell.shrink_clamped(padding)` or `|_, cell| cell.round()`)
+    pub fn materialize_with<F>(
+        &self,
+        container: &AxisAlignedRectangle<T>,
+        mut transform: F,
+    ) -> Layout<T, M>
+    where
+        M: Clone,
+        F: FnMut(usize, AxisAlignedRectangle<T>) -> AxisAlignedRectangle<T>,
+    {
+        let mut cells: Vec<(usize, AxisAlignedRectangle<T>)> = Vec::new();
+        self.collect_cells(container, &mut cells);
+        cells.sort_by_key(|(index, _)| *index);
+        Layout::new(
+            cells
+                .into_iter()
+                .map(|(index, cell)| transform(index, cell))
+                .collect(),
+        )
+        .with_tree(self.clone())
+    }
+
+    fn collect_cells(
+        &self,
+        rect: &AxisAlignedRectangle<T>,
+        cells: &mut Vec<(usize, AxisAlignedRectangle<T>)>,
+    ) {
+        match self {
+            SplitTree::Leaf { index, .. } => cells.push((*index, rect.clone())),
+            SplitTree::Cut { axis, children, .. } => {
+                let fractions: Vec<T> = children.iter().map(|c| c.fraction).collect();
+                let parts = rect.divide_by_weights_and_axis(&fractions, *axis);
+                for (child, part) in children.iter().zip(parts.iter()) {
+                    child.node.collect_cells(part, cells);
+                }
+            }
+        }
+    }
+}
+
+/// Identifies a cut line in a split tree: the path of child indices from the root to the cut node,
+/// and which boundary between its children (0 for the one after the first child).
+/// the id stays the same as long as the structure does, wherever the tree is materialized
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone)]
+pub struct CutId {
+    pub node: Vec<usize>,
+    pub boundary: usize,
+}
+
+/// A cut line of a materialized split tree
+#[derive(Debug, PartialEq, Clone)]
+pub struct CutLine<T> {
+    pub id: CutId,
+    /// vertical lines have an x position and run from y = start to y = end; horizontal lines the other way around
+    pub axis: Axis,
+    pub position: T,
+    pub start: T,
+    pub end: T,
+    /// the leaf indices of the part before (left of or above) the line
+    pub before: Vec<usize>,
+    /// the leaf indices of the part after (right of or below) the line
+    pub after: Vec<usize>,
+}
+
+impl<T, M> SplitTree<T, M>
+where
+    T: Copy + Num + NumAssignOps + NumOps + for<'a> std::iter::Sum<&'a T>,
+{
+    /// the cut lines of the tree materialized in the container, parents before children
+    pub fn cut_lines(&self, container: &AxisAlignedRectangle<T>) -> Vec<CutLine<T>> {
+        let mut lines = Vec::new();
+        self.collect_cut_lines(container, &mut Vec::new(), &mut lines);
+        lines
+    }
+
+    fn collect_cut_lines(
+        &self,
+        rect: &AxisAlignedRectangle<T>,
+        path: &mut Vec<usize>,
+        lines: &mut Vec<CutLine<T>>,
+    ) {
+        let SplitTree::Cut { axis, children, .. } = self else {
+            return;
+        };
+        let fractions: Vec<T> = children.iter().map(|c| c.fraction).collect();
+        let parts = rect.divide_by_weights_and_axis(&fractions, *axis);
+        let (start, end) = match axis {
+            Axis::Vertical => (rect.y(), rect.y() + rect.height()),
+            Axis::Horizontal => (rect.x(), rect.x() + rect.width()),
+        };
+        for (boundary, (pair, after)) in children.windows(2).zip(parts.iter().skip(1)).enumerate() {
+            lines.push(CutLine {
+                id: CutId {
+                    node: path.clone(),
+                    boundary,
+                },
+                axis: *axis,
+                position: after.origin().value_for_axis(*axis),
+                start,
+                end,
+                before: pair[0].node.leaves(),
+                after: pair[1].node.leaves(),
+            });
+        }
+        for (i, (child, part)) in children.iter().zip(parts.iter()).enumerate() {
+            path.push(i);
+            child.node.collect_cut_lines(part, path, lines);
+            path.pop();
+        }
+    }
+}
+
+/// What a group of cells stands for
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum GroupKind {
+    /// a strip of a strip layout
+    Strip,
+    /// the small items gathered in an "other" bucket
+    Other,
+    /// the children of a parent of a hierarchy
+    Parent,
+}
+
+/// A group of cells, e.g. a strip of a strip layout: its rectangle, the weight indices of its cells and their total weight,
+/// for drawing legends and group outlines
+#[derive(Debug, PartialEq, Clone)]
+pub struct StripSummary<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps,
+{
+    pub id: usize,
+    pub kind: GroupKind,
+    pub rect: AxisAlignedRectangle<T>,
+    /// in the order of the cells in the strip
+    pub members: Vec<usize>,
+    pub weight: T,
+}
+
+impl<T, M> SplitTree<T, M>
+where
+    T: Copy + Num + NumAssignOps + NumOps + for<'a> std::iter::Sum<&'a T>,
+{
+    /// the strips of the tree materialized in the container: the children of the root cut,
+    /// in position order along its axis and identified by it. a single leaf is one strip
+    pub fn strip_summaries(
+        &self,
+        container: &AxisAlignedRectangle<T>,
+        weights: &[T],
+    ) -> Vec<StripSummary<T>> {
+        let summary = |id: usize, node: &SplitTree<T, M>, rect: &AxisAlignedRectangle<T>| {
+            let members = node.leaves();
+            let weight = members.iter().filter_map(|i| weights.get(*i)).sum();
+            StripSummary {
+                id,
+                kind: GroupKind::Strip,
+                rect: rect.clone(),
+                members,
+                weight,
+            }
+        };
+        match self {
+            SplitTree::Leaf { .. } => vec![summary(0, self, container)],
+            SplitTree::Cut { axis, children, .. } => {
+                let fractions: Vec<T> = children.iter().map(|c| c.fraction).collect();
+                let parts = container.divide_by_weights_and_axis(&fractions, *axis);
+                children
+                    .iter()
+                    .zip(parts.iter())
+                    .enumerate()
+                    .map(|(id, (child, part))| summary(id, &child.node, part))
+                    .collect()
+            }
+        }
+    }
+}
+
+/// Why a split tree is not valid. nodes are identified by the path of child indices from the root
+#[derive(Debug, PartialEq, Clone)]
+pub enum SplitTreeError<T> {
+    /// the fractions of the children of the cut do not sum to 1
+    FractionSum { node: Vec<usize>, sum: T },
+    /// the child has a zero (or negative) fraction, so its cells would have no area
+    DegenerateChild { node: Vec<usize> },
+    /// the cut has no children
+    EmptyCut { node: Vec<usize> },
+    /// the tree has a different number of leaves than there are weights
+    LeafCount { expected: usize, actual: usize },
+    /// the leaf has an index out of the weights
+    LeafOutOfRange { index: usize },
+    /// two leaves have the same index
+    DuplicateLeaf { index: usize },
+}
+
+impl<T> std::fmt::Display for SplitTreeError<T>
+where
+    T: std::fmt::Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SplitTreeError::FractionSum { node, sum } => {
+                write!(f, "the fractions of node {:?} sum to {}", node, sum)
+            }
+            SplitTreeError::DegenerateChild { node } => {
+                write!(f, "node {:?} has no positive fraction", node)
+            }
+            SplitTreeError::EmptyCut { node } => write!(f, "node {:?} cuts into nothing", node),
+            SplitTreeError::LeafCount { expected, actual } => {
+                write!(f, "{} leaves for {} weights", actual, expected)
+            }
+            SplitTreeError::LeafOutOfRange { index } => {
+                write!(f, "leaf {} is out of the weights", index)
+            }
+            SplitTreeError::DuplicateLeaf { index } => write!(f, "leaf {} appears twice", index),
+        }
+    }
+}
+
+impl<T> std::error::Error for SplitTreeError<T> where T: std::fmt::Debug + std::fmt::Display {}
+
+impl<T, M> SplitTree<T, M>
+where
+    T: Copy + Num + NumAssignOps + NumOps + PartialOrd + for<'a> std::iter::Sum<&'a T>,
+{
+    /// check the structure, e.g. of a tree loaded or edited externally: every cut has children with positive
+    /// fractions summing to 1 (within the tolerance), and there is exactly one leaf for each of the weights
+    pub fn validate(
+        &self,
+        weight_count: usize,
+        tolerance: impl Into<Tolerance<T>>,
+    ) -> Result<(), SplitTreeError<T>> {
+        self.validate_node(&mut Vec::new(), &tolerance.into())?;
+        let leaves = self.leaves();
+        if leaves.len() != weight_count {
+            return Err(SplitTreeError::LeafCount {
+                expected: weight_count,
+                actual: leaves.len(),
+            });
+        }
+        let mut seen = vec![false; weight_count];
+        for index in leaves {
+            match seen.get_mut(index) {
+                None => return Err(SplitTreeError::LeafOutOfRange { index }),
+                Some(true) => return Err(SplitTreeError::DuplicateLeaf { index }),
+                Some(seen) => *seen = true,
+            }
+        }
+        Ok(())
+    }
+
+    fn validate_node(
+        &self,
+        path: &mut Vec<usize>,
+        tolerance: &Tolerance<T>,
+    ) -> Result<(), SplitTreeError<T>> {
+        let SplitTree::Cut { children, .. } = self else {
+            return Ok(());
+        };
+        if children.is_empty() {
+            return Err(SplitTreeError::EmptyCut { node: path.clone() });
+        }
+        let sum: T = children.iter().map(|c| &c.fraction).sum();
+        if !tolerance.approx_eq(sum, T::one()) {
+            return Err(SplitTreeError::FractionSum {
+                node: path.clone(),
+                sum,
+            });
+        }
+        for (i, child) in children.iter().enumerate() {
+            path.push(i);
+            if child.fraction <= T::zero() {
+                return Err(SplitTreeError::DegenerateChild { node: path.clone() });
+            }
+            child.node.validate_node(path, tolerance)?;
+            path.pop();
+        }
+        Ok(())
+    }
+}
+
+/// Why rectangles can not be turned into a split tree
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum FromRectsError {
+    /// the rectangles do not exactly tile the container: they overlap, leave gaps or stick out
+    NotATiling,
+    /// the rectangles tile the container, but some part can not be cut through without cutting a rectangle
+    NotGuillotine,
+}
+
+impl std::fmt::Display for FromRectsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FromRectsError::NotATiling => write!(f, "the rectangles do not tile the container"),
+            FromRectsError::NotGuillotine => {
+                write!(f, "the rectangles can not be made by guillotine cuts")
+            }
+        }
+    }
+}
+
+impl std::error::Error for FromRectsError {}
+
+impl<T> SplitTree<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps + PartialOrd,
+{
+    /// the split tree of rectangles that exactly tile the container by guillotine cuts (e.g. a layout made elsewhere).
+    /// the n-th rectangle becomes the leaf of index n. edges must match exactly
+    pub fn from_rects(
+        container: &AxisAlignedRectangle<T>,
+        rects: &[AxisAlignedRectangle<T>],
+    ) -> Result<Self, FromRectsError> {
+        let inside = |r: &AxisAlignedRectangle<T>| {
+            r.x() >= container.x()
+                && r.y() >= container.y()
+                && r.x() + r.width() <= container.x() + container.width()
+                && r.y() + r.height() <= container.y() + container.height()
+        };
+        let area = rects
+            .iter()
+            .fold(T::zero(), |sum, r| sum + r.width() * r.height());
+        let overlapping = rects.iter().enumerate().any(|(i, a)| {
+            rects
+                .iter()
+                .skip(i + 1)
+                .any(|b| a.intersection(b).is_some())
+        });
+        if rects.is_empty()
+            || !rects.iter().all(inside)
+            || overlapping
+            || area != container.width() * container.height()
+        {
+            return Err(FromRectsError::NotATiling);
+        }
+        let indices: Vec<usize> = (0..rects.len()).collect();
+        Self::from_tiling(container, rects, &indices)
+    }
+
+    /// the rectangles of the indices tile the region
+    fn from_tiling(
+        region: &AxisAlignedRectangle<T>,
+        rects: &[AxisAlignedRectangle<T>],
+        indices: &[usize],
+    ) -> Result<Self, FromRectsError> {
+        if let [index] = indices {
+            return Ok(SplitTree::leaf(*index));
+        }
+        for axis in [Axis::Vertical, Axis::Horizontal] {
+            let start = |r: &AxisAlignedRectangle<T>| r.origin().value_for_axis(axis);
+            let end = |r: &AxisAlignedRectangle<T>| start(r) + r.size_for_axis(axis);
+            let region_end = end(region);
+            // the ends of rectangles that no rectangle crosses
+            let mut cuts: Vec<T> = indices
+                .iter()
+                .map(|i| end(&rects[*i]))
+                .filter(|position| *position < region_end)
+                .filter(|position| {
+                    !indices
+                        .iter()
+                        .any(|i| start(&rects[*i]) < *position && *position < end(&rects[*i]))
+                })
+                .collect();
+            if cuts.is_empty() {
+                continue;
+            }
+            cuts.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+            cuts.dedup_by(|a, b| a == b);
+            cuts.push(region_end);
+            let mut children = Vec::new();
+            let mut part_start = start(region);
+            for part_end in cuts {
+                let part = match axis {
+                    Axis::Vertical => AxisAlignedRectangle::new(
+                        &Point::new(part_start, region.y()),
+                        &Rectangle::new(part_end - part_start, region.height()),
+                    ),
+                    Axis::Horizontal => AxisAlignedRectangle::new(
+                        &Point::new(region.x(), part_start),
+                        &Rectangle::new(region.width(), part_end - part_start),
+                    ),
+                };
+                let members: Vec<usize> = indices
+                    .iter()
+                    .filter(|i| start(&rects[**i]) >= part_start && end(&rects[**i]) <= part_end)
+                    .copied()
+                    .collect();
+                children.push(SplitChild {
+                    fraction: (part_end - part_start) / region.size_for_axis(axis),
+                    node: Self::from_tiling(&part, rects, &members)?,
+                });
+                part_start = part_end;
+            }
+            return Ok(SplitTree::cut(axis, children));
+        }
+        Err(FromRectsError::NotGuillotine)
+    }
+}
+
+/// Why an edit of a split tree failed. nodes are identified by the path of child indices from the root
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum EditError {
+    /// there is no node at the path
+    NoSuchNode(Vec<usize>),
+    /// the node at the path is a leaf, not a cut
+    NotACut(Vec<usize>),
+    /// the root can not be moved
+    InvalidMove,
+}
+
+impl std::fmt::Display for EditError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EditError::NoSuchNode(path) => write!(f, "no node at {:?}", path),
+            EditError::NotACut(path) => write!(f, "node {:?} is not a cut", path),
+            EditError::InvalidMove => write!(f, "invalid move"),
+        }
+    }
+}
+
+impl std::error::Error for EditError {}
+
+/// trees are made without metadata; `map_meta` gives them a metadata type
+impl<T> SplitTree<T> {
+    pub fn leaf(index: usize) -> Self {
+        SplitTree::Leaf { index, meta: None }
+    }
+
+    pub fn cut(axis: Axis, children: Vec<SplitChild<T>>) -> Self {
+        SplitTree::Cut {
+            axis,
+            children,
+            meta: None,
+        }
+    }
+}
+
+impl<T, M> SplitTree<T, M> {
+    /// the same tree with the metadata of every node converted, e.g. from `()` to a type that can hold some
+    pub fn map_meta<N, F>(self, mut f: F) -> SplitTree<T, N>
+    where
+        F: FnMut(M) -> N,
+    {
+        self.map_meta_with(&mut f)
+    }
+
+    fn map_meta_with<N, F>(self, f: &mut F) -> SplitTree<T, N>
+    where
+        F: FnMut(M) -> N,
+    {
+        match self {
+            SplitTree::Leaf { index, meta } => SplitTree::Leaf {
+                index,
+                meta: meta.map(&mut *f),
+            },
+            SplitTree::Cut {
+                axis,
+                children,
+                meta,
+            } => SplitTree::Cut {
+                axis,
+                meta: meta.map(&mut *f),
+                children: children
+                    .into_iter()
+                    .map(|child| SplitChild {
+                        fraction: child.fraction,
+                        node: child.node.map_meta_with(f),
+                    })
+                    .collect(),
+            },
+        }
+    }
+
+    /// attach metadata to the node
+    pub fn with_meta(mut self, value: M) -> Self {
+        *self.meta_mut() = Some(value);
+        self
+    }
+
+    pub fn meta(&self) -> Option<&M> {
+        match self {
+            SplitTree::Leaf { meta, .. } | SplitTree::Cut { meta, .. } => meta.as_ref(),
+        }
+    }
+
+    pub fn meta_mut(&mut self) -> &mut Option<M> {
+        match self {
+            SplitTree::Leaf { meta, .. } | SplitTree::Cut { meta, .. } => meta,
+        }
+    }
+
+    /// the metadata of the leaf of the weight index
+    pub fn leaf_meta(&self, index: usize) -> Option<&M> {
+        match self {
+            SplitTree::Leaf { index: i, meta } if *i == index => meta.as_ref(),
+            SplitTree::Leaf { .. } => None,
+            SplitTree::Cut { children, .. } => {
+                children.iter().find_map(|c| c.node.leaf_meta(index))
+            }
+        }
+    }
+
+    /// the node at the path of child indices from the root
+    pub fn node(&self, path: &[usize]) -> Option<&SplitTree<T, M>> {
+        match path.split_first() {
+            None => Some(self),
+            Some((first, rest)) => match self {
+                SplitTree::Leaf { .. } => None,
+                SplitTree::Cut { children, .. } => children.get(*first)?.node.node(rest),
+            },
+        }
+    }
+
+    pub fn node_mut(&mut self, path: &[usize]) -> Option<&mut SplitTree<T, M>> {
+        match path.split_first() {
+            None => Some(self),
+            Some((first, rest)) => match self {
+                SplitTree::Leaf { .. } => None,
+                SplitTree::Cut { children, .. } => children.get_mut(*first)?.node.node_mut(rest),
+            },
+        }
+    }
+
+    /// exchange the places of the leaves of two weights; the places keep their sizes and the leaves their metadata.
+    /// returns whether both leaves were found
+    pub fn swap_leaves(&mut self, a: usize, b: usize) -> bool {
+        let leaves = self.leaves();
+        if !leaves.contains(&a) || !leaves.contains(&b) {
+            return false;
+        }
+        let (mut meta_a, mut meta_b) = (None, None);
+        self.for_each_leaf_mut(&mut |index, meta| {
+            if *index == a {
+                meta_a = meta.take();
+            } else if *index == b {
+                meta_b = meta.take();
+            }
+        });
+        self.for_each_leaf_mut(&mut |index, meta| {
+            if *index == a {
+                *index = b;
+                *meta = meta_b.take();
+            } else if *index == b {
+                *index = a;
+                *meta = meta_a.take();
+            }
+        });
+        true
+    }
+
+    fn for_each_leaf_mut<F>(&mut self, f: &mut F)
+    where
+        F: FnMut(&mut usize, &mut Option<M>),
+    {
+        match self {
+            SplitTree::Leaf { index, meta } => f(index, meta),
+            SplitTree::Cut { children, .. } => {
+                for child in children {
+                    child.node.for_each_leaf_mut(f);
+                }
+            }
+        }
+    }
+
+    /// change the axis of the cut at the path, e.g. to turn a column into a row
+    pub fn set_axis(&mut self, path: &[usize], axis: Axis) -> Result<(), EditError> {
+        match self.node_mut(path) {
+            Some(SplitTree::Cut { axis: a, .. }) => {
+                *a = axis;
+                Ok(())
+            }
+            Some(SplitTree::Leaf { .. }) => Err(EditError::NotACut(path.to_vec())),
+            None => Err(EditError::NoSuchNode(path.to_vec())),
+        }
+    }
+}
+
+impl<T, M> SplitTree<T, M>
+where
+    T: Copy + Num + NumAssignOps + NumOps + for<'a> std::iter::Sum<&'a T>,
+{
+    /// move the subtree at `from` into the cut at `to` (the path after the subtree is taken out),
+    /// as its child at `position` (clamped to its children). the fractions of both cuts are scaled to sum to 1 again,
+    /// the moved subtree taking an equal share of its new cut; call `rebalance` to size everything by the weights.
+    /// a cut left with a single child is replaced by it, and one left without children is removed.
+    /// the tree is left as is when the move fails
+    pub fn move_subtree(
+        &mut self,
+        from: &[usize],
+        to: &[usize],
+        position: usize,
+    ) -> Result<(), EditError>
+    where
+        M: Clone,
+    {
+        let Some((child_index, parent_path)) = from.split_last() else {
+            return Err(EditError::InvalidMove);
+        };
+        let mut edited = self.clone();
+        let moved = match edited.node_mut(parent_path) {
+            Some(SplitTree::Cut { children, .. }) if *child_index < children.len() => {
+                let moved = children.remove(*child_index);
+                normalize_fractions(children);
+                moved
+            }
+            _ => return Err(EditError::NoSuchNode(from.to_vec())),
+        };
+        let position = match edited.node_mut(to) {
+            Some(SplitTree::Cut { children, .. }) => {
+                let count = T::one() + children.iter().fold(T::zero(), |n, _| n + T::one());
+                for child in children.iter_mut() {
+                    child.fraction = child.fraction * (count - T::one()) / count;
+                }
+                let position = position.min(children.len());
+                children.insert(
+                    position,
+                    SplitChild {
+                        fraction: T::one() / count,
+                        node: moved.node,
+                    },
+                );
+                position
+            }
+            Some(SplitTree::Leaf { .. }) => return Err(EditError::NotACut(to.to_vec())),
+            None => return Err(EditError::NoSuchNode(to.to_vec())),
+        };
+        // the cut the subtree was taken out of is one further when it was inserted before it
+        let mut parent_path = parent_path.to_vec();
+        if parent_path.len() > to.len()
+            && parent_path.starts_with(to)
+            && position <= parent_path[to.len()]
+        {
+            parent_path[to.len()] += 1;
+        }
+        edited.collapse(parent_path);
+        *self = edited;
+        Ok(())
+    }
+
+    /// replace the cut at the path by its child when it has only one, or remove it when it has none
+    /// (and then its parent the same way)
+    fn collapse(&mut self, mut path: Vec<usize>) {
+        loop {
+            let Some(SplitTree::Cut { children, .. }) = self.node_mut(&path) else {
+                return;
+            };
+            match children.len() {
+                0 => {}
+                1 => {
+                    let only = children.remove(0);
+                    if let Some(node) = self.node_mut(&path) {
+                        *node = only.node;
+                    }
+                    return;
+                }
+                _ => return,
+            }
+            let Some(index) = path.pop() else {
+                return;
+            };
+            if let Some(SplitTree::Cut { children, .. }) = self.node_mut(&path) {
+                children.remove(index);
+                normalize_fractions(children);
+            }
+        }
+    }
+
+    /// set every fraction to the share of the weights of its leaves in its cut,
+    /// so that the materialized cells have the areas of their weights again
+    pub fn rebalance(&mut self, weights: &[T]) {
+        self.rebalance_node(weights);
+    }
+
+    /// returns the sum of the weights of the leaves
+    fn rebalance_node(&mut self, weights: &[T]) -> T {
+        match self {
+            SplitTree::Leaf { index, .. } => weights.get(*index).copied().unwrap_or(T::zero()),
+            SplitTree::Cut { children, .. } => {
+                let mut total = T::zero();
+                for child in children.iter_mut() {
+                    child.fraction = child.node.rebalance_node(weights);
+                    total += child.fraction;
+                }
+                if total != T::zero() {
+                    for child in children.iter_mut() {
+                        child.fraction /= total;
+                    }
+                }
+                total
+            }
+        }
+    }
+}
+
+fn normalize_fractions<T, M>(children: &mut [SplitChild<T, M>])
+where
+    T: Copy + Num + NumAssignOps + NumOps,
+{
+    let total = children.iter().fold(T::zero(), |sum, c| sum + c.fraction);
+    if total == T::zero() {
+        return;
+    }
+    for child in children {
+        child.fraction /= total;
+    }
+}
+
+impl<T, M> SplitTree<T, M>
+where
+    T: std::fmt::Display,
+{
+    /// the hierarchy of cuts as text, one node per line with the fraction of its parent, e.g.
+    /// ```text
+    /// cut vertical
+    /// ├── 0.5 cut horizontal
+    /// │   ├── 0.5 leaf 0
+    /// │   └── 0.5 leaf 1
+    /// └── 0.5 leaf 2
+    /// ```
+    pub fn to_ascii_tree(&self) -> String {
+        let mut lines = vec![self.label()];
+        self.write_children("", &mut lines);
+        lines.join("\n")
+    }
+
+    fn label(&self) -> String {
+        match self {
+            SplitTree::Leaf { index, .. } => format!("leaf {}", index),
+            SplitTree::Cut { axis, .. } => match axis {
+                Axis::Vertical => "cut vertical".to_string(),
+                Axis::Horizontal => "cut horizontal".to_string(),
+            },
+        }
+    }
+
+    fn write_children(&self, prefix: &str, lines: &mut Vec<String>) {
+        let SplitTree::Cut { children, .. } = self else {
+            return;
+        };
+        for (i, child) in children.iter().enumerate() {
+            let last = i + 1 == children.len();
+            let (branch, indent) = match last {
+                true => ("└── ", "    "),
+                false => ("├── ", "│   "),
+            };
+            lines.push(format!(
+                "{}{}{} {}",
+                prefix,
+                branch,
+                child.fraction,
+                child.node.label()
+            ));
+            child
+                .node
+                .write_children(&format!("{}{}", prefix, indent), lines);
+        }
+    }
+}
+
+impl<T, M> SplitTree<T, M> {
+    /// the weight indices of the leaves, from the start edges on
+    pub fn leaves(&self) -> Vec<usize> {
+        match self {
+            SplitTree::Leaf { index, .. } => vec![*index],
+            SplitTree::Cut { children, .. } => {
+                children.iter().flat_map(|c| c.node.leaves()).collect()
+            }
+        }
+    }
+
+    pub fn leaf_count(&self) -> usize {
+        match self {
+            SplitTree::Leaf { .. } => 1,
+            SplitTree::Cut { children, .. } => children.iter().map(|c| c.node.leaf_count()).sum(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::area::Area;
+
+    #[test]
+    fn test_slices() {
+        let tree = SplitTree::slices(&[1.0, 3.0], Axis::Vertical);
+        assert_eq!(
+            tree,
+            SplitTree::cut(
+                Axis::Vertical,
+                vec![
+                    SplitChild {
+                        fraction: 0.25,
+                        node: SplitTree::leaf(0)
+                    },
+                    SplitChild {
+                        fraction: 0.75,
+                        node: SplitTree::leaf(1)
+                    },
+                ]
+            )
+        );
+        assert_eq!(tree.leaves(), vec![0, 1]);
+        assert_eq!(tree.leaf_count(), 2);
+    }
+
+    #[test]
+    fn test_strips() {
+        let weights = [2.0, 2.0, 1.0, 3.0];
+        let groups = vec![vec![0, 1], vec![2, 3]];
+        let tree = SplitTree::strips(&weights, &groups, Axis::Vertical, EmissionOrder::Forward);
+        let SplitTree::Cut { axis, children, .. } = &tree else {
+            panic!("not a cut");
+        };
+        assert_eq!(*axis, Axis::Vertical);
+        assert_eq!(children[0].fraction, 0.5);
+        assert_eq!(
+            children[1].node,
+            SplitTree::cut(
+                Axis::Horizontal,
+                vec![
+                    SplitChild {
+                        fraction: 0.25,
+                        node: SplitTree::leaf(2)
+                    },
+                    SplitChild {
+                        fraction: 0.75,
+                        node: SplitTree::leaf(3)
+                    },
+                ]
+            )
+        );
+        assert_eq!(tree.leaves(), vec![0, 1, 2, 3]);
+
+        let tree = SplitTree::strips(
+            &weights,
+            &groups,
+            Axis::Vertical,
+            EmissionOrder::Boustrophedon,
+        );
+        assert_eq!(tree.leaves(), vec![0, 1, 3, 2]);
+        let tree = SplitTree::strips(&weights, &groups, Axis::Vertical, EmissionOrder::Reverse);
+        assert_eq!(tree.leaves(), vec![2, 3, 0, 1]);
+    }
+
+    #[test]
+    fn test_strip_summaries() {
+        let rect = AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(8.0, 4.0));
+        let weights = [2.0, 2.0, 1.0, 3.0];
+        let groups = vec![vec![0, 1], vec![2, 3]];
+        let tree = SplitTree::strips(&weights, &groups, Axis::Vertical, EmissionOrder::Reverse);
+        let summaries = tree.strip_summaries(&rect, &weights);
+        // in position order: the reversed second strip comes first
+        assert_eq!(summaries.len(), 2);
+        assert_eq!(summaries[0].members, vec![2, 3]);
+        assert_eq!(summaries[0].weight, 4.0);
+        assert_eq!(
+            summaries[0].rect,
+            AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(4.0, 4.0))
+        );
+        assert_eq!(summaries[1].members, vec![0, 1]);
+        assert_eq!(summaries[1].rect.x(), 4.0);
+        assert_eq!((summaries[1].id, summaries[1].kind), (1, GroupKind::Strip));
+
+        let leaf = SplitTree::<f64>::leaf(0).strip_summaries(&rect, &[5.0]);
+        assert_eq!(leaf.len(), 1);
+        assert_eq!(leaf[0].rect, rect);
+        assert_eq!(leaf[0].weight, 5.0);
+    }
+
+    #[test]
+    fn test_materialize() {
+        let rect = AxisAlignedRectangle::new(&Point::new(3.0, 5.0), &Rectangle::new(300.0, 200.0));
+        let weights: Vec<f64> = vec![4.0, 3.0, 2.0, 1.0, 1.0];
+        for order in [
+            EmissionOrder::Forward,
+            EmissionOrder::Boustrophedon,
+            EmissionOrder::Reverse,
+            EmissionOrder::CenterOut,
+        ] {
+            for axis in [Axis::Vertical, Axis::Horizontal] {
+                let (divided, tree) = rect.divide_strips_with_tree(&weights, axis, 1.0, order);
+                let layout = tree.materialize(&rect);
+                assert_eq!(layout.len(), divided.len());
+                for (m, d) in layout.iter().zip(divided.iter()) {
+                    assert!((m.x() - d.x()).abs() < 1e-9);
+                    assert!((m.y() - d.y()).abs() < 1e-9);
+                    assert!((m.width() - d.width()).abs() < 1e-9);
+                    assert!((m.height() - d.height()).abs() < 1e-9);
+                }
+            }
+        }
+
+        let tree = SplitTree::slices(&weights, Axis::Vertical);
+        assert_eq!(
+            tree.materialize(&rect).into_cells(),
+            rect.divide_by_weights_and_axis(&weights, Axis::Vertical)
+        );
+    }
+
+    #[test]
+    fn test_materialize_with() {
+        let tree = SplitTree::slices(&[1.0, 2.0], Axis::Vertical);
+        let small = AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(10.0, 10.0));
+        let mut indices = vec![];
+        let layout = tree.materialize_with(&small, |index, cell| {
+            indices.push(index);
+            cell.shrink_clamped(1.0).round()
+        });
+        assert_eq!(indices, vec![0, 1]);
+        assert_eq!(
+            layout.cells(),
+            &[
+                AxisAlignedRectangle::new(&Point::new(1.0, 1.0), &Rectangle::new(1.0, 8.0)),
+                AxisAlignedRectangle::new(&Point::new(5.0, 1.0), &Rectangle::new(4.0, 8.0)),
+            ]
+        );
+        // the same structure at another zoom level
+        let large = AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(30.0, 30.0));
+        assert_eq!(tree.materialize(&large).cells()[1].width(), 20.0);
+    }
+
+    #[test]
+    fn test_cut_lines() {
+        let rect = AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(4.0, 4.0));
+        let weights = [2.0, 2.0, 1.0, 3.0];
+        let groups = vec![vec![0, 1], vec![2, 3]];
+        let tree = SplitTree::strips(&weights, &groups, Axis::Vertical, EmissionOrder::Forward);
+        let lines = tree.cut_lines(&rect);
+        assert_eq!(
+            lines,
+            vec![
+                CutLine {
+                    id: CutId {
+                        node: vec![],
+                        boundary: 0
+                    },
+                    axis: Axis::Vertical,
+                    position: 2.0,
+                    start: 0.0,
+                    end: 4.0,
+                    before: vec![0, 1],
+                    after: vec![2, 3],
+                },
+                CutLine {
+                    id: CutId {
+                        node: vec![0],
+                        boundary: 0
+                    },
+                    axis: Axis::Horizontal,
+                    position: 2.0,
+                    start: 0.0,
+                    end: 2.0,
+                    before: vec![0],
+                    after: vec![1],
+                },
+                CutLine {
+                    id: CutId {
+                        node: vec![1],
+                        boundary: 0
+                    },
+                    axis: Axis::Horizontal,
+                    position: 1.0,
+                    start: 2.0,
+                    end: 4.0,
+                    before: vec![2],
+                    after: vec![3],
+                },
+            ]
+        );
+        // the ids stay, the positions follow the container
+        let large = AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(8.0, 8.0));
+        let large_lines = tree.cut_lines(&large);
+        assert_eq!(large_lines[2].id, lines[2].id);
+        assert_eq!(large_lines[2].position, 2.0);
+        assert_eq!(tree.materialize(&large).cut_lines(), large_lines);
+        assert!(SplitTree::<f64>::leaf(0).cut_lines(&rect).is_empty());
+    }
+
+    #[test]
+    fn test_to_ascii_tree() {
+        let weights = [2.0, 2.0, 1.0, 3.0];
+        let groups = vec![vec![0, 1], vec![2, 3]];
+        let tree = SplitTree::strips(&weights, &groups, Axis::Vertical, EmissionOrder::Forward);
+        assert_eq!(
+            tree.to_ascii_tree(),
+            [
+                "cut vertical",
+                "├── 0.5 cut horizontal",
+                "│   ├── 0.5 leaf 0",
+                "│   └── 0.5 leaf 1",
+                "└── 0.5 cut horizontal",
+                "    ├── 0.25 leaf 2",
+                "    └── 0.75 leaf 3",
+            ]
+            .join("\n")
+        );
+        assert_eq!(SplitTree::<f64>::leaf(7).to_ascii_tree(), "leaf 7");
+    }
+
+    #[test]
+    fn test_validate() {
+        let weights = [2.0, 2.0, 1.0, 3.0];
+        let groups = vec![vec![0, 1], vec![2, 3]];
+        let tree = SplitTree::strips(&weights, &groups, Axis::Vertical, EmissionOrder::Forward);
+        assert_eq!(tree.validate(4, 1e-9), Ok(()));
+        assert_eq!(
+            tree.validate(5, 1e-9),
+            Err(SplitTreeError::LeafCount {
+                expected: 5,
+                actual: 4
+            })
+        );
+
+        let leaf = |index| SplitChild {
+            fraction: 0.5,
+            node: SplitTree::leaf(index),
+        };
+        let cut = |children| SplitTree::cut(Axis::Vertical, children);
+        assert_eq!(
+            cut(vec![leaf(0), leaf(1), leaf(2)]).validate(3, 1e-9),
+            Err(SplitTreeError::FractionSum {
+                node: vec![],
+                sum: 1.5
+            })
+        );
+        assert_eq!(
+            cut(vec![leaf(0), leaf(0)]).validate(2, 1e-9),
+            Err(SplitTreeError::DuplicateLeaf { index: 0 })
+        );
+        assert_eq!(
+            cut(vec![leaf(0), leaf(2)]).validate(2, 1e-9),
+            Err(SplitTreeError::LeafOutOfRange { index: 2 })
+        );
+        let nested = cut(vec![
+            leaf(0),
+            SplitChild {
+                fraction: 0.5,
+                node: cut(vec![
+                    SplitChild {
+                        fraction: 0.0,
+                        node: SplitTree::leaf(1),
+                    },
+                    SplitChild {
+                        fraction: 1.0,
+                        node: SplitTree::leaf(2),
+                    },
+                ]),
+            },
+        ]);
+        let error = nested.validate(3, 1e-9).unwrap_err();
+        assert_eq!(error, SplitTreeError::DegenerateChild { node: vec![1, 0] });
+        assert_eq!(error.to_string(), "node [1, 0] has no positive fraction");
+        assert_eq!(
+            cut(vec![]).validate(0, 1e-9),
+            Err(SplitTreeError::EmptyCut { node: vec![] })
+        );
+    }
+
+    #[test]
+    fn test_edits() {
+        let rect = AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(4.0, 4.0));
+        let weights: [f64; 4] = [2.0, 2.0, 1.0, 3.0];
+        let groups = vec![vec![0, 1], vec![2, 3]];
+        let original = SplitTree::strips(&weights, &groups, Axis::Vertical, EmissionOrder::Forward);
+
+        let mut tree = original.clone();
+        assert!(tree.swap_leaves(0, 3));
+        assert!(!tree.swap_leaves(0, 9));
+        assert_eq!(tree.leaves(), vec![3, 1, 2, 0]);
+        let before = original.materialize(&rect);
+        let after = tree.materialize(&rect);
+        assert_eq!(after.cells()[3], before.cells()[0]);
+        assert_eq!(after.cells()[0], before.cells()[3]);
+
+        let mut tree = original.clone();
+        assert_eq!(tree.set_axis(&[1], Axis::Vertical), Ok(()));
+        assert_eq!(
+            tree.set_axis(&[1, 0], Axis::Vertical),
+            Err(EditError::NotACut(vec![1, 0]))
+        );
+        assert_eq!(
+            tree.set_axis(&[2], Axis::Vertical),
+            Err(EditError::NoSuchNode(vec![2]))
+        );
+        let cells = tree.materialize(&rect);
+        assert_eq!(
+            cells.cells()[3],
+            AxisAlignedRectangle::new(&Point::new(2.5, 0.0), &Rectangle::new(1.5, 4.0))
+        );
+
+        // move the leaf of weight 3 into the first column, after the leaf of weight 0
+        let mut tree = original.clone();
+        assert_eq!(tree.move_subtree(&[1, 1], &[0], 1), Ok(()));
+        assert_eq!(tree.leaves(), vec![0, 3, 1, 2]);
+        assert_eq!(tree.validate(4, Tolerance::default()), Ok(()));
+        tree.rebalance(&weights);
+        assert_eq!(tree.validate(4, 1e-9), Ok(()));
+        let layout = tree.materialize(&rect);
+        for (cell, weight) in layout.iter().zip(weights.iter()) {
+            assert!((cell.area() - 16.0 * weight / 8.0).abs() < 1e-9);
+        }
+
+        assert_eq!(tree.move_subtree(&[], &[0], 0), Err(EditError::InvalidMove));
+        // the path of the cut is the one after the subtree is taken out, and a failed move changes nothing
+        let unchanged = tree.clone();
+        assert_eq!(
+            tree.move_subtree(&[0], &[1], 0),
+            Err(EditError::NoSuchNode(vec![1]))
+        );
+        assert_eq!(tree, unchanged);
+        // the second column was left with one leaf, and replaced by it
+        assert_eq!(tree.node(&[1]), Some(&SplitTree::leaf(2)));
+        assert_eq!(
+            tree.move_subtree(&[0, 1], &[1], 0),
+            Err(EditError::NotACut(vec![1]))
+        );
+        assert_eq!(
+            tree.move_subtree(&[5], &[0], 0),
+            Err(EditError::NoSuchNode(vec![5]))
+        );
+
+        // the second column is [0] once the first one is taken out, and the root left with one child is replaced by it
+        let mut tree = original.clone();
+        assert_eq!(tree.move_subtree(&[0], &[0], 2), Ok(()));
+        assert_eq!(tree.leaves(), vec![2, 3, 0, 1]);
+        assert!(matches!(
+            &tree,
+            SplitTree::Cut { axis: Axis::Horizontal, children, .. } if children.len() == 3
+        ));
+        // a column left with one leaf is replaced by the leaf
+        let mut tree = original.clone();
+        assert_eq!(tree.move_subtree(&[0, 0], &[1], 0), Ok(()));
+        assert_eq!(tree.leaves(), vec![1, 0, 2, 3]);
+        assert_eq!(tree.node(&[0]), Some(&SplitTree::leaf(1)));
+        assert_eq!(tree.validate(4, Tolerance::default()), Ok(()));
+    }
+
+    #[test]
+    fn test_from_rects() {
+        let rect = AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(4.0, 4.0));
+        let weights = [2.0, 2.0, 1.0, 3.0];
+        let groups = vec![vec![0, 1], vec![2, 3]];
+        let tree = SplitTree::strips(&weights, &groups, Axis::Vertical, EmissionOrder::Forward);
+        let layout = tree.materialize(&rect);
+        assert_eq!(SplitTree::from_rects(&rect, layout.cells()), Ok(tree));
+
+        // a pinwheel tiles the container, but not by guillotine cuts
+        let r = |x: f64, y: f64, w: f64, h: f64| {
+            AxisAlignedRectangle::new(&Point::new(x, y), &Rectangle::new(w, h))
+        };
+        let square = r(0.0, 0.0, 3.0, 3.0);
+        let pinwheel = [
+            r(0.0, 0.0, 2.0, 1.0),
+            r(2.0, 0.0, 1.0, 2.0),
+            r(1.0, 2.0, 2.0, 1.0),
+            r(0.0, 1.0, 1.0, 2.0),
+            r(1.0, 1.0, 1.0, 1.0),
+        ];
+        assert_eq!(
+            SplitTree::from_rects(&square, &pinwheel),
+            Err(FromRectsError::NotGuillotine)
+        );
+        assert_eq!(
+            SplitTree::from_rects(&square, &pinwheel[..4]),
+            Err(FromRectsError::NotATiling)
+        );
+        assert_eq!(
+            SplitTree::from_rects(&square, &[r(0.0, 0.0, 3.0, 3.0), r(0.0, 0.0, 1.0, 1.0)]),
+            Err(FromRectsError::NotATiling)
+        );
+        assert_eq!(
+            SplitTree::from_rects(&square, &[]),
+            Err(FromRectsError::NotATiling)
+        );
+        // three columns in one cut
+        let columns = [
+            r(2.0, 0.0, 1.0, 3.0),
+            r(0.0, 0.0, 1.0, 3.0),
+            r(1.0, 0.0, 1.0, 3.0),
+        ];
+        let tree = SplitTree::from_rects(&square, &columns).unwrap();
+        assert_eq!(tree.leaves(), vec![1, 2, 0]);
+        assert_eq!(tree.cut_lines(&square).len(), 2);
+    }
+
+    #[test]
+    fn test_meta() {
+        let rect = AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(4.0, 4.0));
+        let mut tree = SplitTree::slices(&[1.0, 3.0], Axis::Vertical)
+            .map_meta(|()| serde_json::Value::Null)
+            .with_meta(serde_json::json!({ "name": "root" }));
+        if let Some(node) = tree.node_mut(&[1]) {
+            *node.meta_mut() = Some(serde_json::json!({ "color": "red", "collapsed": true }));
+        }
+        assert_eq!(tree.meta(), Some(&serde_json::json!({ "name": "root" })));
+        assert_eq!(
+            tree.leaf_meta(1).map(|m| &m["color"]),
+            Some(&serde_json::json!("red"))
+        );
+        assert_eq!(tree.leaf_meta(0), None);
+
+        // survives serialization
+        let json = serde_json::to_string(&tree).unwrap();
+        let restored: SplitTree<f64, serde_json::Value> = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, tree);
+        // nodes without metadata serialize without it
+        let plain = serde_json::to_value(SplitTree::<f64>::leaf(0)).unwrap();
+        assert_eq!(plain, serde_json::json!({ "Leaf": { "index": 0 } }));
+
+        // survives edits and materialization: the metadata moves with its leaf
+        assert!(tree.swap_leaves(0, 1));
+        assert_eq!(tree.node(&[0]).and_then(|n| n.meta()), tree.leaf_meta(1));
+        assert_eq!(
+            tree.leaf_meta(1).map(|m| &m["color"]),
+            Some(&serde_json::json!("red"))
+        );
+        assert_eq!(tree.leaf_meta(0), None);
+        let layout = tree.materialize(&rect);
+        assert_eq!(layout.tree().and_then(|t| t.meta()), tree.meta());
+    }
+}
@@ -0,0 +1,147 @@
+use num_traits::{Num, NumAssignOps, NumOps};
+
+use crate::axis_aligned_rectangle::AxisAlignedRectangle;
+use crate::component::Component;
+use crate::dividing::Dividing;
+use crate::point::Point;
+use crate::rectangle::{Rectangle, RectangleSize};
+
+/// An axis aligned square: the left top corner and the length of a side
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct Square<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps,
+{
+    origin: Point<T>,
+    side: T,
+}
+
+impl<T> Square<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps + PartialOrd,
+{
+    pub fn new(origin: &Point<T>, side: T) -> Self {
+        Self {
+            origin: *origin,
+            side,
+        }
+    }
+
+    pub fn origin(&self) -> Point<T> {
+        self.origin
+    }
+
+    pub fn side(&self) -> T {
+        self.side
+    }
+
+    pub fn area(&self) -> T {
+        self.side * self.side
+    }
+
+    pub fn to_rect(&self) -> AxisAlignedRectangle<T> {
+        AxisAlignedRectangle::new(&self.origin, &Rectangle::new(self.side, self.side))
+    }
+
+    /// the largest square in the rectangle, centered along its longer side
+    pub fn largest_in(rect: &AxisAlignedRectangle<T>) -> Self {
+        let two = T::one() + T::one();
+        let (width, height) = (rect.width(), rect.height());
+        match width > height {
+            true => Self::new(
+                &Point::new(rect.x() + (width - height) / two, rect.y()),
+                height,
+            ),
+            false => Self::new(
+                &Point::new(rect.x(), rect.y() + (height - width) / two),
+                width,
+            ),
+        }
+    }
+}
+
+impl<T> From<Square<T>> for AxisAlignedRectangle<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps + PartialOrd,
+{
+    fn from(square: Square<T>) -> Self {
+        square.to_rect()
+    }
+}
+
+/// Exact squares for the weights, and the fillers: the rest of the container that no square covers
+#[derive(Debug, PartialEq, Clone)]
+pub struct SquareLayout<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps,
+{
+    /// the n-th square corresponds to the n-th weight
+    pub squares: Vec<Square<T>>,
+    pub fillers: Vec<AxisAlignedRectangle<T>>,
+}
+
+impl<T> AxisAlignedRectangle<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps + PartialOrd + for<'a> std::iter::Sum<&'a T>,
+{
+    /// dividing a rectangle into exact squares for the weights: the container is divided into cells
+    /// aiming at squares (see `divide_vertical_then_horizontal_with_weights`), and each cell is cut down
+    /// to its largest centered square. the cut-off parts are returned as fillers, so that the squares and
+    /// the fillers together tile the container. squares are as large as their weights as far as the cells are square
+    pub fn divide_squares(&self, weights: &[T]) -> SquareLayout<T> {
+        let vertical_first = self.width() >= self.height();
+        let cells = match vertical_first {
+            true => self.divide_vertical_then_horizontal_with_weights(weights, T::one(), false),
+            false => self.divide_horizontal_then_vertical_with_weights(weights, T::one(), false),
+        };
+        let mut squares = Vec::with_capacity(cells.len());
+        let mut fillers = Vec::new();
+        for cell in &cells {
+            let square = Square::largest_in(cell);
+            fillers.extend(
+                cell.subtract(&square.to_rect())
+                    .into_iter()
+                    .filter(|filler| filler.width() > T::zero() && filler.height() > T::zero()),
+            );
+            squares.push(square);
+        }
+        SquareLayout { squares, fillers }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::verify_tiling;
+    use crate::tolerance::Tolerance;
+
+    #[test]
+    fn test_largest_in() {
+        let wide = AxisAlignedRectangle::new(&Point::new(1, 2), &Rectangle::new(10, 4));
+        assert_eq!(Square::largest_in(&wide), Square::new(&Point::new(4, 2), 4));
+        let tall = AxisAlignedRectangle::new(&Point::new(1, 2), &Rectangle::new(4, 10));
+        assert_eq!(Square::largest_in(&tall), Square::new(&Point::new(1, 5), 4));
+        assert_eq!(
+            AxisAlignedRectangle::from(Square::new(&Point::new(0, 0), 3)),
+            AxisAlignedRectangle::new(&Point::new(0, 0), &Rectangle::new(3, 3))
+        );
+    }
+
+    #[test]
+    fn test_divide_squares() {
+        let rect = AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(300.0, 200.0));
+        let weights = [4.0, 1.0, 1.0, 2.0, 3.0];
+        let layout = rect.divide_squares(&weights);
+        assert_eq!(layout.squares.len(), weights.len());
+        let mut tiles: Vec<AxisAlignedRectangle<f64>> =
+            layout.squares.iter().map(|s| s.to_rect()).collect();
+        tiles.extend(layout.fillers.iter().cloned());
+        assert_eq!(verify_tiling(&rect, &tiles, &Tolerance::default()), Ok(()));
+
+        // a grid of equal cells that are exactly square needs no fillers
+        let square = AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(2.0, 2.0));
+        let layout = square.divide_squares(&[1.0; 4]);
+        assert!(layout.fillers.is_empty());
+        assert!(layout.squares.iter().all(|s| s.side() == 1.0));
+    }
+}
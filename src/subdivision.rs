@@ -0,0 +1,157 @@
+use num_traits::{Float, Num, NumAssignOps, NumOps};
+
+use crate::axis::{Axis, SizeForAxis};
+use crate::axis_aligned_rectangle::AxisAlignedRectangle;
+use crate::dividing::Dividing;
+
+/// Where a rectangle is cut, as the share of the first part
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum SubdivisionRatio<T> {
+    /// the first part is 1 / φ (about 0.618), so the parts relate like the whole to the first part
+    Golden,
+    /// the first part is a third
+    Thirds,
+    /// two equal parts
+    Halves,
+    /// the first part is the given share (between 0 and 1)
+    Custom(T),
+}
+
+impl<T> SubdivisionRatio<T>
+where
+    T: Float,
+{
+    /// the share of the first part
+    pub fn ratio(&self) -> T {
+        let one = T::one();
+        let two = one + one;
+        match self {
+            SubdivisionRatio::Golden => two / (one + (two + two + one).sqrt()),
+            SubdivisionRatio::Thirds => one / (two + one),
+            SubdivisionRatio::Halves => one / two,
+            SubdivisionRatio::Custom(ratio) => *ratio,
+        }
+    }
+}
+
+/// Which parts are cut again at the next depth
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum Recursion {
+    /// both parts, for 2^depth cells
+    #[default]
+    All,
+    /// only the smaller part, like the golden spiral: one cell per depth plus the last remainder
+    Smaller,
+    /// only the larger part
+    Larger,
+}
+
+impl<T> AxisAlignedRectangle<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps + Float,
+{
+    /// cutting a rectangle at the ratio, then cutting the parts again with the other axis, down to the depth.
+    /// returns the cells that are not cut any further, in depth-first order (the first part before the second)
+    pub fn subdivide_by_ratio(
+        &self,
+        ratio: SubdivisionRatio<T>,
+        depth: usize,
+        first_axis: Axis,
+        recursion: Recursion,
+    ) -> Vec<Self> {
+        let mut cells = Vec::new();
+        self.subdivide(ratio.ratio(), depth, first_axis, recursion, &mut cells);
+        cells
+    }
+
+    fn subdivide(
+        &self,
+        ratio: T,
+        depth: usize,
+        axis: Axis,
+        recursion: Recursion,
+        cells: &mut Vec<Self>,
+    ) {
+        if depth == 0 {
+            cells.push(self.clone());
+            return;
+        }
+        let (first, second) = self.divide(self.size_for_axis(axis) * ratio, axis);
+        let first_is_larger = ratio * (T::one() + T::one()) >= T::one();
+        let (cut_first, cut_second) = match recursion {
+            Recursion::All => (true, true),
+            Recursion::Larger => (first_is_larger, !first_is_larger),
+            Recursion::Smaller => (!first_is_larger, first_is_larger),
+        };
+        for (part, cut) in [(first, cut_first), (second, cut_second)] {
+            match cut {
+                true => part.subdivide(ratio, depth - 1, axis.opposite(), recursion, cells),
+                false => cells.push(part),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::component::Component;
+    use crate::point::Point;
+    use crate::rectangle::{Rectangle, RectangleSize};
+
+    #[test]
+    fn test_ratio() {
+        assert!((SubdivisionRatio::<f64>::Golden.ratio() - 0.6180339887498949).abs() < 1e-12);
+        assert!((SubdivisionRatio::<f64>::Thirds.ratio() - 1.0 / 3.0).abs() < 1e-12);
+        assert_eq!(SubdivisionRatio::<f64>::Halves.ratio(), 0.5);
+        assert_eq!(SubdivisionRatio::Custom(0.25).ratio(), 0.25);
+    }
+
+    #[test]
+    fn test_subdivide_all() {
+        let rect = AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(8.0, 8.0));
+        let cells =
+            rect.subdivide_by_ratio(SubdivisionRatio::Halves, 2, Axis::Vertical, Recursion::All);
+        assert_eq!(cells.len(), 4);
+        assert!(cells.iter().all(|c| c.width() == 4.0 && c.height() == 4.0));
+        assert_eq!((cells[1].x(), cells[1].y()), (0.0, 4.0));
+        assert_eq!(
+            rect.subdivide_by_ratio(SubdivisionRatio::Halves, 0, Axis::Vertical, Recursion::All),
+            vec![rect.clone()]
+        );
+    }
+
+    #[test]
+    fn test_subdivide_golden_spiral() {
+        let phi = (1.0 + 5.0.sqrt()) / 2.0;
+        let rect = AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(phi, 1.0));
+        let cells = rect.subdivide_by_ratio(
+            SubdivisionRatio::Golden,
+            4,
+            Axis::Vertical,
+            Recursion::Smaller,
+        );
+        assert_eq!(cells.len(), 5);
+        // a golden rectangle splits into a square and a smaller golden rectangle
+        for cell in &cells[..4] {
+            assert!((cell.width() - cell.height()).abs() < 1e-12);
+        }
+        let area: f64 = cells.iter().map(|c| c.width() * c.height()).sum();
+        assert!((area - phi).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_subdivide_thirds() {
+        let rect = AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(9.0, 9.0));
+        let cells = rect.subdivide_by_ratio(
+            SubdivisionRatio::Thirds,
+            2,
+            Axis::Horizontal,
+            Recursion::Larger,
+        );
+        assert_eq!(cells.len(), 3);
+        assert!((cells[0].height() - 3.0).abs() < 1e-12);
+        assert!((cells[1].width() - 3.0).abs() < 1e-12);
+        assert!((cells[2].width() - 6.0).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,762 @@
+use num_traits::{Float, Num, NumAssignOps, NumOps, ToPrimitive};
+
+use crate::axis_aligned_rectangle::AxisAlignedRectangle;
+use crate::component::Component;
+use crate::point::Point;
+use crate::rectangle::Rectangle;
+use crate::rectangle::RectangleSize;
+use crate::tolerance::{DefaultTolerance, Tolerance};
+use crate::weight::normalize_weights;
+
+/// how far the aspect ratio of a cell may be from the target in `assert_respect_aspect_ratio` and `DebugDivider`,
+/// multiplied by the normalized weight of the cell so that small cells may be further off.
+/// strip layouts only aim at the target, so this is a bound on their quality rather than on float errors
+pub fn aspect_ratio_tolerance<T>() -> Tolerance<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps + Float,
+{
+    Tolerance::absolute(T::from(0.5).unwrap_or_else(T::one))
+}
+
+/// assert that there is a rectangle for each weight, and that each covers the share of the container's area of its weight.
+/// the areas and the shares are compared within the tolerance
+pub fn assert_weights_dividing_within<T, D>(
+    original: &D,
+    divided: &[D],
+    weights: &[T],
+    tolerance: &Tolerance<T>,
+) where
+    D: RectangleSize<T>,
+    T: Copy + std::fmt::Debug + Num + NumAssignOps + NumOps + Float + for<'a> std::iter::Sum<&'a T>,
+{
+    let area = |r: &D| r.width() * r.height();
+    assert_eq!(divided.len(), weights.len());
+
+    let original_area = area(original);
+    let divided_areas: Vec<T> = divided.iter().map(area).collect();
+    let divided_area: T = divided_areas.iter().sum();
+    assert!(
+        tolerance.approx_eq(original_area, divided_area),
+        "divided area {:?} != original area {:?}",
+        divided_area,
+        original_area
+    );
+    let normalized_weights = normalize_weights(weights);
+    let area_shares = normalize_weights(&divided_areas);
+    for (i, (w, share)) in normalized_weights
+        .iter()
+        .zip(area_shares.iter())
+        .enumerate()
+    {
+        assert!(
+            tolerance.approx_eq(*w, *share),
+            "rectangle {} covers {:?} of the area for the weight {:?}",
+            i,
+            share,
+            w
+        );
+    }
+}
+
+/// assert that all the rectangles are inside the container and no two of them overlap,
+/// where edges within the tolerance count as touching
+pub fn assert_no_overlaps_within<T>(
+    original: &AxisAlignedRectangle<T>,
+    divided: &[AxisAlignedRectangle<T>],
+    tolerance: &Tolerance<T>,
+) where
+    T: Copy + std::fmt::Debug + Num + NumAssignOps + NumOps + Float,
+{
+    let at_least = |a: T, b: T| a >= b || tolerance.approx_eq(a, b);
+    for (i, d) in divided.iter().enumerate() {
+        assert!(
+            at_least(d.x(), original.x())
+                && at_least(d.y(), original.y())
+                && at_least(original.x() + original.width(), d.x() + d.width())
+                && at_least(original.y() + original.height(), d.y() + d.height()),
+            "rectangle {} {:?} is outside {:?}",
+            i,
+            d,
+            original
+        );
+    }
+    for (i, d1) in divided.iter().enumerate() {
+        for (j, d2) in divided.iter().enumerate().skip(i + 1) {
+            if let Some(overlap) = d1.intersection(d2) {
+                assert!(
+                    tolerance.approx_eq(overlap.width(), T::zero())
+                        || tolerance.approx_eq(overlap.height(), T::zero()),
+                    "rectangles {} {:?} and {} {:?} overlap",
+                    i,
+                    d1,
+                    j,
+                    d2
+                );
+            }
+        }
+    }
+}
+
+/// assert that the rectangles are close to the aspect ratio: the difference multiplied by the normalized weight
+/// must be within the tolerance, which lets rectangles of smaller weights be further off
+pub fn assert_respect_aspect_ratio_within<T>(
+    divided: &[AxisAlignedRectangle<T>],
+    weights: &[T],
+    aspect_ratio: T,
+    tolerance: &Tolerance<T>,
+) where
+    T: Copy + std::fmt::Debug + Num + NumAssignOps + NumOps + Float + for<'a> std::iter::Sum<&'a T>,
+{
+    let normalized_weights = normalize_weights(weights);
+    for (i, (d, w)) in divided.iter().zip(normalized_weights.iter()).enumerate() {
+        let actual = d.width() / d.height();
+        let diff = (actual - aspect_ratio).abs();
+        assert!(
+            tolerance.approx_eq(diff * *w, T::zero()),
+            "rectangle {} has the aspect ratio {:?} for the target {:?}",
+            i,
+            actual,
+            aspect_ratio
+        );
+    }
+}
+
+/// `assert_weights_dividing_within` the default tolerance of the type
+pub fn assert_weights_dividing<T, D>(original: &D, divided: &[D], weights: &[T])
+where
+    D: RectangleSize<T>,
+    T: Copy
+        + std::fmt::Debug
+        + Num
+        + NumAssignOps
+        + NumOps
+        + Float
+        + DefaultTolerance
+        + for<'a> std::iter::Sum<&'a T>,
+{
+    assert_weights_dividing_within(original, divided, weights, &Tolerance::default())
+}
+
+/// `assert_no_overlaps_within` the default tolerance of the type
+pub fn assert_no_overlaps<T>(
+    original: &AxisAlignedRectangle<T>,
+    divided: &[AxisAlignedRectangle<T>],
+) where
+    T: Copy + std::fmt::Debug + Num + NumAssignOps + NumOps + Float + DefaultTolerance,
+{
+    assert_no_overlaps_within(original, divided, &Tolerance::default())
+}
+
+/// `assert_respect_aspect_ratio_within` the `aspect_ratio_tolerance`
+pub fn assert_respect_aspect_ratio<T>(
+    divided: &[AxisAlignedRectangle<T>],
+    weights: &[T],
+    aspect_ratio: T,
+) where
+    T: Copy + std::fmt::Debug + Num + NumAssignOps + NumOps + Float + for<'a> std::iter::Sum<&'a T>,
+{
+    assert_respect_aspect_ratio_within(divided, weights, aspect_ratio, &aspect_ratio_tolerance())
+}
+
+/// The environment variable that makes `assert_golden` write the golden files instead of comparing with them
+pub const UPDATE_GOLDEN: &str = "UPDATE_GOLDEN";
+
+/// canonical text of the cells for golden files: a `index x y width height` line for each cell,
+/// rounded to the decimal places so that float noise does not show up as a change
+pub fn snapshot<T>(cells: &[AxisAlignedRectangle<T>], decimals: usize) -> String
+where
+    T: Copy + Num + NumAssignOps + NumOps + Float,
+{
+    let scale = 10f64.powi(decimals as i32);
+    // adding zero turns -0 into 0
+    let quantize = |v: T| (v.to_f64().unwrap_or(f64::NAN) * scale).round() / scale + 0.0;
+    cells
+        .iter()
+        .enumerate()
+        .map(|(i, c)| {
+            format!(
+                "{} {:.*} {:.*} {:.*} {:.*}\n",
+                i,
+                decimals,
+                quantize(c.x()),
+                decimals,
+                quantize(c.y()),
+                decimals,
+                quantize(c.width()),
+                decimals,
+                quantize(c.height())
+            )
+        })
+        .collect()
+}
+
+/// the differences between two snapshots, a line for each changed, added or removed cell
+/// (e.g. `cell 2: x 10.00 -> 12.50 (+2.50)`). `None` when they are the same
+pub fn golden_diff(expected: &str, actual: &str) -> Option<String> {
+    let parse = |text: &str| -> Vec<Vec<String>> {
+        text.lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| line.split_whitespace().skip(1).map(String::from).collect())
+            .collect()
+    };
+    let (expected, actual) = (parse(expected), parse(actual));
+    let mut lines = Vec::new();
+    for i in 0..expected.len().max(actual.len()) {
+        match (expected.get(i), actual.get(i)) {
+            (Some(e), Some(a)) => {
+                for (name, (ev, av)) in ["x", "y", "width", "height"].iter().zip(e.iter().zip(a)) {
+                    if ev == av {
+                        continue;
+                    }
+                    let delta = match (ev.parse::<f64>(), av.parse::<f64>()) {
+                        (Ok(ev), Ok(av)) => format!(" ({:+})", av - ev),
+                        _ => String::new(),
+                    };
+                    lines.push(format!("cell {}: {} {} -> {}{}", i, name, ev, av, delta));
+                }
+            }
+            (Some(e), None) => lines.push(format!("cell {}: removed ({})", i, e.join(" "))),
+            (None, Some(a)) => lines.push(format!("cell {}: added ({})", i, a.join(" "))),
+            (None, None) => {}
+        }
+    }
+    match lines.is_empty() {
+        true => None,
+        false => Some(lines.join("\n")),
+    }
+}
+
+/// assert that the snapshot of the cells is the same as the golden file.
+/// with the `UPDATE_GOLDEN` environment variable set, the golden file is written instead
+pub fn assert_golden<T, P>(path: P, cells: &[AxisAlignedRectangle<T>], decimals: usize)
+where
+    T: Copy + Num + NumAssignOps + NumOps + Float,
+    P: AsRef<std::path::Path>,
+{
+    let path = path.as_ref();
+    let actual = snapshot(cells, decimals);
+    if std::env::var_os(UPDATE_GOLDEN).is_some() {
+        if let Some(dir) = path.parent() {
+            if let Err(e) = std::fs::create_dir_all(dir) {
+                panic!("cannot create {}: {}", dir.display(), e);
+            }
+        }
+        if let Err(e) = std::fs::write(path, &actual) {
+            panic!("cannot write {}: {}", path.display(), e);
+        }
+        return;
+    }
+    let expected = match std::fs::read_to_string(path) {
+        Ok(expected) => expected,
+        Err(e) => panic!(
+            "cannot read the golden file {} ({}). run with {}=1 to write it",
+            path.display(),
+            e,
+            UPDATE_GOLDEN
+        ),
+    };
+    if let Some(diff) = golden_diff(&expected, &actual) {
+        panic!(
+            "the cells differ from the golden file {}:\n{}",
+            path.display(),
+            diff
+        );
+    }
+}
+
+/// Why cells do not tile a container
+#[derive(Debug, PartialEq, Clone)]
+pub enum TilingError<T> {
+    /// the cell reaches outside the container
+    OutOfBounds { index: usize },
+    /// the cells overlap by more than the tolerance
+    Overlap { a: usize, b: usize },
+    /// the cells do not cover the area of the container
+    AreaMismatch { container: T, cells: T },
+}
+
+impl<T> std::fmt::Display for TilingError<T>
+where
+    T: std::fmt::Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TilingError::OutOfBounds { index } => {
+                write!(f, "cell {} reaches outside the container", index)
+            }
+            TilingError::Overlap { a, b } => write!(f, "cells {} and {} overlap", a, b),
+            TilingError::AreaMismatch { container, cells } => write!(
+                f,
+                "the cells cover an area of {}, the container {}",
+                cells, container
+            ),
+        }
+    }
+}
+
+impl<T> std::error::Error for TilingError<T> where T: std::fmt::Debug + std::fmt::Display {}
+
+/// check that the cells tile the container: each inside it, no two overlapping and together covering its area,
+/// every comparison within the tolerance
+pub fn verify_tiling<T>(
+    container: &AxisAlignedRectangle<T>,
+    cells: &[AxisAlignedRectangle<T>],
+    tolerance: &Tolerance<T>,
+) -> Result<(), TilingError<T>>
+where
+    T: Copy + Num + NumAssignOps + NumOps + PartialOrd + for<'a> std::iter::Sum<&'a T>,
+{
+    let at_least = |a: T, b: T| a >= b || tolerance.approx_eq(a, b);
+    let right = |r: &AxisAlignedRectangle<T>| r.x() + r.width();
+    let bottom = |r: &AxisAlignedRectangle<T>| r.y() + r.height();
+    for (index, cell) in cells.iter().enumerate() {
+        if !(at_least(cell.x(), container.x())
+            && at_least(cell.y(), container.y())
+            && at_least(right(container), right(cell))
+            && at_least(bottom(container), bottom(cell)))
+        {
+            return Err(TilingError::OutOfBounds { index });
+        }
+    }
+    for (a, cell_a) in cells.iter().enumerate() {
+        for (b, cell_b) in cells.iter().enumerate().skip(a + 1) {
+            let overlapping = cell_a.intersection(cell_b).is_some_and(|overlap| {
+                !tolerance.approx_eq(overlap.width(), T::zero())
+                    && !tolerance.approx_eq(overlap.height(), T::zero())
+            });
+            if overlapping {
+                return Err(TilingError::Overlap { a, b });
+            }
+        }
+    }
+    let areas: Vec<T> = cells.iter().map(|c| c.width() * c.height()).collect();
+    let cells_area: T = areas.iter().sum();
+    let container_area = container.width() * container.height();
+    if !tolerance.approx_eq(cells_area, container_area) {
+        return Err(TilingError::AreaMismatch {
+            container: container_area,
+            cells: cells_area,
+        });
+    }
+    Ok(())
+}
+
+/// Why cells do not cover the pixels of a container exactly once
+#[derive(Debug, PartialEq, Clone)]
+pub enum CoverageError {
+    /// a coordinate or size of the cell (or the container when `None`) is not a whole number
+    NotWhole { index: Option<usize> },
+    /// the cell reaches outside the container
+    OutOfBounds { index: usize },
+    /// `count` pixels are not covered by any cell, the first (in row-major order) at x, y
+    Uncovered { x: i64, y: i64, count: usize },
+    /// `count` pixels are covered by more than one cell, the first (in row-major order) at x, y
+    Overlapped { x: i64, y: i64, count: usize },
+}
+
+impl std::fmt::Display for CoverageError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CoverageError::NotWhole { index: Some(index) } => {
+                write!(f, "cell {} is not on whole pixels", index)
+            }
+            CoverageError::NotWhole { index: None } => {
+                write!(f, "the container is not on whole pixels")
+            }
+            CoverageError::OutOfBounds { index } => {
+                write!(f, "cell {} reaches outside the container", index)
+            }
+            CoverageError::Uncovered { x, y, count } => {
+                write!(
+                    f,
+                    "{} pixels are not covered, the first at ({}, {})",
+                    count, x, y
+                )
+            }
+            CoverageError::Overlapped { x, y, count } => write!(
+                f,
+                "{} pixels are covered more than once, the first at ({}, {})",
+                count, x, y
+            ),
+        }
+    }
+}
+
+impl std::error::Error for CoverageError {}
+
+/// rasterize the cells onto the pixels of the container and check that every pixel is covered exactly once.
+/// for integer layouts, or float layouts snapped to whole numbers
+pub fn verify_pixel_coverage<T>(
+    container: &AxisAlignedRectangle<T>,
+    cells: &[AxisAlignedRectangle<T>],
+) -> Result<(), CoverageError>
+where
+    T: Copy + Num + NumAssignOps + NumOps + ToPrimitive,
+{
+    // left, top, right, bottom
+    let pixels = |rect: &AxisAlignedRectangle<T>| -> Option<(i64, i64, i64, i64)> {
+        let whole = |v: T| v.to_f64().filter(|v| v.fract() == 0.0).map(|v| v as i64);
+        let (x, y) = (whole(rect.x())?, whole(rect.y())?);
+        Some((x, y, x + whole(rect.width())?, y + whole(rect.height())?))
+    };
+    let (left, top, right, bottom) =
+        pixels(container).ok_or(CoverageError::NotWhole { index: None })?;
+    let width = (right - left).max(0) as usize;
+    let height = (bottom - top).max(0) as usize;
+    let mut grid = vec![0u32; width * height];
+    for (index, cell) in cells.iter().enumerate() {
+        let (l, t, r, b) = pixels(cell).ok_or(CoverageError::NotWhole { index: Some(index) })?;
+        if l < left || t < top || r > right || b > bottom {
+            return Err(CoverageError::OutOfBounds { index });
+        }
+        for y in t..b {
+            let row = (y - top) as usize * width;
+            for x in l..r {
+                grid[row + (x - left) as usize] += 1;
+            }
+        }
+    }
+    let position = |i: usize| (left + (i % width) as i64, top + (i / width) as i64);
+    let first_and_count = |predicate: fn(u32) -> bool| {
+        let first = grid.iter().position(|c| predicate(*c))?;
+        Some((
+            position(first),
+            grid.iter().filter(|c| predicate(**c)).count(),
+        ))
+    };
+    if let Some(((x, y), count)) = first_and_count(|c| c == 0) {
+        return Err(CoverageError::Uncovered { x, y, count });
+    }
+    if let Some(((x, y), count)) = first_and_count(|c| c > 1) {
+        return Err(CoverageError::Overlapped { x, y, count });
+    }
+    Ok(())
+}
+
+/// assert that `verify_pixel_coverage` passes
+pub fn assert_pixel_coverage<T>(
+    container: &AxisAlignedRectangle<T>,
+    cells: &[AxisAlignedRectangle<T>],
+) where
+    T: Copy + Num + NumAssignOps + NumOps + ToPrimitive,
+{
+    if let Err(e) = verify_pixel_coverage(container, cells) {
+        panic!("{}", e);
+    }
+}
+
+/// A small deterministic PRNG (SplitMix64), so that generated inputs are the same everywhere for the same seed
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// uniform in [0, 1)
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// uniform in [min, max)
+    pub fn range(&mut self, min: f64, max: f64) -> f64 {
+        min + (max - min) * self.next_f64()
+    }
+}
+
+/// How generated weights are distributed
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Distribution {
+    /// every weight between min and max is as likely
+    Uniform { min: f64, max: f64 },
+    /// mostly small weights and a few large ones
+    Exponential { mean: f64 },
+    /// a few dominating weights and a long tail, like file sizes or populations.
+    /// smaller exponents make the tail heavier
+    Pareto { min: f64, exponent: f64 },
+}
+
+impl Default for Distribution {
+    fn default() -> Self {
+        Distribution::Uniform {
+            min: 1.0,
+            max: 10.0,
+        }
+    }
+}
+
+impl Distribution {
+    pub fn sample(&self, rng: &mut Rng) -> f64 {
+        // 1 - [0, 1) is in (0, 1], so the logarithm is finite
+        let unit = 1.0 - rng.next_f64();
+        match *self {
+            Distribution::Uniform { min, max } => rng.range(min, max),
+            Distribution::Exponential { mean } => -mean * unit.ln(),
+            Distribution::Pareto { min, exponent } => min / unit.powf(1.0 / exponent),
+        }
+    }
+}
+
+/// n weights from the distribution, the same for the same seed
+pub fn random_weights(seed: u64, n: usize, distribution: Distribution) -> Vec<f64> {
+    let mut rng = Rng::new(seed);
+    (0..n).map(|_| distribution.sample(&mut rng)).collect()
+}
+
+/// a container with whole number coordinates: the origin within ±1000 and each side from 1 to 2000,
+/// the same for the same seed
+pub fn random_container(seed: u64) -> AxisAlignedRectangle<f64> {
+    let mut rng = Rng::new(seed);
+    let mut whole = |min: f64, max: f64| rng.range(min, max + 1.0).floor();
+    let x = whole(-1000.0, 1000.0);
+    let y = whole(-1000.0, 1000.0);
+    let width = whole(1.0, 2000.0);
+    let height = whole(1.0, 2000.0);
+    AxisAlignedRectangle::new(&Point::new(x, y), &Rectangle::new(width, height))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::deterministic::quantize_rect;
+    use crate::dividing::Dividing;
+
+    #[test]
+    fn test_assertions_pass_for_divided() {
+        let rect = AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(600.0, 400.0));
+        let weights = [3.0, 2.0, 1.0, 1.0];
+        let divided = rect.divide_vertical_then_horizontal_with_weights(&weights, 1.5, false);
+        assert_weights_dividing(&rect, &divided, &weights);
+        assert_no_overlaps(&rect, &divided);
+        assert_respect_aspect_ratio(&divided, &weights, 1.5);
+    }
+
+    #[test]
+    #[should_panic(expected = "overlap")]
+    fn test_assert_no_overlaps_fails() {
+        let rect = AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(10.0, 10.0));
+        let divided = [
+            AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(6.0, 10.0)),
+            AxisAlignedRectangle::new(&Point::new(5.0, 0.0), &Rectangle::new(5.0, 10.0)),
+        ];
+        assert_no_overlaps(&rect, &divided);
+    }
+
+    #[test]
+    fn test_configurable_tolerances() {
+        let rect = AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(10.0, 10.0));
+        let divided = [
+            AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(6.0, 10.0)),
+            AxisAlignedRectangle::new(&Point::new(5.0, 0.0), &Rectangle::new(5.0, 10.0)),
+        ];
+        assert_no_overlaps_within(&rect, &divided, &Tolerance::absolute(1.0));
+
+        let divided = [
+            AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(6.0, 10.0)),
+            AxisAlignedRectangle::new(&Point::new(6.0, 0.0), &Rectangle::new(4.0, 10.0)),
+        ];
+        let weights = [1.0, 1.0];
+        assert_weights_dividing_within(&rect, &divided, &weights, &Tolerance::absolute(0.1));
+        let result = std::panic::catch_unwind(|| {
+            assert_weights_dividing(&rect, &divided, &weights);
+        });
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_snapshot() {
+        let cells = [
+            AxisAlignedRectangle::new(&Point::new(-0.0001, 0.0), &Rectangle::new(1.0 / 3.0, 2.0)),
+            AxisAlignedRectangle::new(&Point::new(1.0 / 3.0, 0.0), &Rectangle::new(2.5, 2.0)),
+        ];
+        assert_eq!(
+            snapshot(&cells, 2),
+            "0 0.00 0.00 0.33 2.00\n1 0.33 0.00 2.50 2.00\n"
+        );
+    }
+
+    #[test]
+    fn test_golden_diff() {
+        let expected = "0 0.00 0.00 5.00 2.00\n1 5.00 0.00 5.00 2.00\n";
+        assert_eq!(golden_diff(expected, expected), None);
+        let actual = "0 0.00 0.00 6.00 2.00\n1 6.00 0.00 4.00 2.00\n2 0.00 2.00 1.00 1.00\n";
+        assert_eq!(
+            golden_diff(expected, actual),
+            Some(
+                [
+                    "cell 0: width 5.00 -> 6.00 (+1)",
+                    "cell 1: x 5.00 -> 6.00 (+1)",
+                    "cell 1: width 5.00 -> 4.00 (-1)",
+                    "cell 2: added (0.00 2.00 1.00 1.00)",
+                ]
+                .join("\n")
+            )
+        );
+    }
+
+    #[test]
+    fn test_assert_golden() {
+        let rect = AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(600.0, 400.0));
+        let weights = [3.0, 2.0, 1.0, 1.0, 1.0];
+        let divided = rect.divide_vertical_then_horizontal_with_weights(&weights, 1.5, false);
+        let golden = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("testdata/golden/divide_vertical_then_horizontal.txt");
+        assert_golden(golden, &divided, 3);
+    }
+
+    #[test]
+    fn test_random_weights() {
+        for distribution in [
+            Distribution::default(),
+            Distribution::Exponential { mean: 2.0 },
+            Distribution::Pareto {
+                min: 1.0,
+                exponent: 1.5,
+            },
+        ] {
+            let weights = random_weights(42, 100, distribution);
+            assert_eq!(weights.len(), 100);
+            assert_eq!(weights, random_weights(42, 100, distribution));
+            assert_ne!(weights, random_weights(43, 100, distribution));
+            assert!(weights.iter().all(|w| w.is_finite() && *w > 0.0));
+        }
+        let uniform = random_weights(7, 1000, Distribution::Uniform { min: 2.0, max: 3.0 });
+        assert!(uniform.iter().all(|w| (2.0..3.0).contains(w)));
+        let pareto = random_weights(
+            7,
+            1000,
+            Distribution::Pareto {
+                min: 1.0,
+                exponent: 1.5,
+            },
+        );
+        assert!(pareto.iter().all(|w| *w >= 1.0));
+    }
+
+    #[test]
+    fn test_random_container() {
+        for seed in 0..100 {
+            let rect = random_container(seed);
+            assert_eq!(rect, random_container(seed));
+            assert!((1.0..=2000.0).contains(&rect.width()));
+            assert!((1.0..=2000.0).contains(&rect.height()));
+            assert!((-1000.0..=1000.0).contains(&rect.x()));
+            assert_eq!(rect.x().fract(), 0.0);
+            let weights = random_weights(seed, 20, Distribution::default());
+            let divided = rect.divide_vertical_then_horizontal_with_weights(&weights, 1.0, false);
+            assert_weights_dividing(&rect, &divided, &weights);
+            assert_no_overlaps(&rect, &divided);
+        }
+    }
+
+    #[test]
+    fn test_verify_pixel_coverage() {
+        let rect = AxisAlignedRectangle::new(&Point::new(-3, 2), &Rectangle::new(5, 4));
+        let cells = [
+            AxisAlignedRectangle::new(&Point::new(-3, 2), &Rectangle::new(2, 4)),
+            AxisAlignedRectangle::new(&Point::new(-1, 2), &Rectangle::new(3, 1)),
+            AxisAlignedRectangle::new(&Point::new(-1, 3), &Rectangle::new(3, 3)),
+        ];
+        assert_eq!(verify_pixel_coverage(&rect, &cells), Ok(()));
+        assert_eq!(
+            verify_pixel_coverage(&rect, &cells[..2]),
+            Err(CoverageError::Uncovered {
+                x: -1,
+                y: 3,
+                count: 9
+            })
+        );
+        let overlapping = [
+            cells[0].clone(),
+            AxisAlignedRectangle::new(&Point::new(-2, 2), &Rectangle::new(4, 4)),
+        ];
+        assert_eq!(
+            verify_pixel_coverage(&rect, &overlapping),
+            Err(CoverageError::Overlapped {
+                x: -2,
+                y: 2,
+                count: 4
+            })
+        );
+        let outside = [AxisAlignedRectangle::new(
+            &Point::new(-3, 2),
+            &Rectangle::new(6, 4),
+        )];
+        assert_eq!(
+            verify_pixel_coverage(&rect, &outside),
+            Err(CoverageError::OutOfBounds { index: 0 })
+        );
+        let fractional =
+            AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(2.5, 1.0));
+        assert_eq!(
+            verify_pixel_coverage(&fractional, &[]),
+            Err(CoverageError::NotWhole { index: None })
+        );
+    }
+
+    #[test]
+    fn test_verify_pixel_coverage_of_quantized() {
+        for seed in 0..5 {
+            let rect = random_container(seed);
+            let weights = random_weights(seed, 10, Distribution::default());
+            let divided: Vec<_> = rect
+                .divide_vertical_then_horizontal_with_weights(&weights, 1.0, false)
+                .iter()
+                .map(|cell| quantize_rect(cell, 1.0))
+                .collect();
+            assert_pixel_coverage(&rect, &divided);
+        }
+    }
+
+    #[test]
+    fn test_verify_tiling() {
+        let rect = AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(600.0, 400.0));
+        let weights = [3.0, 2.0, 1.0, 1.0, 1.0];
+        let divided = rect.divide_vertical_then_horizontal_with_weights(&weights, 1.5, false);
+        assert_eq!(
+            verify_tiling(&rect, &divided, &Tolerance::default()),
+            Ok(())
+        );
+        assert_eq!(
+            verify_tiling(&rect, &divided[1..], &Tolerance::default()),
+            Err(TilingError::AreaMismatch {
+                container: 240000.0,
+                cells: 150000.0
+            })
+        );
+
+        let nudged = [
+            AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(5.001, 4.0)),
+            AxisAlignedRectangle::new(&Point::new(5.0, 0.0), &Rectangle::new(5.0, 4.0)),
+        ];
+        let container =
+            AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(10.0, 4.0));
+        assert_eq!(
+            verify_tiling(&container, &nudged, &Tolerance::default()),
+            Err(TilingError::Overlap { a: 0, b: 1 })
+        );
+        assert_eq!(
+            verify_tiling(&container, &nudged, &Tolerance::new(0.01, 0.001)),
+            Ok(())
+        );
+        let outside = [AxisAlignedRectangle::new(
+            &Point::new(-1.0, 0.0),
+            &Rectangle::new(11.0, 4.0),
+        )];
+        assert_eq!(
+            verify_tiling(&container, &outside, &Tolerance::default()),
+            Err(TilingError::OutOfBounds { index: 0 })
+        );
+    }
+}
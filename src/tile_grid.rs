@@ -0,0 +1,130 @@
+use num_traits::{Num, NumAssignOps, NumCast, NumOps};
+
+use crate::axis_aligned_rectangle::AxisAlignedRectangle;
+use crate::component::Component;
+use crate::point::Point;
+use crate::rectangle::{Rectangle, RectangleSize};
+
+/// Whole tiles of a fixed size in a grid centered in a container, and the margins around it
+#[derive(Debug, PartialEq, Clone)]
+pub struct TileGrid<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps,
+{
+    pub rows: usize,
+    pub cols: usize,
+    /// row-major: the tile at (row, col) is `tiles[row * cols + col]`
+    pub tiles: Vec<AxisAlignedRectangle<T>>,
+    /// the non-empty parts of the container outside the grid, at most four: above, below, left and right of it.
+    /// together with the tiles they tile the container
+    pub margins: Vec<AxisAlignedRectangle<T>>,
+}
+
+impl<T> AxisAlignedRectangle<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps + PartialOrd + NumCast,
+{
+    /// tiling a rectangle with as many whole tiles of the size as fit, without scaling them.
+    /// the grid is centered (the right and bottom margins get the odd unit of integers).
+    /// a tile with a non-positive side fits nowhere, so the whole container is the margin
+    pub fn tile_grid(&self, tile: &Rectangle<T>) -> TileGrid<T> {
+        let count = |length: T, side: T| match side > T::zero() && length >= side {
+            true => (length / side).to_usize().unwrap_or(0),
+            false => 0,
+        };
+        let cols = count(self.width(), tile.width());
+        let rows = count(self.height(), tile.height());
+        let (rows, cols) = match rows == 0 || cols == 0 {
+            true => (0, 0),
+            false => (rows, cols),
+        };
+        let (Some(cols_t), Some(rows_t)) = (T::from(cols), T::from(rows)) else {
+            return TileGrid {
+                rows: 0,
+                cols: 0,
+                tiles: vec![],
+                margins: vec![self.clone()],
+            };
+        };
+        let two = T::one() + T::one();
+        let grid = AxisAlignedRectangle::new(
+            &Point::new(
+                self.x() + (self.width() - cols_t * tile.width()) / two,
+                self.y() + (self.height() - rows_t * tile.height()) / two,
+            ),
+            &Rectangle::new(cols_t * tile.width(), rows_t * tile.height()),
+        );
+        let mut tiles = Vec::with_capacity(rows * cols);
+        let mut y = grid.y();
+        for _ in 0..rows {
+            let mut x = grid.x();
+            for _ in 0..cols {
+                tiles.push(AxisAlignedRectangle::new(&Point::new(x, y), tile));
+                x += tile.width();
+            }
+            y += tile.height();
+        }
+        let margins = match tiles.is_empty() {
+            true => vec![self.clone()],
+            false => self.subtract(&grid),
+        };
+        TileGrid {
+            rows,
+            cols,
+            tiles,
+            margins,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::verify_tiling;
+    use crate::tolerance::Tolerance;
+
+    fn rect(x: i32, y: i32, width: i32, height: i32) -> AxisAlignedRectangle<i32> {
+        AxisAlignedRectangle::new(&Point::new(x, y), &Rectangle::new(width, height))
+    }
+
+    #[test]
+    fn test_tile_grid() {
+        let container = rect(0, 0, 11, 7);
+        let grid = container.tile_grid(&Rectangle::new(3, 2));
+        assert_eq!((grid.rows, grid.cols), (3, 3));
+        assert_eq!(grid.tiles.len(), 9);
+        assert_eq!(grid.tiles[0], rect(1, 0, 3, 2));
+        assert_eq!(grid.tiles[5], rect(7, 2, 3, 2));
+        assert_eq!(
+            grid.margins,
+            vec![rect(0, 6, 11, 1), rect(0, 0, 1, 6), rect(10, 0, 1, 6)]
+        );
+        let mut cells = grid.tiles.clone();
+        cells.extend(grid.margins.iter().cloned());
+        assert_eq!(
+            verify_tiling(&container, &cells, &Tolerance::exact()),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_tile_grid_float() {
+        let container =
+            AxisAlignedRectangle::new(&Point::new(10.0, 20.0), &Rectangle::new(100.0, 50.0));
+        let grid = container.tile_grid(&Rectangle::new(30.0, 25.0));
+        assert_eq!((grid.rows, grid.cols), (2, 3));
+        assert_eq!(grid.tiles[0].origin(), Point::new(15.0, 20.0));
+        assert_eq!(grid.margins.len(), 2);
+    }
+
+    #[test]
+    fn test_tile_grid_nothing_fits() {
+        let container = rect(0, 0, 10, 10);
+        for tile in [Rectangle::new(11, 1), Rectangle::new(1, 0)] {
+            let grid = container.tile_grid(&tile);
+            assert_eq!((grid.rows, grid.cols), (0, 0));
+            assert!(grid.tiles.is_empty());
+            assert_eq!(grid.margins, vec![container.clone()]);
+        }
+    }
+}
@@ -0,0 +1,212 @@
+use num_traits::{Num, NumAssignOps, NumOps};
+
+use crate::axis_aligned_rectangle::AxisAlignedRectangle;
+use crate::component::Component;
+use crate::point::Point;
+use crate::rectangle::{Rectangle, RectangleSize};
+
+/// How close two values have to be to count as equal: within `absolute`, or within `relative`
+/// times the larger magnitude, whichever is looser. a plain value converts to an absolute tolerance
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Tolerance<T> {
+    pub absolute: T,
+    pub relative: T,
+}
+
+impl<T> Tolerance<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps + PartialOrd,
+{
+    pub fn new(absolute: T, relative: T) -> Self {
+        Self { absolute, relative }
+    }
+
+    pub fn absolute(absolute: T) -> Self {
+        Self::new(absolute, T::zero())
+    }
+
+    pub fn relative(relative: T) -> Self {
+        Self::new(T::zero(), relative)
+    }
+
+    /// only exactly equal values are equal
+    pub fn exact() -> Self {
+        Self::new(T::zero(), T::zero())
+    }
+
+    pub fn approx_eq(&self, a: T, b: T) -> bool {
+        let abs = |v: T| match v < T::zero() {
+            true => T::zero() - v,
+            false => v,
+        };
+        let diff = abs(a - b);
+        let magnitude = match abs(a) > abs(b) {
+            true => abs(a),
+            false => abs(b),
+        };
+        diff <= self.absolute || diff <= self.relative * magnitude
+    }
+}
+
+impl<T> From<T> for Tolerance<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps + PartialOrd,
+{
+    fn from(absolute: T) -> Self {
+        Self::absolute(absolute)
+    }
+}
+
+/// e.g. `tolerance 0.01 absolute, 0 relative`
+impl<T> std::fmt::Display for Tolerance<T>
+where
+    T: std::fmt::Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "tolerance {} absolute, {} relative",
+            self.absolute, self.relative
+        )
+    }
+}
+
+/// The tolerance that fits the precision of a numeric type
+pub trait DefaultTolerance: Sized {
+    fn default_tolerance() -> Tolerance<Self>;
+}
+
+impl DefaultTolerance for f32 {
+    fn default_tolerance() -> Tolerance<Self> {
+        Tolerance {
+            absolute: 1e-4,
+            relative: 1e-5,
+        }
+    }
+}
+
+impl DefaultTolerance for f64 {
+    fn default_tolerance() -> Tolerance<Self> {
+        Tolerance {
+            absolute: 1e-9,
+            relative: 1e-9,
+        }
+    }
+}
+
+macro_rules! exact_default_tolerance {
+    ($($t:ty),*) => {
+        $(
+            impl DefaultTolerance for $t {
+                fn default_tolerance() -> Tolerance<Self> {
+                    Tolerance {
+                        absolute: 0,
+                        relative: 0,
+                    }
+                }
+            }
+        )*
+    };
+}
+
+exact_default_tolerance!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+#[cfg(feature = "decimal")]
+impl DefaultTolerance for rust_decimal::Decimal {
+    fn default_tolerance() -> Tolerance<Self> {
+        Tolerance {
+            absolute: rust_decimal::Decimal::new(1, 20),
+            relative: rust_decimal::Decimal::ZERO,
+        }
+    }
+}
+
+impl<T> Default for Tolerance<T>
+where
+    T: DefaultTolerance,
+{
+    fn default() -> Self {
+        T::default_tolerance()
+    }
+}
+
+/// Comparing geometry component-wise within a tolerance
+pub trait ApproxEq<T> {
+    fn approx_eq(&self, other: &Self, tolerance: &Tolerance<T>) -> bool;
+}
+
+impl<T> ApproxEq<T> for Point<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps + PartialOrd,
+{
+    fn approx_eq(&self, other: &Self, tolerance: &Tolerance<T>) -> bool {
+        tolerance.approx_eq(self.x(), other.x()) && tolerance.approx_eq(self.y(), other.y())
+    }
+}
+
+impl<T> ApproxEq<T> for Rectangle<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps + PartialOrd,
+{
+    fn approx_eq(&self, other: &Self, tolerance: &Tolerance<T>) -> bool {
+        tolerance.approx_eq(self.width(), other.width())
+            && tolerance.approx_eq(self.height(), other.height())
+    }
+}
+
+impl<T> ApproxEq<T> for AxisAlignedRectangle<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps + PartialOrd,
+{
+    fn approx_eq(&self, other: &Self, tolerance: &Tolerance<T>) -> bool {
+        self.origin().approx_eq(&other.origin(), tolerance)
+            && self.rect().approx_eq(&other.rect(), tolerance)
+    }
+}
+
+impl<T, A> ApproxEq<T> for [A]
+where
+    A: ApproxEq<T>,
+{
+    fn approx_eq(&self, other: &Self, tolerance: &Tolerance<T>) -> bool {
+        self.len() == other.len()
+            && self
+                .iter()
+                .zip(other.iter())
+                .all(|(a, b)| a.approx_eq(b, tolerance))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_approx_eq() {
+        let tolerance = Tolerance::new(0.01, 0.001);
+        assert!(tolerance.approx_eq(1.0, 1.005));
+        assert!(!tolerance.approx_eq(1.0, 1.02));
+        // relative for large magnitudes
+        assert!(tolerance.approx_eq(1000.0, 1000.9));
+        assert!(!tolerance.approx_eq(1000.0, 1002.0));
+        assert!(Tolerance::exact().approx_eq(3, 3));
+        assert!(!Tolerance::exact().approx_eq(3, 4));
+        assert!(Tolerance::from(1).approx_eq(3, 4));
+        assert!(Tolerance::default().approx_eq(0.1 + 0.2, 0.3));
+        assert!(!Tolerance::<f64>::default().approx_eq(0.1, 0.1001));
+        assert!(!Tolerance::<i32>::default().approx_eq(1, 2));
+    }
+
+    #[test]
+    fn test_approx_eq_geometry() {
+        let a = AxisAlignedRectangle::new(&Point::new(0.1 + 0.2, 1.0), &Rectangle::new(2.0, 3.0));
+        let b = AxisAlignedRectangle::new(&Point::new(0.3, 1.0), &Rectangle::new(2.0, 3.0));
+        let c = AxisAlignedRectangle::new(&Point::new(0.3, 1.0), &Rectangle::new(2.1, 3.0));
+        let tolerance = Tolerance::default();
+        assert!(a.approx_eq(&b, &tolerance));
+        assert!(!a.approx_eq(&c, &tolerance));
+        assert!(a.approx_eq(&c, &Tolerance::absolute(0.2)));
+        assert!([a.clone(), b.clone()][..].approx_eq(&[b.clone(), a.clone()], &tolerance));
+        assert!(!std::slice::from_ref(&a).approx_eq(&[a.clone(), b], &tolerance));
+    }
+}
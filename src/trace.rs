@@ -0,0 +1,64 @@
+//! Explaining the decisions the dividing algorithms make.
+
+/// What happened to a strip after a weight was picked into it
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum StripOutcome {
+    /// the first cell is not as wide (or tall) as the aspect ratio asks yet, so the strip takes more weights
+    Open,
+    /// the first cell reached the aspect ratio, so the strip is closed
+    ReachedAspectRatio,
+    /// there are no more weights, so the strip is closed as it is
+    OutOfWeights,
+}
+
+/// A decision of `Dividing::strip_groups`: a weight picked into a strip
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct StripDecision<T> {
+    /// the index of the weight picked
+    pub index: usize,
+    /// the strip (in order of the weights) it went to
+    pub strip: usize,
+    /// the aspect ratio (width / height) the first cell of the strip has with the weight picked
+    pub first_cell_aspect_ratio: T,
+    /// the aspect ratio the strip is closed at
+    pub target_aspect_ratio: T,
+    pub outcome: StripOutcome,
+}
+
+impl<T> std::fmt::Display for StripDecision<T>
+where
+    T: std::fmt::Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let outcome = match self.outcome {
+            StripOutcome::Open => "strip stays open",
+            StripOutcome::ReachedAspectRatio => "strip closed: aspect ratio reached",
+            StripOutcome::OutOfWeights => "strip closed: no more weights",
+        };
+        write!(
+            f,
+            "weight {} -> strip {}: first cell {} (target {}), {}",
+            self.index, self.strip, self.first_cell_aspect_ratio, self.target_aspect_ratio, outcome
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let decision = StripDecision {
+            index: 3,
+            strip: 1,
+            first_cell_aspect_ratio: 0.5,
+            target_aspect_ratio: 1.0,
+            outcome: StripOutcome::Open,
+        };
+        assert_eq!(
+            decision.to_string(),
+            "weight 3 -> strip 1: first cell 0.5 (target 1), strip stays open"
+        );
+    }
+}
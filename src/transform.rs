@@ -0,0 +1,205 @@
+use num_traits::{Num, NumAssignOps, NumOps};
+
+use crate::axis_aligned_rectangle::AxisAlignedRectangle;
+use crate::component::Component;
+use crate::layout::Layout;
+use crate::point::Point;
+use crate::rectangle::{Rectangle, RectangleSize};
+
+/// An affine transform that maps axis aligned rectangles to axis aligned rectangles:
+/// optionally swapping x and y, then scaling each axis (negative scales flip) and translating.
+/// `(x, y)` maps to `(scale_x * x + translate_x, scale_y * y + translate_y)`, after the swap
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Transform2D<T> {
+    pub swap: bool,
+    pub scale_x: T,
+    pub scale_y: T,
+    pub translate_x: T,
+    pub translate_y: T,
+}
+
+impl<T> Default for Transform2D<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps + PartialOrd,
+{
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+impl<T> Transform2D<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps + PartialOrd,
+{
+    pub fn identity() -> Self {
+        Self {
+            swap: false,
+            scale_x: T::one(),
+            scale_y: T::one(),
+            translate_x: T::zero(),
+            translate_y: T::zero(),
+        }
+    }
+
+    pub fn translate(x: T, y: T) -> Self {
+        Self {
+            translate_x: x,
+            translate_y: y,
+            ..Self::identity()
+        }
+    }
+
+    /// scaling around the origin
+    pub fn scale(x: T, y: T) -> Self {
+        Self {
+            scale_x: x,
+            scale_y: y,
+            ..Self::identity()
+        }
+    }
+
+    /// mirroring x around the origin
+    pub fn flip_x() -> Self {
+        Self::scale(T::zero() - T::one(), T::one())
+    }
+
+    /// mirroring y around the origin
+    pub fn flip_y() -> Self {
+        Self::scale(T::one(), T::zero() - T::one())
+    }
+
+    /// rotating a quarter turn around the origin, clockwise on screen (y pointing down):
+    /// `(x, y)` maps to `(-y, x)`
+    pub fn rotate_clockwise() -> Self {
+        Self {
+            swap: true,
+            scale_x: T::zero() - T::one(),
+            ..Self::identity()
+        }
+    }
+
+    /// the transform that applies this one and then the other one
+    pub fn then(&self, other: &Self) -> Self {
+        let (scale_x, scale_y) = match other.swap {
+            true => (self.scale_y, self.scale_x),
+            false => (self.scale_x, self.scale_y),
+        };
+        let translated = other.apply_point(&Point::new(self.translate_x, self.translate_y));
+        Self {
+            swap: self.swap != other.swap,
+            scale_x: other.scale_x * scale_x,
+            scale_y: other.scale_y * scale_y,
+            translate_x: translated.x(),
+            translate_y: translated.y(),
+        }
+    }
+
+    pub fn apply_point(&self, point: &Point<T>) -> Point<T> {
+        let (x, y) = match self.swap {
+            true => (point.y(), point.x()),
+            false => (point.x(), point.y()),
+        };
+        Point::new(
+            self.scale_x * x + self.translate_x,
+            self.scale_y * y + self.translate_y,
+        )
+    }
+
+    /// the rectangle spanned by the transformed corners
+    pub fn apply_rect(&self, rect: &AxisAlignedRectangle<T>) -> AxisAlignedRectangle<T> {
+        let a = self.apply_point(&rect.origin());
+        let b = self.apply_point(&Point::new(
+            rect.x() + rect.width(),
+            rect.y() + rect.height(),
+        ));
+        let (left, right) = match a.x() < b.x() {
+            true => (a.x(), b.x()),
+            false => (b.x(), a.x()),
+        };
+        let (top, bottom) = match a.y() < b.y() {
+            true => (a.y(), b.y()),
+            false => (b.y(), a.y()),
+        };
+        AxisAlignedRectangle::new(
+            &Point::new(left, top),
+            &Rectangle::new(right - left, bottom - top),
+        )
+    }
+}
+
+impl<T> Layout<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps + PartialOrd,
+{
+    /// apply the transform to every cell, e.g. for zooming and panning.
+    /// the cells keep their order; the split tree is not kept, since flips and rotations reorder its parts
+    pub fn transform(&self, transform: &Transform2D<T>) -> Self {
+        let transformed = Self::new(self.iter().map(|cell| transform.apply_rect(cell)).collect());
+        match self.strips() {
+            Some(strips) => transformed.with_strips(strips.to_vec()),
+            None => transformed,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(x: i32, y: i32, width: i32, height: i32) -> AxisAlignedRectangle<i32> {
+        AxisAlignedRectangle::new(&Point::new(x, y), &Rectangle::new(width, height))
+    }
+
+    #[test]
+    fn test_apply() {
+        let r = rect(1, 2, 3, 4);
+        assert_eq!(Transform2D::identity().apply_rect(&r), r);
+        assert_eq!(
+            Transform2D::translate(10, 20).apply_rect(&r),
+            rect(11, 22, 3, 4)
+        );
+        assert_eq!(Transform2D::scale(2, 3).apply_rect(&r), rect(2, 6, 6, 12));
+        assert_eq!(Transform2D::flip_x().apply_rect(&r), rect(-4, 2, 3, 4));
+        assert_eq!(Transform2D::flip_y().apply_rect(&r), rect(1, -6, 3, 4));
+        assert_eq!(
+            Transform2D::rotate_clockwise().apply_rect(&r),
+            rect(-6, 1, 4, 3)
+        );
+    }
+
+    #[test]
+    fn test_then() {
+        let r = rect(1, 2, 3, 4);
+        let transforms = [
+            Transform2D::translate(10, 20),
+            Transform2D::scale(2, 3),
+            Transform2D::flip_x(),
+            Transform2D::rotate_clockwise(),
+        ];
+        for a in &transforms {
+            for b in &transforms {
+                assert_eq!(a.then(b).apply_rect(&r), b.apply_rect(&a.apply_rect(&r)));
+            }
+        }
+        let full_turn = (0..4).fold(Transform2D::<i32>::identity(), |t, _| {
+            t.then(&Transform2D::rotate_clockwise())
+        });
+        assert_eq!(full_turn, Transform2D::identity());
+    }
+
+    #[test]
+    fn test_layout_transform() {
+        let layout = Layout::new(vec![rect(0, 0, 2, 4), rect(2, 0, 2, 4)]).with_strips(vec![0, 1]);
+        // zoom in 2x around (2, 2), then pan
+        let zoom = Transform2D::translate(-2, -2)
+            .then(&Transform2D::scale(2, 2))
+            .then(&Transform2D::translate(2, 2))
+            .then(&Transform2D::translate(1, 0));
+        let transformed = layout.transform(&zoom);
+        assert_eq!(
+            transformed.cells(),
+            &[rect(-1, -2, 4, 8), rect(3, -2, 4, 8)][..]
+        );
+        assert_eq!(transformed.strips(), Some(&[0, 1][..]));
+    }
+}
@@ -0,0 +1,241 @@
+//! Alternative layouts of the same weights, for users to pick from.
+use num_traits::{Float, Num, NumAssignOps, NumOps};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::area::Area;
+use crate::axis::Axis;
+use crate::axis_aligned_rectangle::AxisAlignedRectangle;
+use crate::dividing::Dividing;
+use crate::emission_order::EmissionOrder;
+use crate::layout::Layout;
+use crate::metrics::worst_weight_error;
+use crate::split_tree::SplitTree;
+
+/// How much a variant may differ from the strip layout
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Variation<T> {
+    /// how far each strip's thickness may change, as a fraction of it (e.g. 0.1 for up to 10%)
+    pub strip_jitter: T,
+    /// whether the emission order is picked at random too
+    pub vary_order: bool,
+    /// the largest weight error allowed (see `metrics::worst_weight_error`).
+    /// the jitter is halved until the variant is within it
+    pub max_weight_error: T,
+}
+
+/// A varied layout, with how it was made and the weight error it incurred
+#[derive(Debug, PartialEq, Clone)]
+pub struct Variant<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps,
+{
+    pub layout: Layout<T>,
+    pub order: EmissionOrder,
+    pub weight_error: T,
+}
+
+const ORDERS: [EmissionOrder; 4] = [
+    EmissionOrder::Forward,
+    EmissionOrder::Boustrophedon,
+    EmissionOrder::Reverse,
+    EmissionOrder::CenterOut,
+];
+
+/// the number of times the jitter is halved before giving up on it
+const ATTEMPTS: usize = 8;
+
+impl<T> Variation<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps + Float + for<'a> std::iter::Sum<&'a T>,
+{
+    /// a variant of the strip layout (see `Layout::divide_strips`) from the seed: the strips are grouped the same,
+    /// and their thicknesses are jittered. the same seed gives the same variant.
+    /// when no jitter keeps the error within the bound, the strips are left as they are
+    pub fn vary(
+        &self,
+        container: &AxisAlignedRectangle<T>,
+        weights: &[T],
+        primary_axis: Axis,
+        aspect_ratio: T,
+        order: EmissionOrder,
+        seed: u64,
+    ) -> Variant<T> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let order = match self.vary_order {
+            true => ORDERS[rng.gen_range(0..ORDERS.len())],
+            false => order,
+        };
+        let groups = container.strip_groups(weights, primary_axis, aspect_ratio);
+        let mut strips = vec![0; weights.len()];
+        for (n, group) in groups.iter().enumerate() {
+            for i in group {
+                strips[*i] = n;
+            }
+        }
+        let tree = SplitTree::strips(weights, &groups, primary_axis, order);
+        let offsets: Vec<T> = groups
+            .iter()
+            .map(|_| T::from(rng.gen_range(-1.0..=1.0)).unwrap_or(T::zero()))
+            .collect();
+        let two = T::one() + T::one();
+        let mut jitter = self.strip_jitter;
+        for attempt in 0..=ATTEMPTS {
+            if attempt == ATTEMPTS {
+                jitter = T::zero();
+            }
+            let layout = jittered(&tree, &offsets, jitter)
+                .materialize(container)
+                .with_strips(strips.clone());
+            let weight_error = worst_weight_error(layout.cells(), container.area(), weights);
+            if weight_error <= self.max_weight_error || jitter == T::zero() {
+                return Variant {
+                    layout,
+                    order,
+                    weight_error,
+                };
+            }
+            jitter /= two;
+        }
+        unreachable!("the last attempt has no jitter")
+    }
+
+    /// `count` variants from consecutive seeds, starting at `seed`
+    #[allow(clippy::too_many_arguments)]
+    pub fn variants(
+        &self,
+        container: &AxisAlignedRectangle<T>,
+        weights: &[T],
+        primary_axis: Axis,
+        aspect_ratio: T,
+        order: EmissionOrder,
+        seed: u64,
+        count: usize,
+    ) -> Vec<Variant<T>> {
+        (0..count as u64)
+            .map(|n| {
+                self.vary(
+                    container,
+                    weights,
+                    primary_axis,
+                    aspect_ratio,
+                    order,
+                    seed.wrapping_add(n),
+                )
+            })
+            .collect()
+    }
+}
+
+/// the tree with each strip (in position order) thicker or thinner by its offset times the jitter
+fn jittered<T>(tree: &SplitTree<T>, offsets: &[T], jitter: T) -> SplitTree<T>
+where
+    T: Copy + Num + NumAssignOps + NumOps + Float + for<'a> std::iter::Sum<&'a T>,
+{
+    let mut tree = tree.clone();
+    if let SplitTree::Cut { children, .. } = &mut tree {
+        for (child, offset) in children.iter_mut().zip(offsets.iter()) {
+            child.fraction *= T::one() + *offset * jitter;
+        }
+        let total: T = children.iter().map(|c| &c.fraction).sum();
+        for child in children.iter_mut() {
+            child.fraction /= total;
+        }
+    }
+    tree
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::point::Point;
+    use crate::rectangle::Rectangle;
+    use crate::testing::verify_tiling;
+    use crate::tolerance::Tolerance;
+
+    fn container() -> AxisAlignedRectangle<f64> {
+        AxisAlignedRectangle::new(&Point::new(0.0, 0.0), &Rectangle::new(120.0, 80.0))
+    }
+
+    const WEIGHTS: [f64; 8] = [5.0, 3.0, 3.0, 2.0, 2.0, 1.0, 1.0, 1.0];
+
+    #[test]
+    fn test_vary() {
+        let variation = Variation {
+            strip_jitter: 0.2,
+            vary_order: false,
+            max_weight_error: 0.02,
+        };
+        let variant = variation.vary(
+            &container(),
+            &WEIGHTS,
+            Axis::Vertical,
+            1.0,
+            EmissionOrder::Forward,
+            3,
+        );
+        assert!(variant.weight_error <= 0.02);
+        assert!(variant.weight_error > 0.0);
+        assert_eq!(variant.order, EmissionOrder::Forward);
+        assert_eq!(
+            verify_tiling(&container(), variant.layout.cells(), &Tolerance::default()),
+            Ok(())
+        );
+        let exact = Layout::divide_strips(
+            &container(),
+            &WEIGHTS,
+            Axis::Vertical,
+            1.0,
+            EmissionOrder::Forward,
+        );
+        assert_ne!(variant.layout.cells(), exact.cells());
+        assert_eq!(variant.layout.strips(), exact.strips());
+        // deterministic for the seed
+        let again = variation.vary(
+            &container(),
+            &WEIGHTS,
+            Axis::Vertical,
+            1.0,
+            EmissionOrder::Forward,
+            3,
+        );
+        assert_eq!(again, variant);
+    }
+
+    #[test]
+    fn test_variants() {
+        let variation = Variation {
+            strip_jitter: 0.1,
+            vary_order: true,
+            max_weight_error: 0.01,
+        };
+        let variants = variation.variants(
+            &container(),
+            &WEIGHTS,
+            Axis::Vertical,
+            1.0,
+            EmissionOrder::Forward,
+            0,
+            6,
+        );
+        assert_eq!(variants.len(), 6);
+        assert!(variants.iter().all(|v| v.weight_error <= 0.01));
+        assert!(variants.windows(2).any(|v| v[0].layout != v[1].layout));
+
+        // no error allowed: the strips are left exact
+        let strict = Variation {
+            strip_jitter: 0.1,
+            vary_order: false,
+            max_weight_error: 0.0,
+        };
+        let variant = strict.vary(
+            &container(),
+            &WEIGHTS,
+            Axis::Vertical,
+            1.0,
+            EmissionOrder::Forward,
+            0,
+        );
+        assert!(variant.weight_error < 1e-12);
+    }
+}
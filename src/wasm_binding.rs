@@ -1,11 +1,23 @@
+use crate::axis::Axis;
 use crate::axis_aligned_rectangle::AxisAlignedRectangle;
 use crate::component::Component;
+use crate::direction::StartCorner;
+use crate::direction::TextDirection;
 use crate::dividing::Dividing;
+use crate::emission_order::EmissionOrder;
+use crate::hierarchy::{
+    HierarchyCell, HierarchyLayout, HierarchyOptions, LayoutNode, NestedOptions,
+};
 use crate::point::Point;
 use crate::rectangle::{Rectangle, RectangleSize};
+#[cfg(feature = "wasm-threads")]
+use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 use serde_wasm_bindgen;
 use wasm_bindgen::prelude::*;
+/// `initThreadPool(navigator.hardwareConcurrency)` must be awaited in JavaScript before the batch and hierarchy APIs run on threads
+#[cfg(all(feature = "wasm-threads", target_arch = "wasm32"))]
+pub use wasm_bindgen_rayon::init_thread_pool;
 
 #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
 pub struct JSRect {
@@ -22,32 +34,196 @@ pub fn dividing(
     aspect_ratio: f32,
     vertical_first: bool,
     boustrophedron: bool,
+) -> Result<JsValue, JsValue> {
+    dividing_with_direction(
+        rect,
+        weights,
+        aspect_ratio,
+        vertical_first,
+        boustrophedron,
+        false,
+    )
+}
+
+/// same as `dividing`, but lays out right to left (the first weight is placed at the top right) when `rtl` is true
+#[wasm_bindgen(js_name = dividingWithDirection)]
+pub fn dividing_with_direction(
+    rect: JsValue,
+    weights: &[f32],
+    aspect_ratio: f32,
+    vertical_first: bool,
+    boustrophedron: bool,
+    rtl: bool,
+) -> Result<JsValue, JsValue> {
+    let Ok(rect) = serde_wasm_bindgen::from_value::<JSRect>(rect) else {
+        return Err(JsValue::from_str("failed to parse rect"));
+    };
+    let direction = match rtl {
+        true => TextDirection::Rtl,
+        false => TextDirection::Ltr,
+    };
+    let js_rects = divide_js_rect(
+        &rect,
+        weights,
+        aspect_ratio,
+        vertical_first,
+        boustrophedron,
+        direction.start_corner(),
+    );
+    serde_wasm_bindgen::to_value(&js_rects).map_err(|e| e.into())
+}
+
+/// A container and its weights, for `dividingBatch`
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct JSBatchItem {
+    pub rect: JSRect,
+    pub weights: Vec<f32>,
+}
+
+/// same as `dividing`, for many containers at once: `items` is an array of `{ rect, weights }`, and the result
+/// has the rects of each item. with the `wasm-threads` feature the items are divided on the thread pool
+/// (see `initThreadPool`). the call still blocks until the pool is done, so it must run from a Worker, not the main thread
+#[wasm_bindgen(js_name = dividingBatch)]
+pub fn dividing_batch(
+    items: JsValue,
+    aspect_ratio: f32,
+    vertical_first: bool,
+    boustrophedron: bool,
+) -> Result<JsValue, JsValue> {
+    let Ok(items) = serde_wasm_bindgen::from_value::<Vec<JSBatchItem>>(items) else {
+        return Err(JsValue::from_str("failed to parse items"));
+    };
+    let divided = divide_batch(&items, aspect_ratio, vertical_first, boustrophedron);
+    serde_wasm_bindgen::to_value(&divided).map_err(|e| e.into())
+}
+
+fn divide_batch(
+    items: &[JSBatchItem],
+    aspect_ratio: f32,
+    vertical_first: bool,
+    boustrophedron: bool,
+) -> Vec<Vec<JSRect>> {
+    let divide = |item: &JSBatchItem| {
+        divide_js_rect(
+            &item.rect,
+            &item.weights,
+            aspect_ratio,
+            vertical_first,
+            boustrophedron,
+            StartCorner::TopLeft,
+        )
+    };
+    #[cfg(feature = "wasm-threads")]
+    let divided = items.par_iter().map(divide).collect();
+    #[cfg(not(feature = "wasm-threads"))]
+    let divided = items.iter().map(divide).collect();
+    divided
+}
+
+/// A weighted tree for `dividingHierarchy`: `{ weight, children }`
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct JSLayoutNode {
+    pub weight: f32,
+    #[serde(default)]
+    pub children: Vec<JSLayoutNode>,
+}
+
+/// A cell of `dividingHierarchy`, with the cells of the children of its node in their order
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct JSHierarchyCell {
+    pub rect: JSRect,
+    pub depth: usize,
+    pub children: Vec<JSHierarchyCell>,
+}
+
+/// dividing a rectangle by a tree of `{ weight, children }` into strips at every level (see `divide_hierarchy`).
+/// with the `wasm-threads` feature the subtrees are divided on the thread pool (see `initThreadPool`).
+/// the call still blocks until the pool is done, so it must run from a Worker, not the main thread
+#[wasm_bindgen(js_name = dividingHierarchy)]
+pub fn dividing_hierarchy(
+    rect: JsValue,
+    root: JsValue,
+    aspect_ratio: f32,
+    padding_per_level: f32,
 ) -> Result<JsValue, JsValue> {
     let Ok(rect) = serde_wasm_bindgen::from_value::<JSRect>(rect) else {
         return Err(JsValue::from_str("failed to parse rect"));
     };
+    let Ok(root) = serde_wasm_bindgen::from_value::<JSLayoutNode>(root) else {
+        return Err(JsValue::from_str("failed to parse root"));
+    };
+    let divided = divide_js_hierarchy(&rect, &root, aspect_ratio, padding_per_level);
+    serde_wasm_bindgen::to_value(&divided).map_err(|e| e.into())
+}
+
+fn divide_js_hierarchy(
+    rect: &JSRect,
+    root: &JSLayoutNode,
+    aspect_ratio: f32,
+    padding_per_level: f32,
+) -> JSHierarchyCell {
+    fn node(js: &JSLayoutNode) -> LayoutNode<f32> {
+        LayoutNode::new(js.weight, js.children.iter().map(node).collect())
+    }
+    fn js_cell(cell: &HierarchyCell<f32>) -> JSHierarchyCell {
+        JSHierarchyCell {
+            rect: JSRect {
+                x: cell.rect.x(),
+                y: cell.rect.y(),
+                w: cell.rect.width(),
+                h: cell.rect.height(),
+            },
+            depth: cell.depth,
+            children: cell.children.iter().map(js_cell).collect(),
+        }
+    }
     let rect =
         AxisAlignedRectangle::new(&Point::new(rect.x, rect.y), &Rectangle::new(rect.w, rect.h));
-    let rects = match vertical_first {
-        true => {
-            rect.divide_vertical_then_horizontal_with_weights(weights, aspect_ratio, boustrophedron)
-        }
-        false => {
-            rect.divide_horizontal_then_vertical_with_weights(weights, aspect_ratio, boustrophedron)
-        }
+    let options = HierarchyOptions {
+        layout: HierarchyLayout::Strips,
+        aspect_ratio,
+        nested: NestedOptions {
+            padding_per_level,
+            header_height: None,
+        },
     };
+    let root = node(root);
+    #[cfg(feature = "wasm-threads")]
+    let divided = rect.par_divide_hierarchy(&root, &options);
+    #[cfg(not(feature = "wasm-threads"))]
+    let divided = rect.divide_hierarchy(&root, &options);
+    js_cell(&divided)
+}
 
-    let js_rects = rects
-        .iter()
-        .map(|rect| JSRect {
-            x: rect.x(),
-            y: rect.y(),
-            w: rect.width(),
-            h: rect.height(),
-        })
-        .collect::<Vec<_>>();
-
-    serde_wasm_bindgen::to_value(&js_rects).map_err(|e| e.into())
+fn divide_js_rect(
+    rect: &JSRect,
+    weights: &[f32],
+    aspect_ratio: f32,
+    vertical_first: bool,
+    boustrophedron: bool,
+    corner: StartCorner,
+) -> Vec<JSRect> {
+    let rect =
+        AxisAlignedRectangle::new(&Point::new(rect.x, rect.y), &Rectangle::new(rect.w, rect.h));
+    let primary_axis = match vertical_first {
+        true => Axis::Vertical,
+        false => Axis::Horizontal,
+    };
+    rect.divide_strips_from_corner(
+        weights,
+        primary_axis,
+        aspect_ratio,
+        EmissionOrder::from_boustrophedon(boustrophedron),
+        corner,
+    )
+    .iter()
+    .map(|rect| JSRect {
+        x: rect.x(),
+        y: rect.y(),
+        w: rect.width(),
+        h: rect.height(),
+    })
+    .collect()
 }
 
 #[cfg(test)]
@@ -91,4 +267,103 @@ mod tests {
             ]
         );
     }
+
+    #[wasm_bindgen_test]
+    fn test_rtl() {
+        let result = dividing_with_direction(
+            serde_wasm_bindgen::to_value(&JSRect {
+                x: 0.0,
+                y: 0.0,
+                w: 100.0,
+                h: 100.0,
+            })
+            .unwrap(),
+            &[1.0, 1.0],
+            0.5,
+            false,
+            false,
+            true,
+        )
+        .unwrap();
+        let result: Vec<JSRect> = serde_wasm_bindgen::from_value(result).unwrap();
+        assert_eq!(
+            result,
+            vec![
+                JSRect {
+                    x: 50.0,
+                    y: 0.0,
+                    w: 50.0,
+                    h: 100.0
+                },
+                JSRect {
+                    x: 0.0,
+                    y: 0.0,
+                    w: 50.0,
+                    h: 100.0
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn test_divide_batch() {
+        let rect = JSRect {
+            x: 0.0,
+            y: 0.0,
+            w: 100.0,
+            h: 100.0,
+        };
+        let items = vec![
+            JSBatchItem {
+                rect,
+                weights: vec![1.0, 1.0],
+            },
+            JSBatchItem {
+                rect,
+                weights: vec![1.0, 2.0, 1.0],
+            },
+        ];
+        let divided = divide_batch(&items, 1.0, true, false);
+        assert_eq!(divided.len(), 2);
+        for (item, rects) in items.iter().zip(divided.iter()) {
+            assert_eq!(
+                rects,
+                &divide_js_rect(&rect, &item.weights, 1.0, true, false, StartCorner::TopLeft)
+            );
+        }
+        assert_eq!(divided[0][1].y, 50.0);
+    }
+
+    #[test]
+    fn test_divide_js_hierarchy() {
+        let rect = JSRect {
+            x: 0.0,
+            y: 0.0,
+            w: 100.0,
+            h: 100.0,
+        };
+        let leaf = |weight| JSLayoutNode {
+            weight,
+            children: vec![],
+        };
+        let root = JSLayoutNode {
+            weight: 1.0,
+            children: vec![
+                JSLayoutNode {
+                    weight: 1.0,
+                    children: vec![leaf(1.0), leaf(1.0)],
+                },
+                leaf(1.0),
+            ],
+        };
+        let divided = divide_js_hierarchy(&rect, &root, 0.5, 0.0);
+        assert_eq!(divided.rect, rect);
+        assert_eq!(divided.children.len(), 2);
+        assert_eq!(divided.children[0].depth, 1);
+        let grandchildren = &divided.children[0].children;
+        assert_eq!(grandchildren.len(), 2);
+        assert_eq!(grandchildren[1].depth, 2);
+        let area: f32 = grandchildren.iter().map(|c| c.rect.w * c.rect.h).sum();
+        assert_eq!(area, 5000.0);
+    }
 }
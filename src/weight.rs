@@ -1,6 +1,11 @@
-use num_traits::{Num, NumAssignOps, NumOps};
+use std::cmp::Ordering;
+
+use num_traits::{Num, NumAssignOps, NumCast, NumOps};
 // weights are just Vec<T>
 
+/// how far percentages may sum from 100, in percentage points, e.g. for three thirds written as 33.33
+pub const PERCENTAGE_TOLERANCE: f64 = 0.1;
+
 pub(crate) fn normalize_weights<T>(weights: &[T]) -> Vec<T>
 where
     T: Copy + Num + NumAssignOps + NumOps + for<'a> std::iter::Sum<&'a T>,
@@ -9,6 +14,91 @@ where
     weights.iter().map(|w| *w / sum).collect()
 }
 
+/// sort weights by the comparator (stable), returning the sorted weights
+/// and the permutation: the n-th sorted weight was at `permutation[n]` in the original order
+pub fn sort_weights_by<T, F>(weights: &[T], mut compare: F) -> (Vec<T>, Vec<usize>)
+where
+    T: Copy,
+    F: FnMut(&T, &T) -> Ordering,
+{
+    let mut permutation: Vec<usize> = (0..weights.len()).collect();
+    permutation.sort_by(|a, b| compare(&weights[*a], &weights[*b]));
+    let sorted = permutation.iter().map(|i| weights[*i]).collect();
+    (sorted, permutation)
+}
+
+/// sort weights in descending order (larger weights first). see `sort_weights_by`
+pub fn sort_weights_descending<T>(weights: &[T]) -> (Vec<T>, Vec<usize>)
+where
+    T: Copy + PartialOrd,
+{
+    sort_weights_by(weights, |a, b| b.partial_cmp(a).unwrap_or(Ordering::Equal))
+}
+
+/// put items laid out in sorted order back into the original order of the weights
+pub fn restore_order<U>(items: &[U], permutation: &[usize]) -> Vec<U>
+where
+    U: Clone,
+{
+    let mut indexed: Vec<(usize, &U)> = permutation.iter().copied().zip(items.iter()).collect();
+    indexed.sort_by_key(|(i, _)| *i);
+    indexed.into_iter().map(|(_, item)| item.clone()).collect()
+}
+
+/// Why percentages can not be used as weights
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum PercentageError<T> {
+    /// the percentage is negative
+    Negative { index: usize, value: T },
+    /// the percentages sum too far from 100
+    Sum { sum: T },
+}
+
+impl<T> std::fmt::Display for PercentageError<T>
+where
+    T: std::fmt::Display,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PercentageError::Negative { index, value } => {
+                write!(f, "percentage {} is negative ({})", index, value)
+            }
+            PercentageError::Sum { sum } => write!(f, "the percentages sum to {}, not 100", sum),
+        }
+    }
+}
+
+impl<T> std::error::Error for PercentageError<T> where T: std::fmt::Debug + std::fmt::Display {}
+
+/// check that the percentages are not negative and sum to 100 within `PERCENTAGE_TOLERANCE`
+/// (exactly 100 for integers)
+pub fn check_percentages<T>(percentages: &[T]) -> Result<(), PercentageError<T>>
+where
+    T: Copy + Num + NumAssignOps + NumOps + NumCast + PartialOrd + for<'a> std::iter::Sum<&'a T>,
+{
+    if let Some((index, value)) = percentages
+        .iter()
+        .enumerate()
+        .find(|(_, p)| **p < T::zero())
+    {
+        return Err(PercentageError::Negative {
+            index,
+            value: *value,
+        });
+    }
+    let sum: T = percentages.iter().sum();
+    let hundred = T::from(100).unwrap_or_else(T::zero);
+    let tolerance = T::from(PERCENTAGE_TOLERANCE).unwrap_or_else(T::zero);
+    let difference = match sum > hundred {
+        true => sum - hundred,
+        false => hundred - sum,
+    };
+    match difference <= tolerance {
+        true => Ok(()),
+        false => Err(PercentageError::Sum { sum }),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -23,4 +113,52 @@ mod tests {
         let normalized = normalize_weights(&weights);
         assert_eq!(normalized, vec![0.1, 0.2, 0.3, 0.4]);
     }
+
+    #[test]
+    fn test_check_percentages() {
+        assert_eq!(check_percentages(&[33.33, 33.33, 33.34]), Ok(()));
+        assert_eq!(check_percentages(&[33.33, 33.33, 33.33]), Ok(()));
+        assert_eq!(
+            check_percentages(&[50.0, 40.0]),
+            Err(PercentageError::Sum { sum: 90.0 })
+        );
+        assert_eq!(
+            check_percentages(&[110, -10]),
+            Err(PercentageError::Negative {
+                index: 1,
+                value: -10
+            })
+        );
+        assert_eq!(
+            check_percentages::<i32>(&[]),
+            Err(PercentageError::Sum { sum: 0 })
+        );
+    }
+
+    #[test]
+    fn test_sort_weights_descending() {
+        let weights = vec![1.0, 4.0, 2.0, 4.0];
+        let (sorted, permutation) = sort_weights_descending(&weights);
+        assert_eq!(sorted, vec![4.0, 4.0, 2.0, 1.0]);
+        // stable: equal weights keep their original order
+        assert_eq!(permutation, vec![1, 3, 2, 0]);
+    }
+
+    #[test]
+    fn test_sort_weights_by() {
+        let weights = vec![3, 1, 2];
+        let (sorted, permutation) = sort_weights_by(&weights, |a, b| a.cmp(b));
+        assert_eq!(sorted, vec![1, 2, 3]);
+        assert_eq!(permutation, vec![1, 2, 0]);
+        for (w, i) in sorted.iter().zip(permutation.iter()) {
+            assert_eq!(*w, weights[*i]);
+        }
+    }
+
+    #[test]
+    fn test_restore_order() {
+        let weights = vec![1.0, 4.0, 2.0, 3.0];
+        let (sorted, permutation) = sort_weights_descending(&weights);
+        assert_eq!(restore_order(&sorted, &permutation), weights);
+    }
 }